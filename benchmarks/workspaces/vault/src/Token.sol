@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+contract Token {
+    string public name;
+    string public symbol;
+    uint8 public decimals;
+    uint256 public totalSupply;
+    address public owner;
+    bool public paused;
+    uint256 internal mintCap;
+    uint256 internal mintedToday;
+    uint256 internal lastMintDay;
+    uint256 internal unusedFeeBps;
+    address internal unusedTreasury;
+
+    mapping(address => uint256) public balanceOf;
+    mapping(address => mapping(address => uint256)) public allowance;
+    mapping(address => bool) internal blocked;
+
+    event Transfer(address indexed from, address indexed to, uint256 amount);
+    event Approval(address indexed owner, address indexed spender, uint256 amount);
+
+    constructor(string memory _name, string memory _symbol, uint256 _mintCap) {
+        name = _name;
+        symbol = _symbol;
+        decimals = 18;
+        owner = msg.sender;
+        mintCap = _mintCap;
+    }
+
+    modifier onlyOwner() {
+        require(msg.sender == owner, "not owner");
+        _;
+    }
+
+    function pause(bool _paused) external onlyOwner {
+        paused = _paused;
+    }
+
+    function block_(address account, bool status) external onlyOwner {
+        blocked[account] = status;
+    }
+
+    function mint(address to, uint256 amount) external onlyOwner {
+        if (block.timestamp / 1 days != lastMintDay) {
+            lastMintDay = block.timestamp / 1 days;
+            mintedToday = 0;
+        }
+        require(mintedToday + amount <= mintCap, "cap");
+        mintedToday += amount;
+        totalSupply += amount;
+        balanceOf[to] += amount;
+        emit Transfer(address(0), to, amount);
+    }
+
+    function approve(address spender, uint256 amount) external returns (bool) {
+        allowance[msg.sender][spender] = amount;
+        emit Approval(msg.sender, spender, amount);
+        return true;
+    }
+
+    function transfer(address to, uint256 amount) external returns (bool) {
+        _transfer(msg.sender, to, amount);
+        return true;
+    }
+
+    function transferFrom(address from, address to, uint256 amount) external returns (bool) {
+        allowance[from][msg.sender] -= amount;
+        _transfer(from, to, amount);
+        return true;
+    }
+
+    function _transfer(address from, address to, uint256 amount) internal {
+        require(!paused, "paused");
+        require(!blocked[from] && !blocked[to], "blocked");
+        balanceOf[from] -= amount;
+        balanceOf[to] += amount;
+        emit Transfer(from, to, amount);
+    }
+}
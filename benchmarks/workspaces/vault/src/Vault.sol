@@ -0,0 +1,93 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {Token} from "./Token.sol";
+
+contract Vault {
+    Token public immutable asset;
+    address public owner;
+    address public pendingOwner;
+    uint256 public totalShares;
+    uint256 public totalAssets;
+    uint256 public depositCap;
+    uint256 public withdrawalFeeBps;
+    uint256 internal accruedFees;
+    uint256 internal lastHarvest;
+    uint256 internal harvestCount;
+    uint256 internal unusedLockPeriod;
+    bool internal unusedEmergency;
+
+    mapping(address => uint256) public sharesOf;
+    mapping(address => uint256) internal lastDeposit;
+
+    struct Position {
+        uint256 shares;
+        uint256 depositedAt;
+    }
+
+    mapping(address => Position[]) internal positions;
+
+    constructor(Token _asset, uint256 _depositCap) {
+        asset = _asset;
+        owner = msg.sender;
+        depositCap = _depositCap;
+    }
+
+    modifier onlyOwner() {
+        require(msg.sender == owner, "not owner");
+        _;
+    }
+
+    function transferOwnership(address next) external onlyOwner {
+        pendingOwner = next;
+    }
+
+    function acceptOwnership() external {
+        require(msg.sender == pendingOwner, "not pending");
+        owner = pendingOwner;
+        pendingOwner = address(0);
+    }
+
+    function setWithdrawalFee(uint256 bps) external onlyOwner {
+        require(bps <= 1_000, "fee");
+        withdrawalFeeBps = bps;
+    }
+
+    function deposit(uint256 assets) external returns (uint256 shares) {
+        require(totalAssets + assets <= depositCap, "cap");
+        shares = totalShares == 0 ? assets : (assets * totalShares) / totalAssets;
+        totalShares += shares;
+        totalAssets += assets;
+        sharesOf[msg.sender] += shares;
+        lastDeposit[msg.sender] = block.timestamp;
+        positions[msg.sender].push(Position(shares, block.timestamp));
+        asset.transferFrom(msg.sender, address(this), assets);
+    }
+
+    function withdraw(uint256 shares) external returns (uint256 assets) {
+        assets = (shares * totalAssets) / totalShares;
+        uint256 fee = (assets * withdrawalFeeBps) / 10_000;
+        accruedFees += fee;
+        sharesOf[msg.sender] -= shares;
+        totalShares -= shares;
+        totalAssets -= assets;
+        asset.transfer(msg.sender, assets - fee);
+    }
+
+    function harvest() external onlyOwner {
+        for (uint256 i = 0; i < 10; i++) {
+            if (accruedFees == 0) {
+                break;
+            }
+            harvestCount++;
+        }
+        lastHarvest = block.timestamp;
+        uint256 fees = accruedFees;
+        accruedFees = 0;
+        asset.transfer(owner, fees);
+    }
+
+    function positionsOf(address account) external view returns (uint256) {
+        return positions[account].length;
+    }
+}
@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+contract Guarded {
+    bytes32 internal constant MINTER_ROLE = keccak256("MINTER");
+
+    address internal owner;
+    address internal admin;
+    mapping(bytes32 => mapping(address => bool)) internal roles;
+    bool internal initializing;
+    address internal immutable self = address(this);
+
+    error NotAdmin();
+
+    modifier onlyOwner() {
+        require(msg.sender == owner, "owner");
+        _;
+    }
+
+    modifier onlyRole(bytes32 role) {
+        _checkRole(role);
+        _;
+    }
+
+    modifier onlyInitializing() {
+        require(initializing, "not initializing");
+        _;
+    }
+
+    modifier onlyProxy() {
+        require(address(this) != self, "not a proxy");
+        _;
+    }
+
+    function hasRole(bytes32 role, address account) public view returns (bool) {
+        return roles[role][account];
+    }
+
+    function _checkRole(bytes32 role) internal view {
+        require(hasRole(role, msg.sender), "role");
+    }
+
+    function byModifier() external onlyOwner {}
+
+    function byRequire() external view {
+        require(msg.sender == admin, "admin");
+    }
+
+    function byRole() external onlyRole(MINTER_ROLE) {}
+
+    function byRevert() external view {
+        if (msg.sender != admin) revert NotAdmin();
+    }
+
+    function byConjunction(uint256 x) external view {
+        require(msg.sender == admin && x > 0, "admin");
+    }
+
+    function byRevertingRole() external view {
+        if (!hasRole(MINTER_ROLE, msg.sender)) revert NotAdmin();
+    }
+
+    // Nothing restricts who can call these
+    function unguarded() external {}
+
+    function alsoUnguarded(uint256 x) external pure returns (uint256) {
+        return x;
+    }
+
+    // Anyone passes when `x > 0`
+    function byDisjunction(uint256 x) external view {
+        require(msg.sender == admin || x > 0, "admin");
+    }
+
+    // Everyone but the admin passes
+    function byInequality() external view {
+        require(msg.sender != admin, "not admin");
+    }
+
+    // Reading a role does not restrict anything
+    function readsRole() external view returns (bool) {
+        return hasRole(MINTER_ROLE, msg.sender);
+    }
+
+    // The role of an arbitrary account, not of the caller
+    function checksOtherRole(address account) external view {
+        require(hasRole(MINTER_ROLE, account), "role");
+    }
+
+    // Named like guards, but neither modifier looks at the caller
+    function initialize() external onlyInitializing {}
+
+    function upgrade() external view onlyProxy {}
+}
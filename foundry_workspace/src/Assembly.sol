@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+contract Assembly {
+    uint256 internal counter;
+    address internal implementation;
+
+    function store(uint256 value) external {
+        assembly {
+            sstore(counter.slot, value)
+        }
+    }
+
+    function forward() external payable {
+        address target = implementation;
+        assembly {
+            calldatacopy(0, 0, calldatasize())
+            let ok := delegatecall(gas(), target, 0, calldatasize(), 0, 0)
+            returndatacopy(0, 0, returndatasize())
+            if iszero(ok) { revert(0, returndatasize()) }
+            return(0, returndatasize())
+        }
+    }
+
+    function deploy(bytes memory code, bytes32 salt) external returns (address deployed) {
+        assembly {
+            deployed := create2(0, add(code, 0x20), mload(code), salt)
+        }
+    }
+
+    function pureMath(uint256 a) external pure returns (uint256 b) {
+        // Opcodes named in comments and strings are not called
+        assembly {
+            // sstore(counter.slot, a)
+            mstore(0, "selfdestruct(0)")
+            b := add(a, 1)
+        }
+    }
+}
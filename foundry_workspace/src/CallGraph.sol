@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+library MathLib {
+    function double(uint256 x) internal pure returns (uint256) {
+        return x * 2;
+    }
+}
+
+abstract contract Parent {
+    uint256 internal value;
+
+    function _store(uint256 x) internal virtual {
+        value = x;
+    }
+}
+
+contract Caller is Parent {
+    using MathLib for uint256;
+
+    modifier checked() {
+        _check();
+        _;
+    }
+
+    function _check() internal view {
+        require(value < 1000, "too big");
+    }
+
+    function _store(uint256 x) internal override {
+        super._store(x.double());
+    }
+
+    // Reaches checked, _check, Caller._store, Parent._store and MathLib.double
+    function entry(uint256 x) external checked {
+        _store(x);
+    }
+
+    // Only reaches `entry` through an external call
+    function forward(Caller other) external {
+        other.entry(1);
+    }
+
+    function isolated() external pure returns (uint256) {
+        return 1;
+    }
+}
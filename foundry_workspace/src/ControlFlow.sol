@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+contract ControlFlow {
+    uint256 internal total;
+
+    function branches(uint256 x) external returns (uint256) {
+        if (x == 0) {
+            revert("zero");
+        } else if (x > 100) {
+            return 100;
+        }
+
+        for (uint256 i = 0; i < x; i++) {
+            if (i == 5) {
+                continue;
+            }
+            if (i == 50) {
+                break;
+            }
+            total += i;
+        }
+
+        while (total > 1000) {
+            total /= 2;
+        }
+
+        do {
+            total += 1;
+        } while (total < 10);
+
+        unchecked {
+            total += x;
+        }
+
+        return total;
+        total = 0; // <--- Unreachable statement
+    }
+
+    function tryCall(ControlFlow other) external returns (bool) {
+        try other.branches(1) returns (uint256 value) {
+            total = value;
+            return true;
+        } catch {
+            return false;
+        }
+    }
+}
@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+abstract contract Vault {
+    uint256 internal deposits;
+
+    // `_beforeDeposit()` runs GuardedVault's override when the deployed contract is a GuardedVault
+    function deposit() external {
+        _beforeDeposit();
+        deposits += 1;
+    }
+
+    function _beforeDeposit() internal virtual {}
+}
+
+contract GuardedVault is Vault {
+    address internal owner;
+
+    function _beforeDeposit() internal override {
+        _checkOwner();
+    }
+
+    function _checkOwner() internal view {
+        require(msg.sender == owner, "not owner");
+    }
+}
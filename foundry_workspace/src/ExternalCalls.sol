@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+interface IVault {
+    function deposit() external payable;
+    function balance() external view returns (uint256);
+}
+
+interface IOwned {
+    function owner() external view returns (address);
+}
+
+contract Registry {
+    address public admin;
+}
+
+library Math {
+    function double(uint256 x) public pure returns (uint256) {
+        return 2 * x;
+    }
+
+    function half(uint256 x) internal pure returns (uint256) {
+        return x / 2;
+    }
+}
+
+contract ExternalCalls {
+    using Math for uint256;
+
+    IVault internal vault;
+
+    function highLevel() external view returns (uint256) {
+        return vault.balance();
+    }
+
+    function highLevelWithValue() external payable {
+        vault.deposit{value: msg.value}();
+    }
+
+    function highLevelWithoutValue() external {
+        vault.deposit{value: 0}();
+    }
+
+    function getters(Registry registry, address owned) external view returns (address, address) {
+        return (registry.admin(), IOwned(owned).owner());
+    }
+
+    function lowLevel(address target, bytes calldata data) external {
+        (bool ok,) = target.call(data);
+        require(ok, "call");
+        (ok,) = target.delegatecall(data);
+        require(ok, "delegatecall");
+        (ok,) = target.staticcall(data);
+        require(ok, "staticcall");
+    }
+
+    function lowLevelWithValue(address target) external payable {
+        (bool ok,) = target.call{value: 1}("");
+        require(ok, "call");
+    }
+
+    function pay(address payable to) external {
+        to.transfer(1);
+        bool ok = to.send(1);
+        require(ok, "send");
+    }
+
+    function libraries(uint256 x) external pure returns (uint256) {
+        return Math.double(x) + x.double() + x.half();
+    }
+
+    function assemblyCalls(address target) external {
+        assembly {
+            // call(gas(), target, 1, 0, 0, 0, 0) in a comment is not a call
+            pop(call(gas(), target, 0, 0, 0, 0, 0))
+            pop(delegatecall(gas(), target, 0, 0, 0, 0))
+            pop(call(gas(), target, 1, 0, 0, 0, 0))
+        }
+    }
+}
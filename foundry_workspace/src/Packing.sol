@@ -0,0 +1,31 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+contract Packed {
+    struct Position {
+        uint128 size;
+        uint64 opened;
+        bool isLong;
+    }
+
+    uint256 internal constant MAX = 100;
+
+    uint128 internal a;
+    uint128 internal b;
+    uint256 internal c;
+    address internal owner;
+    bool internal paused;
+    Position internal position;
+    uint8[40] internal small;
+    mapping(address => uint256) internal balances;
+}
+
+// `a` and `c` could share a slot, as could `paused` and `owner`
+contract Unpacked {
+    uint128 internal a;
+    uint256 internal b;
+    uint128 internal c;
+    bool internal paused;
+    uint256 internal d;
+    address internal owner;
+}
@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+interface IToken {
+    function transfer(address to, uint256 amount) external returns (bool);
+}
+
+contract Payouts {
+    IToken internal token;
+    address[] internal payees;
+
+    function payAll(uint256 amount) external {
+        for (uint256 i = 0; i < payees.length; i++) {
+            token.transfer(payees[i], amount);
+        }
+    }
+
+    function payOne(address to, uint256 amount) external {
+        token.transfer(to, amount);
+    }
+
+    function _payLoop(uint256 amount) internal {
+        for (uint256 i = 0; i < payees.length; i++) {
+            token.transfer(payees[i], amount);
+        }
+    }
+}
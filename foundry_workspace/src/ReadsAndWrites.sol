@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+contract ReadsAndWrites {
+    struct Position {
+        uint256 amount;
+    }
+
+    // Assigned but never read, we expect the write-only detector to catch it
+    uint256 internal writeOnly; // <--- Write-only state variable
+
+    // Read but never assigned, we expect the never-written detector to catch it
+    uint256 internal neverWritten; // <--- Never written state variable
+
+    uint256 internal initialized = 1;
+    uint256 internal deleted;
+    uint256[] internal history;
+    mapping(address => Position) internal positions;
+    // Only accessed through a storage parameter, which both reads and writes it
+    mapping(address => Position) internal escrow;
+
+    function record(uint256 amount) external {
+        writeOnly = amount;
+        history.push(amount);
+        Position storage position = positions[msg.sender];
+        position.amount += amount;
+        delete deleted;
+    }
+
+    function lock(uint256 amount) external {
+        _add(escrow[msg.sender], amount);
+    }
+
+    function _add(Position storage position, uint256 amount) internal {
+        position.amount += amount;
+    }
+
+    function total() external view returns (uint256) {
+        return neverWritten + initialized + deleted + history.length + positions[msg.sender].amount;
+    }
+}
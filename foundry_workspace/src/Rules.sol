@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+// One hit and one miss for each rule of `rules/`
+contract Rules {
+    uint256 internal constant FEE = 1;
+    uint256 internal MAX_SUPPLY;
+    uint256 internal supply;
+
+    function _mint(uint256 amount) external {
+        _increase(amount);
+    }
+
+    function _increase(uint256 amount) internal {
+        require(supply + amount + FEE <= MAX_SUPPLY, "cap");
+        supply += amount;
+    }
+}
@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+contract StorageWrites {
+    struct Account {
+        uint256 balance;
+        uint256 nonce;
+    }
+
+    mapping(address => Account) internal accounts;
+    uint256 internal total;
+    uint256 internal calls;
+    uint256 internal readOnly;
+    mapping(address => Account) internal backups;
+
+    modifier counted() {
+        calls += 1;
+        _;
+    }
+
+    // Writes `accounts` through a storage pointer, `total` through an internal call and `calls`
+    // through its modifier
+    function deposit(uint256 amount) external counted {
+        Account storage account = accounts[msg.sender];
+        account.balance += amount;
+        _addToTotal(amount);
+    }
+
+    function _addToTotal(uint256 amount) internal {
+        total += amount + readOnly;
+    }
+
+    // Passing `accounts` to a storage parameter may write it
+    function bumpNonce() external {
+        _bump(accounts[msg.sender]);
+    }
+
+    function _bump(Account storage account) internal {
+        account.nonce += 1;
+    }
+
+    // Moving the pointer to `backups` writes neither, the write through it may hit both
+    function reset(bool fromBackup) external {
+        Account storage account = accounts[msg.sender];
+        if (fromBackup) {
+            account = backups[msg.sender];
+        }
+        account.nonce = 0;
+    }
+
+    // Taking a storage pointer without writing through it is only a read
+    function peek() external view returns (uint256) {
+        Account storage account = accounts[msg.sender];
+        return account.balance + total;
+    }
+}
@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+interface IERC20 {
+    function transferFrom(address from, address to, uint256 amount) external returns (bool);
+}
+
+contract Taint {
+    struct Order {
+        address owner;
+        uint256 amount;
+    }
+
+    IERC20 internal token;
+    uint256[] internal prices;
+    uint256 internal initialPrice;
+
+    // Constructor arguments come from the deployer, not from callers
+    constructor(IERC20 _token, uint256[] memory _prices, uint256 initial) {
+        token = _token;
+        prices = _prices;
+        initialPrice = prices[initial];
+    }
+
+    // `from` flows through a struct and an internal call into `transferFrom`
+    function pull(address from, uint256 amount) external {
+        Order memory order = Order(from, amount);
+        _pull(order.owner, order.amount);
+    }
+
+    function _pull(address owner, uint256 amount) internal {
+        token.transferFrom(owner, address(this), amount); // <--- Arbitrary `from`
+    }
+
+    // Pulling from the caller is fine
+    function pullOwn(uint256 amount) external {
+        token.transferFrom(msg.sender, address(this), amount);
+    }
+
+    // So is overwriting `from` with the caller, or checking that it is the caller, before the call
+    function pullAsCaller(address from, uint256 amount) external {
+        from = msg.sender;
+        token.transferFrom(from, address(this), amount);
+    }
+
+    function pullChecked(address from, uint256 amount) external {
+        require(from == msg.sender, "not the owner");
+        token.transferFrom(from, address(this), amount);
+    }
+
+    // Only one path overwrites `from`
+    function pullEither(address from, uint256 amount, bool own) external {
+        if (own) {
+            from = msg.sender;
+        }
+        token.transferFrom(from, address(this), amount); // <--- Arbitrary `from`
+    }
+
+    function forward(address target, bytes calldata data) external {
+        (bool ok,) = target.call(data);
+        require(ok, "call failed");
+    }
+
+    function price(uint256 i) external view returns (uint256) {
+        return prices[i];
+    }
+}
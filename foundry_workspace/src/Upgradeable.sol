@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+contract VaultV1 {
+    address internal owner;
+    uint256 internal totalDeposits;
+    mapping(address => uint256) internal balances;
+    uint256[47] private __gap;
+}
+
+// Breaks the storage of VaultV1: swapped `owner` / `totalDeposits`, retyped `balances`
+// and `fee` added without shrinking the gap
+/// @custom:oz-upgrades-from VaultV1
+contract VaultV2 {
+    uint256 internal totalDeposits;
+    address internal owner;
+    mapping(address => uint128) internal balances;
+    uint256 internal fee;
+    uint256[47] private __gap;
+}
+
+contract TokenV1 {
+    uint256 internal supply;
+    uint256[49] private __gap;
+}
+
+// Compatible with TokenV1, `minter` takes a slot from the gap. UpgradeableLegacy.sol has another
+// TokenV1, hence the path.
+/// @custom:oz-upgrades-from src/Upgradeable.sol:TokenV1
+contract TokenV2 {
+    uint256 internal supply;
+    address internal minter;
+    uint256[48] private __gap;
+}
+
+// Not declared as an upgrade of PoolV1, so their layouts are not compared
+contract PoolV1 {
+    uint256 internal reserve;
+}
+
+contract PoolV2 {
+    address internal router;
+}
+
+contract MisplacedGap {
+    uint256[50] private __gap;
+    uint256 internal value;
+}
@@ -0,0 +1,8 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+// Same name as the TokenV1 of Upgradeable.sol, with a layout TokenV2 is not compatible with
+contract TokenV1 {
+    address internal minter;
+    uint256 internal supply;
+}
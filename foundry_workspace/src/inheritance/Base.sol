@@ -0,0 +1,12 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+abstract contract Base {
+    // Only ever used by the child contract in Child.sol
+    uint256 internal sharedValue;
+
+    // This is an unused state variable that we expect the detector to catch
+    uint256 internal unusedInBase; // <--- Unused state variable
+
+    function _bump() internal virtual;
+}
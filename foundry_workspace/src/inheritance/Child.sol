@@ -0,0 +1,14 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {Base} from "./Base.sol";
+
+contract Child is Base {
+    function _bump() internal override {
+        sharedValue += 1;
+    }
+
+    function bump() external {
+        _bump();
+    }
+}
@@ -0,0 +1,21 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+// Linearization of D is [D, C, B, A]
+abstract contract A {
+    // Only used by the most derived contract
+    uint256 internal usedByD;
+}
+
+abstract contract B is A {}
+
+abstract contract C is A {}
+
+contract D is B, C {
+    // Internal variable in a leaf contract, nothing can inherit it to use it
+    uint256 internal unusedInLeaf; // <--- Unused state variable
+
+    function touch() external {
+        usedByD = 1;
+    }
+}
@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity >=0.7.0 <0.8.0;
+
+// Floating over compilers with known bugs, none of which emits PUSH0
+contract Legacy {
+    uint256 public value;
+
+    function set(uint256 newValue) external {
+        value = newValue;
+    }
+}
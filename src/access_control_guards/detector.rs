@@ -0,0 +1,394 @@
+// _   ___   _______ _   _  ________   __  _______   __  ___  ___  _________ _      _____
+// | \ | \ \ / /_   _| | | | | ___ \ \ / / |  ___\ \ / / / _ \ |  \/  || ___ \ |    |  ___|
+// |  \| |\ V /  | | | |_| | | |_/ /\ V /  | |__  \ V / / /_\ \| .  . || |_/ / |    | |__
+// | . ` | \ /   | | |  _  | | ___ \ \ /   |  __| /   \ |  _  || |\/| ||  __/| |    |  __|
+// | |\  | | |   | | | | | | | |_/ / | |   | |___/ /^\ \| | | || |  | || |   | |____| |___
+// \_| \_/ \_/   \_/ \_| |_/ \____/  \_/   \____/\/   \/\_| |_/\_|  |_/\_|   \_____/\____/
+// ______                     _     _       ______     _            _
+// | ___ \                   | |   | |      |  _  \   | |          | |
+// | |_/ /___ _   _ ___  __ _| |__ | | ___  | | | |___| |_ ___  ___| |_ ___  _ __
+// |    // _ \ | | / __|/ _` | '_ \| |/ _ \ | | | / _ \ __/ _ \/ __| __/ _ \| '__|
+// | |\ \  __/ |_| \__ \ (_| | |_) | |  __/ | |/ /  __/ ||  __/ (__| || (_) | |
+// \_| \_\___|\__,_|___/\__,_|_.__/|_|\___| |___/ \___|\__\___|\___|\__\___/|_|
+//
+// This ReusableDetector is responsible for classifying the access control guards of functions.
+// The `detect` function does this following:
+//
+//   1. Accept FunctionDefinition nodes via the `within` parameter.
+//   2. For each of those, look at its `only*` modifiers whose body checks the sender or its role, then at the body
+//      of the function and of everything it reaches internally (modifiers, internal functions), by using the
+//      ReusableDetector: `ReachableFunctionsDetector`.
+//   3. Record each guard found: `only*` modifiers, `require(msg.sender == X)` (possibly within a `&&`), role checks
+//      (`require(hasRole(R, msg.sender))`, `if (!hasRole(R, msg.sender)) revert`, `_checkRole`, `onlyRole`) and
+//      `if (msg.sender != X) revert`, with the state variable the sender / role is compared against.
+//   4. Add the functions without any guard to the `found_instances` vector.
+//   5. Return the `found_instances` vector.
+//
+// Use `guards()` after `detect` to get the guards of the protected functions.
+//
+
+use aderyn_driver::context::browser::{
+    ExtractFunctionCalls, ExtractIfStatements, ExtractRevertStatements,
+};
+use aderyn_driver::context::workspace_context::ASTNode;
+use aderyn_driver::context::workspace_context::WorkspaceContext;
+use aderyn_driver::core_ast::{Expression, FunctionCall, IfStatement, NodeID};
+use aderyn_driver::detector::ReusableDetector;
+
+use crate::analysis::call_graph::invoked_modifier;
+use crate::analysis::expressions::referenced_declaration;
+use crate::analysis::Analyses;
+use crate::reachable_functions::detector::ReachableFunctionsDetector;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GuardKind {
+    // `onlyOwner`, `onlyAdmin`, ...
+    OnlyModifier,
+    // `require(msg.sender == X)` / `assert(msg.sender == X)`
+    SenderEquality,
+    // `require(hasRole(R, msg.sender))`, `if (!hasRole(R, msg.sender)) revert`, `_checkRole(R)`, `onlyRole(R)`
+    RoleCheck,
+    // `if (msg.sender != X) revert ...`
+    SenderRevert,
+}
+
+#[derive(Clone, Debug)]
+pub struct Guard {
+    // FunctionDefinition being protected
+    pub function: NodeID,
+    pub kind: GuardKind,
+    // ModifierInvocation, FunctionCall or IfStatement implementing the guard
+    pub node: NodeID,
+    // State variable the sender (or role) is checked against, when it could be resolved
+    pub compared_against: Option<NodeID>,
+}
+
+#[derive(Default)]
+pub struct AccessControlGuardsDetector {
+    found_instances: Vec<ASTNode>,
+    guards: Vec<Guard>,
+}
+
+impl AccessControlGuardsDetector {
+    pub fn guards(&self) -> &[Guard] {
+        &self.guards
+    }
+
+    // Same as `detect`, reusing the call graph of `analyses` for every function of `within`
+    pub fn detect_with(
+        &mut self,
+        analyses: &Analyses,
+        _: &[ASTNode],
+        within: &[ASTNode],
+    ) -> Result<&[ASTNode], Box<dyn std::error::Error>> {
+        let context = analyses.context();
+        for node in within {
+            let ASTNode::FunctionDefinition(function) = node else {
+                continue;
+            };
+            let mut guards = vec![];
+            let mut handled_modifiers = vec![];
+
+            for invocation in &function.modifiers {
+                let Some(modifier_id) = invoked_modifier(invocation) else {
+                    continue;
+                };
+                let Some(ASTNode::ModifierDefinition(modifier)) = context.nodes.get(&modifier_id)
+                else {
+                    continue;
+                };
+                if !modifier.name.starts_with("only") {
+                    continue;
+                }
+                // The name alone proves nothing (`onlyInitializing`, `onlyProxy`, ...), the body must check
+                // the sender or its role. Otherwise the modifier is scanned with everything else it reaches.
+                let checks = scan(context, &ASTNode::ModifierDefinition(modifier.clone()));
+                if checks.is_empty() {
+                    continue;
+                }
+                let (kind, compared_against) = if modifier.name.contains("Role") {
+                    let role = invocation
+                        .arguments
+                        .as_ref()
+                        .and_then(|arguments| arguments.first())
+                        .and_then(|argument| state_variable(context, argument));
+                    (GuardKind::RoleCheck, role)
+                } else {
+                    let compared_against = checks
+                        .into_iter()
+                        .find_map(|(_, _, compared_against)| compared_against);
+                    (GuardKind::OnlyModifier, compared_against)
+                };
+                handled_modifiers.push(modifier_id);
+                guards.push((kind, invocation.id, compared_against));
+            }
+
+            let mut get_reachable = ReachableFunctionsDetector::default();
+            let reachable = get_reachable.detect_with(analyses, &[], std::slice::from_ref(node))?;
+            for callable in reachable {
+                let already_handled = match callable {
+                    ASTNode::ModifierDefinition(modifier) => {
+                        handled_modifiers.contains(&modifier.id)
+                    }
+                    _ => false,
+                };
+                if !already_handled {
+                    guards.extend(scan(context, callable));
+                }
+            }
+
+            if guards.is_empty() {
+                self.found_instances.push(node.clone());
+            }
+            for (kind, guard_node, compared_against) in guards {
+                self.guards.push(Guard {
+                    function: function.id,
+                    kind,
+                    node: guard_node,
+                    compared_against,
+                });
+            }
+        }
+
+        Ok(&self.found_instances)
+    }
+}
+
+impl ReusableDetector for AccessControlGuardsDetector {
+    fn detect(
+        &mut self,
+        context: &WorkspaceContext,
+        using: &[ASTNode],
+        within: &[ASTNode],
+    ) -> Result<&[ASTNode], Box<dyn std::error::Error>> {
+        self.detect_with(&Analyses::new(context), using, within)
+    }
+
+    fn name(&self) -> String {
+        "access-control-guards".to_string()
+    }
+}
+
+// Guards written inline in the body of a function or modifier
+fn scan(
+    context: &WorkspaceContext,
+    callable: &ASTNode,
+) -> Vec<(GuardKind, NodeID, Option<NodeID>)> {
+    let (function_calls, if_statements) = match callable {
+        ASTNode::FunctionDefinition(n) => (
+            ExtractFunctionCalls::from(n).extracted,
+            ExtractIfStatements::from(n).extracted,
+        ),
+        ASTNode::ModifierDefinition(n) => (
+            ExtractFunctionCalls::from(n).extracted,
+            ExtractIfStatements::from(n).extracted,
+        ),
+        _ => return vec![],
+    };
+
+    let mut guards = vec![];
+    for function_call in &function_calls {
+        match called_name(function_call) {
+            Some("require") | Some("assert") => {
+                let Some(condition) = function_call.arguments.first() else {
+                    continue;
+                };
+                // Every operand of a `&&` must hold, so any of them can be the guard
+                for operand in conjuncts(condition) {
+                    if let Some(compared_against) = sender_comparison(context, operand, "==") {
+                        guards.push((
+                            GuardKind::SenderEquality,
+                            function_call.id,
+                            compared_against,
+                        ));
+                    } else if let Some(role) = sender_role_check(context, operand) {
+                        guards.push((GuardKind::RoleCheck, function_call.id, role));
+                    }
+                }
+            }
+            Some("_checkRole") => {
+                let role = function_call
+                    .arguments
+                    .first()
+                    .and_then(|role| state_variable(context, role));
+                guards.push((GuardKind::RoleCheck, function_call.id, role));
+            }
+            _ => {}
+        }
+    }
+    for if_statement in &if_statements {
+        if !reverts(if_statement) {
+            continue;
+        }
+        let condition = &if_statement.condition;
+        if let Some(compared_against) = sender_comparison(context, condition, "!=") {
+            guards.push((GuardKind::SenderRevert, if_statement.id, compared_against));
+        } else if let Expression::UnaryOperation(negation) = condition {
+            // `if (!hasRole(R, msg.sender)) revert ...`
+            if negation.operator == "!" {
+                if let Some(role) = sender_role_check(context, &negation.sub_expression) {
+                    guards.push((GuardKind::RoleCheck, if_statement.id, role));
+                }
+            }
+        }
+    }
+    guards
+}
+
+fn called_name(function_call: &FunctionCall) -> Option<&str> {
+    match function_call.expression.as_ref() {
+        Expression::Identifier(identifier) => Some(identifier.name.as_str()),
+        Expression::MemberAccess(member_access) => Some(member_access.member_name.as_str()),
+        _ => None,
+    }
+}
+
+fn is_sender(expression: &Expression) -> bool {
+    match expression {
+        Expression::MemberAccess(member_access) => {
+            member_access.member_name == "sender"
+                && matches!(member_access.expression.as_ref(), Expression::Identifier(base) if base.name == "msg")
+        }
+        Expression::FunctionCall(function_call) => called_name(function_call) == Some("_msgSender"),
+        _ => false,
+    }
+}
+
+// `a && b && c` as `[a, b, c]`, any other condition as itself
+fn conjuncts(condition: &Expression) -> Vec<&Expression> {
+    match condition {
+        Expression::BinaryOperation(operation) if operation.operator == "&&" => {
+            let mut operands = conjuncts(&operation.left_expression);
+            operands.extend(conjuncts(&operation.right_expression));
+            operands
+        }
+        condition => vec![condition],
+    }
+}
+
+// When `condition` is `msg.sender <operator> X` (or `X <operator> msg.sender`), the state variable
+// the sender is compared against, if any
+fn sender_comparison(
+    context: &WorkspaceContext,
+    condition: &Expression,
+    operator: &str,
+) -> Option<Option<NodeID>> {
+    let Expression::BinaryOperation(operation) = condition else {
+        return None;
+    };
+    if operation.operator != operator {
+        return None;
+    }
+    let left = operation.left_expression.as_ref();
+    let right = operation.right_expression.as_ref();
+    if is_sender(left) {
+        Some(state_variable(context, right))
+    } else if is_sender(right) {
+        Some(state_variable(context, left))
+    } else {
+        None
+    }
+}
+
+// When `condition` is `hasRole(R, msg.sender)`, the state variable holding the role, if any
+fn sender_role_check(context: &WorkspaceContext, condition: &Expression) -> Option<Option<NodeID>> {
+    let Expression::FunctionCall(function_call) = condition else {
+        return None;
+    };
+    if called_name(function_call) != Some("hasRole")
+        || !function_call.arguments.get(1).is_some_and(is_sender)
+    {
+        return None;
+    }
+    Some(
+        function_call
+            .arguments
+            .first()
+            .and_then(|role| state_variable(context, role)),
+    )
+}
+
+fn state_variable(context: &WorkspaceContext, expression: &Expression) -> Option<NodeID> {
+    let declaration = referenced_declaration(expression)?;
+    match context.nodes.get(&declaration) {
+        Some(ASTNode::VariableDeclaration(variable)) if variable.state_variable => {
+            Some(declaration)
+        }
+        _ => None,
+    }
+}
+
+fn reverts(if_statement: &IfStatement) -> bool {
+    !ExtractRevertStatements::from(if_statement)
+        .extracted
+        .is_empty()
+        || ExtractFunctionCalls::from(if_statement)
+            .extracted
+            .iter()
+            .any(|function_call| called_name(function_call) == Some("revert"))
+}
+
+#[cfg(test)]
+mod access_control_guards_tests {
+
+    use crate::bot_utils::{load_contracts, NodeSelector};
+    use crate::config_tests::tests_configuration;
+
+    use super::{AccessControlGuardsDetector, GuardKind};
+
+    use aderyn_driver::core_ast::NodeType;
+    use aderyn_driver::detector::ReusableDetector;
+
+    #[test]
+    fn test_access_control_guards() {
+        let detector = AccessControlGuardsDetector::default();
+        let cases = tests_configuration().get_reusable_cases_for(detector.name());
+
+        for case in cases {
+            let mut detector = AccessControlGuardsDetector::default();
+            case.run(&mut detector);
+        }
+    }
+
+    #[test]
+    fn test_access_control_guard_kinds() {
+        let context =
+            load_contracts(&["./foundry_workspace/out/AccessControl.sol/Guarded.json".into()]);
+        let within = NodeSelector::kind(NodeType::FunctionDefinition).select(&context);
+
+        let mut detector = AccessControlGuardsDetector::default();
+        detector.detect(&context, &[], &within).unwrap();
+
+        let functions = context.function_definitions();
+        let kinds_of = |name: &str| {
+            let function = functions.iter().find(|f| f.name == name).unwrap();
+            detector
+                .guards()
+                .iter()
+                .filter(|g| g.function == function.id)
+                .map(|g| g.kind)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(kinds_of("byModifier"), vec![GuardKind::OnlyModifier]);
+        assert_eq!(kinds_of("byRequire"), vec![GuardKind::SenderEquality]);
+        assert!(kinds_of("byRole").contains(&GuardKind::RoleCheck));
+        assert_eq!(kinds_of("byRevert"), vec![GuardKind::SenderRevert]);
+        assert_eq!(kinds_of("byConjunction"), vec![GuardKind::SenderEquality]);
+        assert_eq!(kinds_of("byRevertingRole"), vec![GuardKind::RoleCheck]);
+        assert!(kinds_of("initialize").is_empty());
+        assert!(kinds_of("upgrade").is_empty());
+
+        // `onlyOwner` compares the sender against `owner`
+        let owner = context
+            .variable_declarations()
+            .into_iter()
+            .find(|v| v.name == "owner")
+            .unwrap()
+            .id;
+        assert!(detector
+            .guards()
+            .iter()
+            .any(|g| g.kind == GuardKind::OnlyModifier && g.compared_against == Some(owner)));
+    }
+}
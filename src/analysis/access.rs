@@ -0,0 +1,266 @@
+use std::collections::{BTreeSet, HashMap};
+
+use aderyn_driver::context::workspace_context::{ASTNode, WorkspaceContext};
+use aderyn_driver::core_ast::{
+    Assignment, ContractKind, Expression, FunctionCall, FunctionDefinition, MemberAccess, NodeID,
+    NodeType, StorageLocation,
+};
+
+use super::expressions::{expression_id, referenced_declaration};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessKind {
+    Read,
+    Write,
+    ReadWrite,
+    // Pointing a storage pointer elsewhere (`s = y`), which accesses no storage
+    Rebind,
+}
+
+impl AccessKind {
+    pub fn is_read(&self) -> bool {
+        matches!(self, AccessKind::Read | AccessKind::ReadWrite)
+    }
+
+    pub fn is_write(&self) -> bool {
+        matches!(self, AccessKind::Write | AccessKind::ReadWrite)
+    }
+}
+
+// Classifies a reference (Identifier / MemberAccess node id) to a variable as a read, a write
+// or both by looking at the expressions enclosing it:
+//
+//   x = 1, delete x, x.push(1), x[i].y = 1           -> Write
+//   x += 1, x++, x.pop()                              -> ReadWrite
+//   anything else                                     -> Read
+//
+// Passing `x` to a storage parameter (`f(x)` with `f(S storage)`, `x.f()` with a `using for` function
+// taking storage) is a Write: the callee may write through it, even when its body is not in the
+// workspace. Taking a local storage pointer (`S storage s = x`) only reads `x`, writes made through the
+// pointer are references to the pointer, which `storage_aliases` maps back to `x`. Rebinding a local
+// storage pointer (`s = y`) is neither.
+//
+pub fn classify_access(context: &WorkspaceContext, reference: NodeID) -> AccessKind {
+    let mut current = reference;
+
+    // Writing to an element, a field or a tuple component writes the variable itself
+    loop {
+        match context.get_parent(current) {
+            Some(ASTNode::IndexAccess(index_access))
+                if expression_id(&index_access.base_expression) == Some(current) =>
+            {
+                current = index_access.id;
+            }
+            Some(ASTNode::MemberAccess(member_access))
+                if expression_id(&member_access.expression) == Some(current) =>
+            {
+                let called = is_called(context, member_access.id);
+                match member_access.member_name.as_str() {
+                    "push" if called => return AccessKind::Write,
+                    "pop" if called => return AccessKind::ReadWrite,
+                    "length" => return AccessKind::Read,
+                    _ if called && passed_as_storage_pointer(context, current) => {
+                        return AccessKind::Write
+                    }
+                    _ if called => return AccessKind::Read,
+                    _ => current = member_access.id,
+                }
+            }
+            Some(ASTNode::TupleExpression(tuple)) => current = tuple.id,
+            _ => break,
+        }
+    }
+
+    match context.get_parent(current) {
+        Some(ASTNode::Assignment(assignment))
+            if expression_id(&assignment.left_hand_side) == Some(reference)
+                && rebinds_storage_pointer(context, assignment) =>
+        {
+            AccessKind::Rebind
+        }
+        Some(ASTNode::Assignment(assignment))
+            if expression_id(&assignment.left_hand_side) == Some(current) =>
+        {
+            match assignment.operator.as_str() {
+                "=" => AccessKind::Write,
+                _ => AccessKind::ReadWrite,
+            }
+        }
+        Some(ASTNode::FunctionCall(_)) if passed_as_storage_pointer(context, current) => {
+            AccessKind::Write
+        }
+        Some(ASTNode::UnaryOperation(operation)) => match operation.operator.as_str() {
+            "delete" => AccessKind::Write,
+            "++" | "--" => AccessKind::ReadWrite,
+            _ => AccessKind::Read,
+        },
+        _ => AccessKind::Read,
+    }
+}
+
+fn is_called(context: &WorkspaceContext, callee: NodeID) -> bool {
+    match context.get_parent(callee) {
+        Some(ASTNode::FunctionCall(function_call)) => {
+            expression_id(&function_call.expression) == Some(callee)
+        }
+        _ => false,
+    }
+}
+
+// Whether `expression` is an argument (or the receiver of a bound call) passed to a storage parameter
+fn passed_as_storage_pointer(context: &WorkspaceContext, expression: NodeID) -> bool {
+    let function_call = match context.get_parent(expression) {
+        Some(ASTNode::FunctionCall(function_call)) => function_call,
+        Some(ASTNode::MemberAccess(member_access)) => match context.get_parent(member_access.id) {
+            Some(ASTNode::FunctionCall(function_call)) => function_call,
+            _ => return false,
+        },
+        _ => return false,
+    };
+    storage_arguments(context, function_call)
+        .iter()
+        .any(|(argument, _)| expression_id(argument) == Some(expression))
+}
+
+// `s = y` where `s` is a local storage pointer or a storage parameter
+fn rebinds_storage_pointer(context: &WorkspaceContext, assignment: &Assignment) -> bool {
+    if assignment.operator != "=" {
+        return false;
+    }
+    let Expression::Identifier(identifier) = assignment.left_hand_side.as_ref() else {
+        return false;
+    };
+    matches!(
+        context.nodes.get(&identifier.referenced_declaration),
+        Some(ASTNode::VariableDeclaration(variable))
+            if !variable.state_variable && variable.storage_location == StorageLocation::Storage
+    )
+}
+
+// Local storage pointers and storage parameters -> state variables they may point into:
+//
+//   S storage s = map[k]; S storage t = s;             s, t -> map
+//   S storage s = a[k]; s = b[k];                      s -> a, b
+//   f(map[k]) with `f(S storage p)`                     p -> map
+//   map[k].f() with `using L for S` and `f(S storage p)` p -> map
+//
+// A parameter bound to several state variables by different call sites maps to all of them.
+pub fn storage_aliases(context: &WorkspaceContext) -> HashMap<NodeID, BTreeSet<NodeID>> {
+    // (alias, declaration of the expression it is bound to)
+    let mut bindings = vec![];
+    for statement in context.variable_declaration_statements() {
+        let Some(root) = statement
+            .initial_value
+            .as_ref()
+            .and_then(referenced_declaration)
+        else {
+            continue;
+        };
+        for declaration in statement.declarations.iter().flatten() {
+            if declaration.storage_location == StorageLocation::Storage {
+                bindings.push((declaration.id, root));
+            }
+        }
+    }
+    for assignment in context.assignments() {
+        if !rebinds_storage_pointer(context, assignment) {
+            continue;
+        }
+        let pointer = referenced_declaration(&assignment.left_hand_side);
+        let root = referenced_declaration(&assignment.right_hand_side);
+        if let (Some(pointer), Some(root)) = (pointer, root) {
+            bindings.push((pointer, root));
+        }
+    }
+    for function_call in context.function_calls() {
+        for (argument, parameter) in storage_arguments(context, function_call) {
+            if let Some(root) = referenced_declaration(argument) {
+                bindings.push((parameter, root));
+            }
+        }
+    }
+
+    // Pointers can be made from pointers, so bindings are followed until nothing changes
+    let mut aliases: HashMap<NodeID, BTreeSet<NodeID>> = HashMap::new();
+    loop {
+        let mut changed = false;
+        for (alias, root) in &bindings {
+            let targets = if is_state_variable(context, *root) {
+                BTreeSet::from([*root])
+            } else {
+                aliases.get(root).cloned().unwrap_or_default()
+            };
+            let entry = aliases.entry(*alias).or_default();
+            for target in targets {
+                changed |= entry.insert(target);
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    aliases.retain(|_, targets| !targets.is_empty());
+    aliases
+}
+
+pub fn is_state_variable(context: &WorkspaceContext, declaration: NodeID) -> bool {
+    matches!(
+        context.nodes.get(&declaration),
+        Some(ASTNode::VariableDeclaration(variable)) if variable.state_variable
+    )
+}
+
+// Expressions `function_call` passes to storage parameters, with those parameters. The receiver of a
+// bound call `x.f()` is passed to the first parameter.
+fn storage_arguments<'a>(
+    context: &WorkspaceContext,
+    function_call: &'a FunctionCall,
+) -> Vec<(&'a Expression, NodeID)> {
+    let Some(function) = callee(context, function_call) else {
+        return vec![];
+    };
+    let mut arguments = vec![];
+    if let Expression::MemberAccess(member_access) = function_call.expression.as_ref() {
+        if is_bound_call(context, member_access) {
+            arguments.push(member_access.expression.as_ref());
+        }
+    }
+    arguments.extend(function_call.arguments.iter());
+    arguments
+        .into_iter()
+        .zip(function.parameters.parameters.iter())
+        .filter(|(_, parameter)| parameter.storage_location == StorageLocation::Storage)
+        .map(|(argument, parameter)| (argument, parameter.id))
+        .collect()
+}
+
+fn callee<'a>(
+    context: &'a WorkspaceContext,
+    function_call: &FunctionCall,
+) -> Option<&'a FunctionDefinition> {
+    let callee = match function_call.expression.as_ref() {
+        Expression::Identifier(identifier) => Some(identifier.referenced_declaration),
+        Expression::MemberAccess(member_access) => member_access.referenced_declaration,
+        _ => None,
+    };
+    match callee.and_then(|id| context.nodes.get(&id)) {
+        Some(ASTNode::FunctionDefinition(function)) => Some(function),
+        _ => None,
+    }
+}
+
+// `x.f()` calling a `using L for T` function, as opposed to `L.f(x)` or `this.f()`
+fn is_bound_call(context: &WorkspaceContext, member_access: &MemberAccess) -> bool {
+    let base = referenced_declaration(&member_access.expression);
+    let base_is_contract = matches!(
+        base.and_then(|id| context.nodes.get(&id)),
+        Some(ASTNode::ContractDefinition(_))
+    );
+    let callee_in_library = matches!(
+        member_access
+            .referenced_declaration
+            .and_then(|id| context.get_closest_parent(id, NodeType::ContractDefinition)),
+        Some(ASTNode::ContractDefinition(contract)) if contract.kind == ContractKind::Library
+    );
+    !base_is_contract && callee_in_library
+}
@@ -0,0 +1,296 @@
+use std::collections::{BTreeSet, HashMap};
+
+use aderyn_driver::context::workspace_context::{ASTNode, WorkspaceContext};
+use aderyn_driver::core_ast::{
+    ContractKind, Expression, FunctionCall, ModifierInvocation, NodeID, NodeType,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallKind {
+    // `f()` to a function of the same contract or of a base contract
+    Internal,
+    // `f()` landing on an override of the called function, in a contract deriving from the caller's
+    Virtual,
+    // `onlyOwner` in a function header
+    Modifier,
+    // `L.f()` or `x.f()` with `using L for T`
+    Library,
+    // `super.f()`
+    Super,
+    // `other.f()`, `this.f()`: leaves the current execution context
+    External,
+}
+
+#[derive(Clone, Debug)]
+pub struct CallEdge {
+    // FunctionDefinition or ModifierDefinition making the call
+    pub caller: NodeID,
+    // FunctionDefinition or ModifierDefinition being called
+    pub callee: NodeID,
+    // FunctionCall or ModifierInvocation node
+    pub call_site: NodeID,
+    pub kind: CallKind,
+}
+
+// Workspace wide call graph resolved from FunctionCall and ModifierInvocation nodes.
+//
+// Query it through `Analyses::call_graph()` so that it is only computed once per context.
+#[derive(Default, Debug)]
+pub struct CallGraph {
+    edges: Vec<CallEdge>,
+    by_caller: HashMap<NodeID, Vec<usize>>,
+    by_callee: HashMap<NodeID, Vec<usize>>,
+}
+
+impl CallGraph {
+    pub fn build(context: &WorkspaceContext) -> Self {
+        let mut graph = CallGraph::default();
+        let overrides = Overrides::build(context);
+
+        for function_call in context.function_calls() {
+            let Some(caller) = enclosing_callable(context, function_call.id) else {
+                continue;
+            };
+            let Some((callee, kind)) = resolve_call(context, function_call) else {
+                continue;
+            };
+            graph.add(CallEdge {
+                caller,
+                callee,
+                call_site: function_call.id,
+                kind,
+            });
+
+            // `f()` runs the most derived override of `f` of the contract being executed, which may
+            // be any contract deriving from the caller's
+            if kind == CallKind::Internal && is_dispatched(function_call) {
+                for target in overrides.dispatch(context, function_call.id, callee) {
+                    graph.add(CallEdge {
+                        caller,
+                        callee: target,
+                        call_site: function_call.id,
+                        kind: CallKind::Virtual,
+                    });
+                }
+            }
+        }
+
+        for function_definition in context.function_definitions() {
+            for invocation in &function_definition.modifiers {
+                let Some(modifier) = invoked_modifier(invocation) else {
+                    continue;
+                };
+                // Base constructor calls (`constructor() Base(1)`) are modifier invocations too
+                if let Some(ASTNode::ModifierDefinition(_)) = context.nodes.get(&modifier) {
+                    graph.add(CallEdge {
+                        caller: function_definition.id,
+                        callee: modifier,
+                        call_site: invocation.id,
+                        kind: CallKind::Modifier,
+                    });
+                }
+            }
+        }
+
+        graph
+    }
+
+    fn add(&mut self, edge: CallEdge) {
+        let index = self.edges.len();
+        self.by_caller.entry(edge.caller).or_default().push(index);
+        self.by_callee.entry(edge.callee).or_default().push(index);
+        self.edges.push(edge);
+    }
+
+    pub fn edges(&self) -> &[CallEdge] {
+        &self.edges
+    }
+
+    pub fn callees(&self, caller: NodeID) -> Vec<&CallEdge> {
+        self.by_caller
+            .get(&caller)
+            .map(|indexes| indexes.iter().map(|i| &self.edges[*i]).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn callers(&self, callee: NodeID) -> Vec<&CallEdge> {
+        self.by_callee
+            .get(&callee)
+            .map(|indexes| indexes.iter().map(|i| &self.edges[*i]).collect())
+            .unwrap_or_default()
+    }
+
+    // Every function / modifier reachable from `entries` (entries included). External edges are
+    // only followed when `follow_external` is set.
+    pub fn reachable_from(&self, entries: &[NodeID], follow_external: bool) -> BTreeSet<NodeID> {
+        let mut reachable = entries.iter().copied().collect::<BTreeSet<_>>();
+        let mut stack = entries.to_vec();
+        while let Some(caller) = stack.pop() {
+            for edge in self.callees(caller) {
+                if edge.kind == CallKind::External && !follow_external {
+                    continue;
+                }
+                if reachable.insert(edge.callee) {
+                    stack.push(edge.callee);
+                }
+            }
+        }
+        reachable
+    }
+}
+
+// Functions directly overriding each function (from their `baseFunctions`)
+struct Overrides(HashMap<NodeID, Vec<NodeID>>);
+
+impl Overrides {
+    fn build(context: &WorkspaceContext) -> Self {
+        let mut overrides: HashMap<NodeID, Vec<NodeID>> = HashMap::new();
+        for function in context.function_definitions() {
+            for base in function.base_functions.iter().flatten() {
+                overrides.entry(*base).or_default().push(function.id);
+            }
+        }
+        Self(overrides)
+    }
+
+    // `function` and every function overriding it, transitively
+    fn family(&self, function: NodeID) -> BTreeSet<NodeID> {
+        let mut family = BTreeSet::from([function]);
+        let mut pending = vec![function];
+        while let Some(current) = pending.pop() {
+            for overriding in self.0.get(&current).into_iter().flatten() {
+                if family.insert(*overriding) {
+                    pending.push(*overriding);
+                }
+            }
+        }
+        family
+    }
+
+    // Implementations of `callee` other than itself that the call at `call_site` may run: for every
+    // contract whose linearization contains the caller's contract, the first function of the family
+    // found walking that linearization from the most derived contract
+    fn dispatch(
+        &self,
+        context: &WorkspaceContext,
+        call_site: NodeID,
+        callee: NodeID,
+    ) -> BTreeSet<NodeID> {
+        let family = self.family(callee);
+        if family.len() == 1 {
+            return BTreeSet::new();
+        }
+        let Some(ASTNode::ContractDefinition(caller_contract)) =
+            context.get_closest_parent(call_site, NodeType::ContractDefinition)
+        else {
+            return BTreeSet::new();
+        };
+
+        // Contract declaring each member of the family
+        let declared_in = family
+            .iter()
+            .filter_map(|function| {
+                match context.get_closest_parent(*function, NodeType::ContractDefinition) {
+                    Some(ASTNode::ContractDefinition(contract)) => Some((contract.id, *function)),
+                    _ => None,
+                }
+            })
+            .collect::<HashMap<_, _>>();
+
+        context
+            .contract_definitions()
+            .into_iter()
+            .filter(|c| {
+                c.linearized_base_contracts
+                    .as_ref()
+                    .is_some_and(|bases| bases.contains(&caller_contract.id))
+            })
+            .filter_map(|c| {
+                c.linearized_base_contracts
+                    .iter()
+                    .flatten()
+                    .find_map(|base| declared_in.get(base).copied())
+            })
+            .filter(|target| *target != callee)
+            .collect()
+    }
+}
+
+// Plain `f()` calls are dispatched virtually, `Base.f()` and `super.f()` are not
+fn is_dispatched(function_call: &FunctionCall) -> bool {
+    let callee = match function_call.expression.as_ref() {
+        Expression::FunctionCallOptions(options) => options.expression.as_ref(),
+        callee => callee,
+    };
+    matches!(callee, Expression::Identifier(_))
+}
+
+pub fn invoked_modifier(invocation: &ModifierInvocation) -> Option<NodeID> {
+    invocation.modifier_name.referenced_declaration
+}
+
+// FunctionDefinition or ModifierDefinition containing `node`
+pub fn enclosing_callable(context: &WorkspaceContext, node: NodeID) -> Option<NodeID> {
+    if let Some(ASTNode::FunctionDefinition(function)) =
+        context.get_closest_parent(node, NodeType::FunctionDefinition)
+    {
+        return Some(function.id);
+    }
+    if let Some(ASTNode::ModifierDefinition(modifier)) =
+        context.get_closest_parent(node, NodeType::ModifierDefinition)
+    {
+        return Some(modifier.id);
+    }
+    None
+}
+
+fn resolve_call(
+    context: &WorkspaceContext,
+    function_call: &FunctionCall,
+) -> Option<(NodeID, CallKind)> {
+    // `x.f{value: v}()` wraps the callee in call options
+    let callee = match function_call.expression.as_ref() {
+        Expression::FunctionCallOptions(options) => options.expression.as_ref(),
+        callee => callee,
+    };
+
+    let (declaration, kind) = match callee {
+        Expression::Identifier(identifier) => {
+            (identifier.referenced_declaration, CallKind::Internal)
+        }
+        Expression::MemberAccess(member_access) => {
+            let declaration = member_access.referenced_declaration?;
+            let kind = match member_access.expression.as_ref() {
+                Expression::Identifier(base) if base.name == "super" => CallKind::Super,
+                _ if is_library_function(context, declaration) => CallKind::Library,
+                // `Base.f()` jumps internally to the base implementation
+                Expression::Identifier(base)
+                    if base
+                        .type_descriptions
+                        .type_string
+                        .as_ref()
+                        .is_some_and(|t| t.starts_with("type(contract ")) =>
+                {
+                    CallKind::Internal
+                }
+                _ => CallKind::External,
+            };
+            (declaration, kind)
+        }
+        _ => return None,
+    };
+
+    match context.nodes.get(&declaration) {
+        Some(ASTNode::FunctionDefinition(_)) | Some(ASTNode::ModifierDefinition(_)) => {
+            Some((declaration, kind))
+        }
+        _ => None,
+    }
+}
+
+fn is_library_function(context: &WorkspaceContext, function: NodeID) -> bool {
+    matches!(
+        context.get_closest_parent(function, NodeType::ContractDefinition),
+        Some(ASTNode::ContractDefinition(contract)) if contract.kind == ContractKind::Library
+    )
+}
@@ -0,0 +1,481 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use aderyn_driver::context::workspace_context::WorkspaceContext;
+use aderyn_driver::core_ast::{
+    Block, BlockOrStatement, Expression, FunctionDefinition, ModifierDefinition, NodeID, NodeType,
+    Statement,
+};
+
+use super::expressions::expression_id;
+
+pub type BlockId = usize;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockKind {
+    Entry,
+    // Normal end of the function (falling off the body or `return`)
+    Exit,
+    // End of the function through `revert` / `revert(...)`
+    Revert,
+    Body,
+    LoopHeader,
+    Unchecked,
+    TryClause,
+}
+
+#[derive(Clone, Debug)]
+pub struct BasicBlock {
+    pub id: BlockId,
+    pub kind: BlockKind,
+    // Ids of the statements (and loop conditions) executed in this block, in order
+    pub statements: Vec<NodeID>,
+}
+
+// Control flow graph of a single function or modifier body, made of basic blocks
+#[derive(Clone, Debug)]
+pub struct Cfg {
+    pub function: NodeID,
+    pub blocks: Vec<BasicBlock>,
+    pub entry: BlockId,
+    pub exit: BlockId,
+    pub revert: BlockId,
+    edges: BTreeSet<(BlockId, BlockId)>,
+}
+
+impl Cfg {
+    pub fn from_function(context: &WorkspaceContext, function: &FunctionDefinition) -> Self {
+        Self::from_body(context, function.id, function.body.as_ref())
+    }
+
+    pub fn from_modifier(context: &WorkspaceContext, modifier: &ModifierDefinition) -> Self {
+        Self::from_body(context, modifier.id, Some(&modifier.body))
+    }
+
+    fn from_body(context: &WorkspaceContext, function: NodeID, body: Option<&Block>) -> Self {
+        let source = context
+            .nodes
+            .get(&function)
+            .and_then(|node| context.get_source_unit_from_child_node(node))
+            .and_then(|source_unit| source_unit.source.as_deref());
+        let mut builder = Builder {
+            source,
+            ..Builder::default()
+        };
+        let entry = builder.new_block(BlockKind::Entry);
+        let exit = builder.new_block(BlockKind::Exit);
+        let revert = builder.new_block(BlockKind::Revert);
+        builder.exit = exit;
+        builder.revert = revert;
+
+        let end = match body {
+            Some(body) => builder.visit_statements(&body.statements, Some(entry)),
+            None => Some(entry),
+        };
+        if let Some(end) = end {
+            builder.edge(end, exit);
+        }
+
+        Self {
+            function,
+            blocks: builder.blocks,
+            entry,
+            exit,
+            revert,
+            edges: builder.edges,
+        }
+    }
+
+    pub fn successors(&self, block: BlockId) -> Vec<BlockId> {
+        self.edges
+            .iter()
+            .filter(|(from, _)| *from == block)
+            .map(|(_, to)| *to)
+            .collect()
+    }
+
+    pub fn predecessors(&self, block: BlockId) -> Vec<BlockId> {
+        self.edges
+            .iter()
+            .filter(|(_, to)| *to == block)
+            .map(|(from, _)| *from)
+            .collect()
+    }
+
+    // Block executing the given statement
+    pub fn block_of(&self, statement: NodeID) -> Option<BlockId> {
+        self.blocks
+            .iter()
+            .find(|b| b.statements.contains(&statement))
+            .map(|b| b.id)
+    }
+
+    // Blocks holding statements that can never be reached from the entry
+    pub fn unreachable_blocks(&self) -> Vec<BlockId> {
+        let mut reachable = BTreeSet::from([self.entry]);
+        let mut stack = vec![self.entry];
+        while let Some(block) = stack.pop() {
+            for successor in self.successors(block) {
+                if reachable.insert(successor) {
+                    stack.push(successor);
+                }
+            }
+        }
+        self.blocks
+            .iter()
+            .filter(|b| !reachable.contains(&b.id) && !b.statements.is_empty())
+            .map(|b| b.id)
+            .collect()
+    }
+
+    // Graphviz representation, statements are labeled with their node type and id
+    pub fn to_dot(&self, context: &WorkspaceContext) -> String {
+        let mut dot = format!("digraph cfg_{} {{\n    node [shape=box];\n", self.function);
+        for block in &self.blocks {
+            let statements = block
+                .statements
+                .iter()
+                .map(|id| match context.nodes.get(id) {
+                    Some(node) => format!("{:?} #{}", node.node_type(), id),
+                    None => format!("#{}", id),
+                })
+                .collect::<Vec<_>>();
+            let mut label = format!("{} {:?}", block.id, block.kind);
+            for statement in statements {
+                label.push_str("\\n");
+                label.push_str(&statement);
+            }
+            _ = writeln!(dot, "    {} [label=\"{}\"];", block.id, label);
+        }
+        for (from, to) in &self.edges {
+            _ = writeln!(dot, "    {} -> {};", from, to);
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[derive(Default)]
+struct Builder<'a> {
+    // Source of the body. The AST gives plain and `unchecked` blocks the same shape, and `while` and
+    // `do ... while` loops too, so they are told apart by their first keyword.
+    source: Option<&'a str>,
+    blocks: Vec<BasicBlock>,
+    edges: BTreeSet<(BlockId, BlockId)>,
+    exit: BlockId,
+    revert: BlockId,
+    // (continue target, break target) of the enclosing loops
+    loops: Vec<(BlockId, BlockId)>,
+}
+
+impl Builder<'_> {
+    fn new_block(&mut self, kind: BlockKind) -> BlockId {
+        let id = self.blocks.len();
+        self.blocks.push(BasicBlock {
+            id,
+            kind,
+            statements: vec![],
+        });
+        id
+    }
+
+    fn edge(&mut self, from: BlockId, to: BlockId) {
+        self.edges.insert((from, to));
+    }
+
+    fn push(&mut self, block: BlockId, statement: Option<NodeID>) {
+        if let Some(statement) = statement {
+            self.blocks[block].statements.push(statement);
+        }
+    }
+
+    // Whether the source of the node at `src` starts with `keyword`
+    fn starts_with(&self, src: &str, keyword: &str) -> bool {
+        let start = src
+            .split(':')
+            .next()
+            .and_then(|start| start.parse::<usize>().ok());
+        match (self.source, start) {
+            (Some(source), Some(start)) => source
+                .get(start..)
+                .is_some_and(|text| text.starts_with(keyword)),
+            _ => false,
+        }
+    }
+
+    // Returns the block control falls through to, None when it never does (ex: after `return`)
+    fn visit_statements(
+        &mut self,
+        statements: &[Statement],
+        mut current: Option<BlockId>,
+    ) -> Option<BlockId> {
+        for statement in statements {
+            // Code after a terminator gets its own block without predecessors
+            let block = current.unwrap_or_else(|| self.new_block(BlockKind::Body));
+            current = self.visit_statement(statement, block);
+        }
+        current
+    }
+
+    fn visit_body(&mut self, body: &BlockOrStatement, current: BlockId) -> Option<BlockId> {
+        match body {
+            BlockOrStatement::Block(block) => {
+                self.visit_statements(&block.statements, Some(current))
+            }
+            BlockOrStatement::Statement(statement) => self.visit_statement(statement, current),
+        }
+    }
+
+    fn visit_statement(&mut self, statement: &Statement, current: BlockId) -> Option<BlockId> {
+        match statement {
+            Statement::UncheckedBlock(block) if !self.starts_with(&block.src, "unchecked") => {
+                self.visit_statements(&block.statements, Some(current))
+            }
+            Statement::IfStatement(if_statement) => {
+                self.push(current, Some(if_statement.id));
+
+                let true_block = self.new_block(BlockKind::Body);
+                self.edge(current, true_block);
+                let true_end = self.visit_body(&if_statement.true_body, true_block);
+
+                let false_end = match &if_statement.false_body {
+                    Some(false_body) => {
+                        let false_block = self.new_block(BlockKind::Body);
+                        self.edge(current, false_block);
+                        self.visit_body(false_body, false_block)
+                    }
+                    None => Some(current),
+                };
+
+                self.join(&[true_end, false_end])
+            }
+            Statement::WhileStatement(do_while_statement)
+                if self.starts_with(&do_while_statement.src, "do") =>
+            {
+                let body = self.new_block(BlockKind::Body);
+                self.edge(current, body);
+
+                let header = self.new_block(BlockKind::LoopHeader);
+                self.push(header, Some(do_while_statement.id));
+                let after = self.new_block(BlockKind::Body);
+                self.edge(header, body);
+                self.edge(header, after);
+
+                self.loops.push((header, after));
+                if let Some(end) = self.visit_body(&do_while_statement.body, body) {
+                    self.edge(end, header);
+                }
+                self.loops.pop();
+
+                Some(after)
+            }
+            Statement::WhileStatement(while_statement) => {
+                let header = self.new_block(BlockKind::LoopHeader);
+                self.edge(current, header);
+                self.push(header, Some(while_statement.id));
+
+                let after = self.new_block(BlockKind::Body);
+                self.edge(header, after);
+
+                let body = self.new_block(BlockKind::Body);
+                self.edge(header, body);
+                self.loops.push((header, after));
+                if let Some(end) = self.visit_body(&while_statement.body, body) {
+                    self.edge(end, header);
+                }
+                self.loops.pop();
+
+                Some(after)
+            }
+            Statement::ForStatement(for_statement) => {
+                let current = match &for_statement.initialization_expression {
+                    Some(initialization) => self.visit_statement(initialization, current)?,
+                    None => current,
+                };
+
+                let header = self.new_block(BlockKind::LoopHeader);
+                self.edge(current, header);
+                self.push(header, Some(for_statement.id));
+
+                let after = self.new_block(BlockKind::Body);
+                if for_statement.condition.is_some() {
+                    self.edge(header, after);
+                }
+
+                let update = self.new_block(BlockKind::Body);
+                if let Some(loop_expression) = &for_statement.loop_expression {
+                    self.push(update, loop_expression.get_node_id());
+                }
+                self.edge(update, header);
+
+                let body = self.new_block(BlockKind::Body);
+                self.edge(header, body);
+                self.loops.push((update, after));
+                if let Some(end) = self.visit_body(&for_statement.body, body) {
+                    self.edge(end, update);
+                }
+                self.loops.pop();
+
+                Some(after)
+            }
+            Statement::UncheckedBlock(unchecked_block) => {
+                let unchecked = self.new_block(BlockKind::Unchecked);
+                self.edge(current, unchecked);
+                let end = self.visit_statements(&unchecked_block.statements, Some(unchecked))?;
+
+                let after = self.new_block(BlockKind::Body);
+                self.edge(end, after);
+                Some(after)
+            }
+            Statement::TryStatement(try_statement) => {
+                // The statement itself has no id in the AST, the call stands for it
+                self.push(current, Some(try_statement.external_call.id));
+
+                let mut ends = vec![];
+                for clause in &try_statement.clauses {
+                    let clause_block = self.new_block(BlockKind::TryClause);
+                    self.edge(current, clause_block);
+                    ends.push(self.visit_statements(&clause.block.statements, Some(clause_block)));
+                }
+
+                self.join(&ends)
+            }
+            Statement::Return(return_statement) => {
+                self.push(current, Some(return_statement.id));
+                self.edge(current, self.exit);
+                None
+            }
+            Statement::RevertStatement(revert_statement) => {
+                self.push(current, Some(revert_statement.error_call.id));
+                self.edge(current, self.revert);
+                None
+            }
+            Statement::ExpressionStatement(expression_statement)
+                if is_revert_call(&expression_statement.expression) =>
+            {
+                self.push(current, expression_id(&expression_statement.expression));
+                self.edge(current, self.revert);
+                None
+            }
+            Statement::UnhandledStatement {
+                node_type: NodeType::Break,
+                id,
+                ..
+            } => {
+                self.push(current, *id);
+                if let Some((_, break_target)) = self.loops.last() {
+                    self.edge(current, *break_target);
+                }
+                None
+            }
+            Statement::UnhandledStatement {
+                node_type: NodeType::Continue,
+                id,
+                ..
+            } => {
+                self.push(current, *id);
+                if let Some((continue_target, _)) = self.loops.last() {
+                    self.edge(current, *continue_target);
+                }
+                None
+            }
+            other => {
+                self.push(current, other.get_node_id());
+                Some(current)
+            }
+        }
+    }
+
+    // Block where the given branches meet again, None when none of them falls through
+    fn join(&mut self, ends: &[Option<BlockId>]) -> Option<BlockId> {
+        let ends = ends.iter().flatten().copied().collect::<Vec<_>>();
+        if ends.is_empty() {
+            return None;
+        }
+        let join = self.new_block(BlockKind::Body);
+        for end in ends {
+            self.edge(end, join);
+        }
+        Some(join)
+    }
+}
+
+// `revert(...)` used as a function rather than a statement
+pub fn is_revert_call(expression: &Expression) -> bool {
+    match expression {
+        Expression::FunctionCall(function_call) => matches!(
+            function_call.expression.as_ref(),
+            Expression::Identifier(identifier) if identifier.name == "revert"
+        ),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod cfg_tests {
+
+    use crate::bot_utils::load_contracts;
+
+    use super::{BlockKind, Cfg};
+
+    fn cfg_of(
+        name: &str,
+    ) -> (
+        aderyn_driver::context::workspace_context::WorkspaceContext,
+        Cfg,
+    ) {
+        let context =
+            load_contracts(&["./foundry_workspace/out/ControlFlow.sol/ControlFlow.json".into()]);
+        let function = context
+            .function_definitions()
+            .into_iter()
+            .find(|f| f.name == name)
+            .unwrap()
+            .clone();
+        let cfg = Cfg::from_function(&context, &function);
+        (context, cfg)
+    }
+
+    #[test]
+    fn test_cfg_branches_and_loops() {
+        let (context, cfg) = cfg_of("branches");
+
+        // `revert("zero")`, both `return`s and the unreachable tail falling off the body
+        assert_eq!(cfg.predecessors(cfg.revert).len(), 1);
+        assert_eq!(cfg.predecessors(cfg.exit).len(), 3);
+
+        // for, while and do-while each get a header branching into the body or out of the loop
+        let headers = cfg
+            .blocks
+            .iter()
+            .filter(|b| b.kind == BlockKind::LoopHeader)
+            .collect::<Vec<_>>();
+        assert_eq!(headers.len(), 3);
+        assert!(headers.iter().all(|h| cfg.successors(h.id).len() == 2));
+
+        assert_eq!(
+            cfg.blocks
+                .iter()
+                .filter(|b| b.kind == BlockKind::Unchecked)
+                .count(),
+            1
+        );
+
+        // `total = 0` after the last return
+        assert_eq!(cfg.unreachable_blocks().len(), 1);
+
+        assert!(cfg.to_dot(&context).starts_with("digraph"));
+    }
+
+    #[test]
+    fn test_cfg_try_catch() {
+        let (_, cfg) = cfg_of("tryCall");
+
+        let clauses = cfg.successors(cfg.entry);
+        assert_eq!(clauses.len(), 2);
+        assert!(clauses
+            .iter()
+            .all(|c| cfg.blocks[*c].kind == BlockKind::TryClause));
+        assert!(cfg.unreachable_blocks().is_empty());
+    }
+}
@@ -0,0 +1,64 @@
+use aderyn_driver::core_ast::{Expression, Identifier, NodeID};
+
+// Id of the node wrapped by an `Expression`
+pub fn expression_id(expression: &Expression) -> Option<NodeID> {
+    match expression {
+        Expression::Literal(e) => Some(e.id),
+        Expression::Identifier(e) => Some(e.id),
+        Expression::UnaryOperation(e) => Some(e.id),
+        Expression::BinaryOperation(e) => Some(e.id),
+        Expression::Conditional(e) => Some(e.id),
+        Expression::Assignment(e) => Some(e.id),
+        Expression::FunctionCall(e) => Some(e.id),
+        Expression::FunctionCallOptions(e) => Some(e.id),
+        Expression::IndexAccess(e) => Some(e.id),
+        Expression::IndexRangeAccess(e) => Some(e.id),
+        Expression::MemberAccess(e) => Some(e.id),
+        Expression::ElementaryTypeNameExpression(e) => Some(e.id),
+        Expression::TupleExpression(e) => Some(e.id),
+        Expression::NewExpression(e) => Some(e.id),
+        _ => None,
+    }
+}
+
+// Declaration an expression ultimately points to (ex: `x`, `this.x`, `x[i].y` -> `x`)
+pub fn referenced_declaration(expression: &Expression) -> Option<NodeID> {
+    match expression {
+        Expression::Identifier(identifier) => Some(identifier.referenced_declaration),
+        Expression::MemberAccess(member_access) => match member_access.expression.as_ref() {
+            // `this.x`, `super.x`, `Contract.x`, `Library.x`
+            Expression::Identifier(base) if is_contract_like(base) => {
+                member_access.referenced_declaration
+            }
+            base => referenced_declaration(base).or(member_access.referenced_declaration),
+        },
+        Expression::IndexAccess(index_access) => {
+            referenced_declaration(&index_access.base_expression)
+        }
+        Expression::IndexRangeAccess(index_range_access) => {
+            referenced_declaration(&index_range_access.base_expression)
+        }
+        _ => None,
+    }
+}
+
+// Type of the value an expression evaluates to (ex: `uint256[] storage ref`), when solc recorded it
+pub fn type_string(expression: &Expression) -> Option<&str> {
+    let type_descriptions = match expression {
+        Expression::Identifier(e) => &e.type_descriptions,
+        Expression::MemberAccess(e) => &e.type_descriptions,
+        Expression::IndexAccess(e) => &e.type_descriptions,
+        Expression::FunctionCall(e) => &e.type_descriptions,
+        _ => return None,
+    };
+    type_descriptions.type_string.as_deref()
+}
+
+fn is_contract_like(identifier: &Identifier) -> bool {
+    identifier.type_descriptions.type_string.as_ref().is_some_and(|t| {
+        t.starts_with("contract ")
+            || t.starts_with("type(contract ")
+            || t.starts_with("type(library ")
+            || t.starts_with("type(super ")
+    })
+}
@@ -0,0 +1,79 @@
+// Shared analyses that detectors can query on top of the flat `WorkspaceContext` getters
+pub mod access;
+pub mod call_graph;
+pub mod cfg;
+pub mod expressions;
+pub mod pragma;
+pub mod references;
+pub mod solc_bugs;
+pub mod storage_layout;
+pub mod taint;
+pub mod yul;
+
+use std::cell::OnceCell;
+use std::collections::{BTreeSet, HashMap};
+
+use aderyn_driver::context::workspace_context::WorkspaceContext;
+use aderyn_driver::core_ast::NodeID;
+
+use call_graph::CallGraph;
+use pragma::SolidityVersions;
+use references::ReferenceIndex;
+use storage_layout::StorageLayouts;
+
+// The analyses of one WorkspaceContext, each built the first time it is asked for.
+//
+// An `Analyses` borrows its context, so it can never outlive it nor be handed another one. Detectors
+// build one at the start of `detect` and pass it down to the reusable detectors they call through
+// their `detect_with`, so that e.g. one ReferenceIndex serves every contract of a run.
+pub struct Analyses<'a> {
+    context: &'a WorkspaceContext,
+    references: OnceCell<ReferenceIndex>,
+    storage_aliases: OnceCell<HashMap<NodeID, BTreeSet<NodeID>>>,
+    call_graph: OnceCell<CallGraph>,
+    storage_layouts: OnceCell<StorageLayouts>,
+    solidity_versions: OnceCell<SolidityVersions>,
+}
+
+impl<'a> Analyses<'a> {
+    pub fn new(context: &'a WorkspaceContext) -> Self {
+        Self {
+            context,
+            references: OnceCell::new(),
+            storage_aliases: OnceCell::new(),
+            call_graph: OnceCell::new(),
+            storage_layouts: OnceCell::new(),
+            solidity_versions: OnceCell::new(),
+        }
+    }
+
+    pub fn context(&self) -> &'a WorkspaceContext {
+        self.context
+    }
+
+    pub fn references(&self) -> &ReferenceIndex {
+        self.references
+            .get_or_init(|| ReferenceIndex::build(self.context))
+    }
+
+    // See `access::storage_aliases`
+    pub fn storage_aliases(&self) -> &HashMap<NodeID, BTreeSet<NodeID>> {
+        self.storage_aliases
+            .get_or_init(|| access::storage_aliases(self.context))
+    }
+
+    pub fn call_graph(&self) -> &CallGraph {
+        self.call_graph
+            .get_or_init(|| CallGraph::build(self.context))
+    }
+
+    pub fn storage_layouts(&self) -> &StorageLayouts {
+        self.storage_layouts
+            .get_or_init(|| StorageLayouts::build(self.context))
+    }
+
+    pub fn solidity_versions(&self) -> &SolidityVersions {
+        self.solidity_versions
+            .get_or_init(|| SolidityVersions::build(self.context))
+    }
+}
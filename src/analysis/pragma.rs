@@ -0,0 +1,394 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use aderyn_driver::context::workspace_context::{ASTNode, WorkspaceContext};
+use aderyn_driver::core_ast::{NodeID, NodeType, PragmaDirective};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SolidityVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl SolidityVersion {
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    // `0.8.20`
+    pub fn parse(version: &str) -> Option<Self> {
+        let mut parts = version.split('.').map(|p| p.parse::<u64>());
+        let version = Self::new(
+            parts.next()?.ok()?,
+            parts.next()?.ok()?,
+            parts.next()?.ok()?,
+        );
+        parts.next().is_none().then_some(version)
+    }
+}
+
+impl fmt::Display for SolidityVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+// Every solc release, oldest first. Versions newer than the last one are treated as unknown.
+// Keep the last one in sync with `solc_bugs::KNOWN_BUGS_UP_TO`.
+pub fn known_releases() -> Vec<SolidityVersion> {
+    [(4, 26), (5, 17), (6, 12), (7, 6), (8, 34)]
+        .iter()
+        .flat_map(|&(minor, last_patch)| {
+            (0..=last_patch).map(move |patch| SolidityVersion::new(0, minor, patch))
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operator {
+    Exact,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Caret,
+    Tilde,
+}
+
+// A version with its missing components (`0.8`, `0.8.x`) left as wildcards
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct PartialVersion {
+    major: u64,
+    minor: Option<u64>,
+    patch: Option<u64>,
+}
+
+impl PartialVersion {
+    fn parse(version: &str) -> Option<Self> {
+        let mut parts = version.split('.');
+        let component = |part: Option<&str>| -> Option<Option<u64>> {
+            match part {
+                None | Some("x") | Some("X") | Some("*") => Some(None),
+                Some(part) => part.parse().ok().map(Some),
+            }
+        };
+        let major = parts.next()?.parse().ok()?;
+        let minor = component(parts.next())?;
+        let patch = minor.and(component(parts.next())?);
+        Some(Self {
+            major,
+            minor,
+            patch,
+        })
+    }
+
+    fn lowest(&self) -> SolidityVersion {
+        SolidityVersion::new(self.major, self.minor.unwrap_or(0), self.patch.unwrap_or(0))
+    }
+
+    // First version above every version matching the wildcards (`0.8` => `0.9.0`)
+    fn next(&self) -> SolidityVersion {
+        match (self.minor, self.patch) {
+            (None, _) => SolidityVersion::new(self.major + 1, 0, 0),
+            (Some(minor), None) => SolidityVersion::new(self.major, minor + 1, 0),
+            (Some(minor), Some(patch)) => SolidityVersion::new(self.major, minor, patch + 1),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Comparator {
+    operator: Operator,
+    version: PartialVersion,
+}
+
+impl Comparator {
+    fn matches(&self, version: &SolidityVersion) -> bool {
+        let (lowest, next) = (self.version.lowest(), self.version.next());
+        match self.operator {
+            Operator::Exact => lowest <= *version && *version < next,
+            Operator::Greater => *version >= next,
+            Operator::GreaterOrEqual => *version >= lowest,
+            Operator::Less => *version < lowest,
+            Operator::LessOrEqual => *version < next,
+            Operator::Caret => {
+                // Left most non zero component is fixed: ^0.8.1 => <0.9.0, ^1.2 => <2.0.0
+                let upper = if lowest.major > 0 || self.version.minor.is_none() {
+                    SolidityVersion::new(lowest.major + 1, 0, 0)
+                } else if lowest.minor > 0 || self.version.patch.is_none() {
+                    SolidityVersion::new(0, lowest.minor + 1, 0)
+                } else {
+                    SolidityVersion::new(0, 0, lowest.patch + 1)
+                };
+                lowest <= *version && *version < upper
+            }
+            Operator::Tilde => {
+                let upper = match self.version.minor {
+                    None => SolidityVersion::new(lowest.major + 1, 0, 0),
+                    Some(minor) => SolidityVersion::new(lowest.major, minor + 1, 0),
+                };
+                lowest <= *version && *version < upper
+            }
+        }
+    }
+}
+
+// Versions accepted by a `pragma solidity` directive (`^0.8.0`, `>=0.7.0 <0.9.0`, `0.8.19 || 0.8.20`)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionRange {
+    // Any of the alternatives (separated by `||`) when all of its comparators match
+    alternatives: Vec<Vec<Comparator>>,
+    pub source: String,
+}
+
+impl VersionRange {
+    pub fn parse(range: &str) -> Option<Self> {
+        let mut alternatives = vec![];
+        for alternative in range.split("||") {
+            let mut comparators = vec![];
+            let mut rest = alternative.trim();
+            while !rest.is_empty() {
+                // Hyphen range `0.8.0 - 0.8.9`
+                if let Some(after) = rest.strip_prefix('-') {
+                    let previous = comparators.pop().map(|c: Comparator| c.version)?;
+                    comparators.push(Comparator {
+                        operator: Operator::GreaterOrEqual,
+                        version: previous,
+                    });
+                    rest = after.trim_start();
+                    let (version, after) = take_version(rest)?;
+                    comparators.push(Comparator {
+                        operator: Operator::LessOrEqual,
+                        version,
+                    });
+                    rest = after.trim_start();
+                    continue;
+                }
+
+                let (operator, after) = take_operator(rest);
+                let (version, after) = take_version(after.trim_start())?;
+                comparators.push(Comparator { operator, version });
+                rest = after.trim_start();
+            }
+            if comparators.is_empty() {
+                return None;
+            }
+            alternatives.push(comparators);
+        }
+        Some(Self {
+            alternatives,
+            source: range.trim().to_string(),
+        })
+    }
+
+    // Range of a `pragma solidity` directive, other pragmas (`abicoder`, `experimental`) have none
+    pub fn from_pragma(pragma: &PragmaDirective) -> Option<Self> {
+        let (first, literals) = pragma.literals.split_first()?;
+        if first != "solidity" {
+            return None;
+        }
+        // solc splits `^0.8.0` into `^`, `0.8`, `.0`: versions are glued back, comparators spaced
+        let mut range = String::new();
+        for literal in literals {
+            let continues_version = literal.starts_with('.')
+                || (range.ends_with(|c: char| "^~<>=".contains(c))
+                    && !literal.starts_with(|c: char| "^~<>=".contains(c)));
+            if !range.is_empty() && !continues_version {
+                range.push(' ');
+            }
+            range.push_str(literal);
+        }
+        Self::parse(&range)
+    }
+
+    pub fn matches(&self, version: &SolidityVersion) -> bool {
+        self.alternatives
+            .iter()
+            .any(|comparators| comparators.iter().all(|c| c.matches(version)))
+    }
+
+    // Known releases accepted by the range, oldest first
+    pub fn allowed_releases(&self) -> Vec<SolidityVersion> {
+        known_releases()
+            .into_iter()
+            .filter(|version| self.matches(version))
+            .collect()
+    }
+
+    // A single version is accepted (`pragma solidity 0.8.19;`)
+    pub fn is_pinned(&self) -> bool {
+        self.alternatives.len() == 1
+            && self.alternatives[0].len() == 1
+            && self.alternatives[0][0].operator == Operator::Exact
+            && self.alternatives[0][0].version.patch.is_some()
+    }
+
+    pub fn lowest_release(&self) -> Option<SolidityVersion> {
+        self.allowed_releases().first().copied()
+    }
+
+    pub fn highest_release(&self) -> Option<SolidityVersion> {
+        self.allowed_releases().last().copied()
+    }
+
+    // Every accepted known release is at least `version`. Unknown (future) releases are ignored.
+    pub fn requires_at_least(&self, version: SolidityVersion) -> bool {
+        self.lowest_release()
+            .is_some_and(|lowest| lowest >= version)
+    }
+
+    // Some accepted known release is at least `version`
+    pub fn allows_at_least(&self, version: SolidityVersion) -> bool {
+        self.highest_release()
+            .is_some_and(|highest| highest >= version)
+    }
+}
+
+impl fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+fn take_operator(range: &str) -> (Operator, &str) {
+    for (token, operator) in [
+        (">=", Operator::GreaterOrEqual),
+        ("<=", Operator::LessOrEqual),
+        (">", Operator::Greater),
+        ("<", Operator::Less),
+        ("^", Operator::Caret),
+        ("~", Operator::Tilde),
+        ("=", Operator::Exact),
+    ] {
+        if let Some(rest) = range.strip_prefix(token) {
+            return (operator, rest);
+        }
+    }
+    (Operator::Exact, range)
+}
+
+fn take_version(range: &str) -> Option<(PartialVersion, &str)> {
+    let end = range
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == 'x' || c == 'X' || c == '*'))
+        .unwrap_or(range.len());
+    Some((PartialVersion::parse(&range[..end])?, &range[end..]))
+}
+
+// The `pragma solidity` directives of every SourceUnit, with the range they resolve to. When a file
+// has several directives, a version must satisfy all of them.
+//
+// Query it through `Analyses::solidity_versions()` so that it is only computed once per context.
+#[derive(Default, Debug)]
+pub struct SolidityVersions {
+    // SourceUnit id => pragma directives and their ranges
+    pragmas: HashMap<NodeID, Vec<(NodeID, VersionRange)>>,
+}
+
+impl SolidityVersions {
+    pub fn build(context: &WorkspaceContext) -> Self {
+        let mut pragmas: HashMap<NodeID, Vec<(NodeID, VersionRange)>> = HashMap::new();
+        for node in context.nodes.values() {
+            let ASTNode::PragmaDirective(pragma) = node else {
+                continue;
+            };
+            let Some(range) = VersionRange::from_pragma(pragma) else {
+                continue;
+            };
+            if let Some(ASTNode::SourceUnit(source_unit)) =
+                context.get_closest_parent(pragma.id, NodeType::SourceUnit)
+            {
+                pragmas
+                    .entry(source_unit.id)
+                    .or_default()
+                    .push((pragma.id, range));
+            }
+        }
+        pragmas
+            .values_mut()
+            .for_each(|p| p.sort_by_key(|(id, _)| *id));
+        Self { pragmas }
+    }
+
+    // `pragma solidity` directives of the given SourceUnit
+    pub fn pragmas_of(&self, source_unit: NodeID) -> &[(NodeID, VersionRange)] {
+        self.pragmas
+            .get(&source_unit)
+            .map(|p| p.as_slice())
+            .unwrap_or_default()
+    }
+
+    // Whether the SourceUnit containing `node` may be compiled with `version`. Files without a
+    // pragma accept any version.
+    pub fn accepts(
+        &self,
+        context: &WorkspaceContext,
+        node: NodeID,
+        version: &SolidityVersion,
+    ) -> bool {
+        let Some(ASTNode::SourceUnit(source_unit)) =
+            context.get_closest_parent(node, NodeType::SourceUnit)
+        else {
+            return true;
+        };
+        self.pragmas_of(source_unit.id)
+            .iter()
+            .all(|(_, range)| range.matches(version))
+    }
+}
+
+#[cfg(test)]
+mod pragma_tests {
+
+    use crate::analysis::Analyses;
+    use crate::bot_utils::load_contracts;
+
+    use super::{SolidityVersion, VersionRange};
+
+    fn v(version: &str) -> SolidityVersion {
+        SolidityVersion::parse(version).unwrap()
+    }
+
+    #[test]
+    fn test_version_ranges() {
+        let caret = VersionRange::parse("^0.8.13").unwrap();
+        assert!(caret.matches(&v("0.8.13")) && caret.matches(&v("0.8.30")));
+        assert!(!caret.matches(&v("0.8.12")) && !caret.matches(&v("0.9.0")));
+        assert!(!caret.is_pinned());
+
+        let bounded = VersionRange::parse(">=0.7.0 <0.8.0").unwrap();
+        assert_eq!(bounded.lowest_release(), Some(v("0.7.0")));
+        assert_eq!(bounded.highest_release(), Some(v("0.7.6")));
+
+        let union = VersionRange::parse("0.6.12 || ~0.7").unwrap();
+        assert!(union.matches(&v("0.6.12")) && union.matches(&v("0.7.4")));
+        assert!(!union.matches(&v("0.6.11")));
+
+        let hyphen = VersionRange::parse("0.8.0 - 0.8.9").unwrap();
+        assert!(hyphen.matches(&v("0.8.9")) && !hyphen.matches(&v("0.8.10")));
+
+        let pinned = VersionRange::parse("0.8.24").unwrap();
+        assert!(pinned.is_pinned());
+        assert!(pinned.requires_at_least(v("0.8.20")));
+    }
+
+    #[test]
+    fn test_solidity_versions_of_source_units() {
+        let context = load_contracts(&["./foundry_workspace/out/Counter.sol/Counter.json".into()]);
+        let analyses = Analyses::new(&context);
+        let versions = analyses.solidity_versions();
+
+        let counter = context
+            .contract_definitions()
+            .into_iter()
+            .find(|c| c.name == "Counter")
+            .unwrap();
+        // pragma solidity ^0.8.13;
+        assert!(versions.accepts(&context, counter.id, &v("0.8.13")));
+        assert!(!versions.accepts(&context, counter.id, &v("0.7.6")));
+    }
+}
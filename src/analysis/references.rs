@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use aderyn_driver::context::workspace_context::{ASTNode, WorkspaceContext};
+use aderyn_driver::core_ast::NodeID;
+
+// Maps every declaration id to the Identifier, MemberAccess and IdentifierPath nodes referencing
+// it (ex: `x`, `this.x`, `Contract.x`, `Base.Struct`).
+//
+// Query it through `Analyses::references()` so that it is only computed once per context.
+#[derive(Default, Debug)]
+pub struct ReferenceIndex {
+    references: HashMap<NodeID, Vec<NodeID>>,
+}
+
+impl ReferenceIndex {
+    pub fn build(context: &WorkspaceContext) -> Self {
+        let mut references: HashMap<NodeID, Vec<NodeID>> = HashMap::new();
+
+        for identifier in context.identifiers() {
+            references
+                .entry(identifier.referenced_declaration)
+                .or_default()
+                .push(identifier.id);
+        }
+        for member_access in context.member_accesses() {
+            if let Some(declaration) = member_access.referenced_declaration {
+                references
+                    .entry(declaration)
+                    .or_default()
+                    .push(member_access.id);
+            }
+        }
+        for identifier_path in context.identifier_paths() {
+            if let Some(declaration) = identifier_path.referenced_declaration {
+                references
+                    .entry(declaration)
+                    .or_default()
+                    .push(identifier_path.id);
+            }
+        }
+
+        references.values_mut().for_each(|ids| ids.sort());
+        Self { references }
+    }
+
+    // Ids of the nodes referencing `declaration`
+    pub fn references_to(&self, declaration: NodeID) -> &[NodeID] {
+        self.references
+            .get(&declaration)
+            .map(|ids| ids.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn is_referenced(&self, declaration: NodeID) -> bool {
+        !self.references_to(declaration).is_empty()
+    }
+
+    // Nodes referencing `declaration`, resolved against the context the index was built from
+    pub fn reference_nodes<'a>(
+        &self,
+        context: &'a WorkspaceContext,
+        declaration: NodeID,
+    ) -> Vec<&'a ASTNode> {
+        self.references_to(declaration)
+            .iter()
+            .filter_map(|id| context.nodes.get(id))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod reference_index_tests {
+
+    use crate::analysis::Analyses;
+    use crate::bot_utils::load_contracts;
+
+    #[test]
+    fn test_reference_index() {
+        let context = load_contracts(&["./foundry_workspace/out/Counter.sol/Counter.json".into()]);
+        let analyses = Analyses::new(&context);
+        let index = analyses.references();
+
+        let variables = context.variable_declarations();
+        let number = variables.iter().find(|v| v.name == "number").unwrap();
+        let unused = variables.iter().find(|v| v.name == "unused_number").unwrap();
+
+        // `number = newNumber` and `number++`
+        assert_eq!(index.references_to(number.id).len(), 2);
+        assert!(!index.is_referenced(unused.id));
+
+        // The index is built once per `Analyses`
+        assert!(std::ptr::eq(index, analyses.references()));
+    }
+}
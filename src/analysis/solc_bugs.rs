@@ -0,0 +1,286 @@
+use super::pragma::SolidityVersion;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BugSeverity {
+    VeryLow,
+    Low,
+    Medium,
+    High,
+}
+
+// A compiler bug from the Solidity team's list, affecting `introduced..fixed`
+#[derive(Clone, Copy, Debug)]
+pub struct SolcBug {
+    pub name: &'static str,
+    pub severity: BugSeverity,
+    pub introduced: SolidityVersion,
+    pub fixed: SolidityVersion,
+}
+
+impl SolcBug {
+    pub fn affects(&self, version: &SolidityVersion) -> bool {
+        self.introduced <= *version && *version < self.fixed
+    }
+}
+
+const fn bug(
+    name: &'static str,
+    severity: BugSeverity,
+    introduced: (u64, u64),
+    fixed: (u64, u64),
+) -> SolcBug {
+    SolcBug {
+        name,
+        severity,
+        introduced: SolidityVersion::new(0, introduced.0, introduced.1),
+        fixed: SolidityVersion::new(0, fixed.0, fixed.1),
+    }
+}
+
+// Entries of https://github.com/ethereum/solidity/blob/develop/docs/bugs.json for the 0.4 - 0.8 releases,
+// newest fix first, so that detectors never need the network. Bugs introduced before 0.4.0 (or without an
+// `introduced` version) start at 0.4.0, and "low/medium" / "medium/high" round down.
+//
+// This is a hand-copied subset, not the whole list: it keeps the bugs a pragma is most likely to hit, which
+// leaves out most of the 0.4 / 0.5-only bugs (ex: `HighOrderByteCleanStorage`, `DoubleShiftSizeOverflow`).
+// `bugs_affecting` can therefore miss a bug of an old release. To get the complete list from a checkout of
+// the solidity repository, replace the entries with the output of
+//
+//   jq -r 'map(select(.fixed | split(".")[1] | tonumber >= 4)) | sort_by(.fixed | split(".") | map(tonumber))
+//     | reverse | .[] | (.introduced // "0.4.0" | split(".") | map(tonumber)) as $i
+//     | (if $i[1] < 4 then [0, 4, 0] else $i end) as $i | (.fixed | split(".") | map(tonumber)) as $f
+//     | ({"very low": "VeryLow", "low": "Low", "low/medium": "Low", "medium": "Medium",
+//         "medium/high": "Medium", "high": "High"}[.severity]) as $severity
+//     | "bug(\"\(.name)\", BugSeverity::\($severity), (\($i[1]), \($i[2])), (\($f[1]), \($f[2]))),"'
+//     docs/bugs.json
+//
+// then set `KNOWN_BUGS_UP_TO` and the last 0.8 patch of `pragma::known_releases` to the newest
+// release of docs/bugs_by_version.json (`jq -r 'keys[]' docs/bugs_by_version.json | sort -V | tail -n 1`),
+// and run rustfmt.
+pub const KNOWN_BUGS: &[SolcBug] = &[
+    bug(
+        "TransientStorageClearingHelperCollision",
+        BugSeverity::High,
+        (8, 28),
+        (8, 34),
+    ),
+    bug(
+        "LostStorageArrayWriteOnSlotOverflow",
+        BugSeverity::Low,
+        (4, 0),
+        (8, 22),
+    ),
+    bug(
+        "FullInlinerNonExpressionSplitArgumentEvaluationOrder",
+        BugSeverity::Low,
+        (6, 7),
+        (8, 21),
+    ),
+    bug(
+        "MissingSideEffectsOnSelectorAccess",
+        BugSeverity::Low,
+        (6, 2),
+        (8, 21),
+    ),
+    bug(
+        "StorageWriteRemovalBeforeConditionalTermination",
+        BugSeverity::Medium,
+        (8, 13),
+        (8, 17),
+    ),
+    bug(
+        "AbiReencodingHeadOverflowWithStaticArrayCleanup",
+        BugSeverity::Medium,
+        (5, 8),
+        (8, 16),
+    ),
+    bug(
+        "AbiEncodeCallLiteralAsFixedBytesBug",
+        BugSeverity::VeryLow,
+        (8, 11),
+        (8, 16),
+    ),
+    bug(
+        "DirtyBytesArrayToStorage",
+        BugSeverity::Low,
+        (4, 0),
+        (8, 15),
+    ),
+    bug(
+        "InlineAssemblyMemorySideEffects",
+        BugSeverity::Medium,
+        (8, 13),
+        (8, 15),
+    ),
+    bug(
+        "VerbatimInvalidDeduplication",
+        BugSeverity::Low,
+        (8, 5),
+        (8, 15),
+    ),
+    bug(
+        "DataLocationChangeInInternalOverride",
+        BugSeverity::VeryLow,
+        (6, 9),
+        (8, 14),
+    ),
+    bug(
+        "NestedCalldataArrayAbiReencodingSizeValidation",
+        BugSeverity::VeryLow,
+        (5, 8),
+        (8, 14),
+    ),
+    bug(
+        "UserDefinedValueTypesBug",
+        BugSeverity::VeryLow,
+        (8, 8),
+        (8, 9),
+    ),
+    bug("SignedImmutables", BugSeverity::VeryLow, (6, 5), (8, 9)),
+    bug(
+        "ABIDecodeTwoDimensionalArrayMemory",
+        BugSeverity::VeryLow,
+        (4, 16),
+        (8, 4),
+    ),
+    bug("KeccakCaching", BugSeverity::Medium, (4, 0), (8, 3)),
+    bug("EmptyByteArrayCopy", BugSeverity::Medium, (4, 0), (7, 4)),
+    bug("DynamicArrayCleanup", BugSeverity::Medium, (4, 0), (7, 3)),
+    bug("FreeFunctionRedefinition", BugSeverity::Low, (7, 1), (7, 2)),
+    bug("UsingForCalldata", BugSeverity::VeryLow, (6, 9), (6, 10)),
+    bug(
+        "ImplicitConstructorCallvalueCheck",
+        BugSeverity::VeryLow,
+        (4, 5),
+        (6, 8),
+    ),
+    bug(
+        "MissingEscapingInFormatting",
+        BugSeverity::VeryLow,
+        (5, 14),
+        (6, 8),
+    ),
+    bug(
+        "ArraySliceDynamicallyEncodedBaseType",
+        BugSeverity::VeryLow,
+        (6, 0),
+        (6, 8),
+    ),
+    bug(
+        "TupleAssignmentMultiStackSlotComponents",
+        BugSeverity::Low,
+        (4, 0),
+        (6, 6),
+    ),
+    bug(
+        "MemoryArrayCreationOverflow",
+        BugSeverity::Low,
+        (4, 0),
+        (6, 5),
+    ),
+    bug(
+        "YulOptimizerRedundantAssignmentBreakContinue",
+        BugSeverity::Medium,
+        (6, 0),
+        (6, 1),
+    ),
+    bug(
+        "ABIEncoderV2CalldataStructsWithStaticallySizedAndDynamicallyEncodedMembers",
+        BugSeverity::Low,
+        (5, 6),
+        (5, 11),
+    ),
+    bug("SignedArrayStorageCopy", BugSeverity::Low, (4, 7), (5, 10)),
+    bug(
+        "ABIEncoderV2StorageArrayWithMultiSlotElement",
+        BugSeverity::Low,
+        (4, 16),
+        (5, 10),
+    ),
+    bug(
+        "DynamicConstructorArgumentsClippedABIV2",
+        BugSeverity::VeryLow,
+        (4, 16),
+        (5, 9),
+    ),
+    bug(
+        "UninitializedFunctionPointerInConstructor",
+        BugSeverity::VeryLow,
+        (5, 0),
+        (5, 8),
+    ),
+    bug(
+        "IncorrectEventSignatureInLibraries",
+        BugSeverity::VeryLow,
+        (4, 0),
+        (5, 8),
+    ),
+    bug(
+        "ABIEncoderV2PackedStorage",
+        BugSeverity::Low,
+        (5, 0),
+        (5, 7),
+    ),
+    bug(
+        "EventStructWrongData",
+        BugSeverity::VeryLow,
+        (4, 17),
+        (5, 0),
+    ),
+    bug("ExpExponentCleanup", BugSeverity::Medium, (4, 0), (4, 25)),
+    bug(
+        "NestedArrayFunctionCallDecoder",
+        BugSeverity::Medium,
+        (4, 0),
+        (4, 22),
+    ),
+];
+
+// Newest release `KNOWN_BUGS` was generated against
+pub const KNOWN_BUGS_UP_TO: SolidityVersion = SolidityVersion::new(0, 8, 34);
+
+// Known bugs of `version`, most severe first
+pub fn bugs_affecting(version: &SolidityVersion) -> Vec<&'static SolcBug> {
+    let mut bugs = KNOWN_BUGS
+        .iter()
+        .filter(|bug| bug.affects(version))
+        .collect::<Vec<_>>();
+    bugs.sort_by_key(|bug| std::cmp::Reverse(bug.severity));
+    bugs
+}
+
+#[cfg(test)]
+mod solc_bugs_tests {
+
+    use crate::analysis::pragma::{known_releases, SolidityVersion};
+
+    use super::{bugs_affecting, BugSeverity, KNOWN_BUGS, KNOWN_BUGS_UP_TO};
+
+    #[test]
+    fn test_bug_list_covers_known_releases() {
+        // A release added to `known_releases` needs the bug list regenerated
+        let releases = known_releases();
+        assert_eq!(releases.last(), Some(&KNOWN_BUGS_UP_TO));
+        for bug in KNOWN_BUGS {
+            assert!(releases.contains(&bug.introduced), "{}", bug.name);
+            assert!(releases.contains(&bug.fixed), "{}", bug.name);
+            assert!(bug.introduced < bug.fixed, "{}", bug.name);
+        }
+    }
+
+    #[test]
+    fn test_bugs_affecting() {
+        let bugs = bugs_affecting(&SolidityVersion::new(0, 8, 13));
+        assert!(bugs
+            .iter()
+            .any(|b| b.name == "StorageWriteRemovalBeforeConditionalTermination"));
+        assert!(!bugs.iter().any(|b| b.name == "KeccakCaching"));
+        assert!(bugs
+            .windows(2)
+            .all(|pair| pair[0].severity >= pair[1].severity));
+
+        assert!(bugs_affecting(&KNOWN_BUGS_UP_TO)
+            .iter()
+            .all(|b| b.severity < BugSeverity::High));
+    }
+}
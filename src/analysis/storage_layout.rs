@@ -0,0 +1,798 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::Path;
+
+use aderyn_driver::context::browser::ExtractVariableDeclarations;
+use aderyn_driver::context::workspace_context::{ASTNode, WorkspaceContext};
+use aderyn_driver::core_ast::{
+    ContractDefinition, Mutability, NodeID, NodeType, VariableDeclaration,
+};
+
+use crate::inheritance_chain::detector::linearize;
+
+const SLOT_SIZE: u64 = 32;
+
+// Name of the arrays reserving slots for variables added by later versions (OpenZeppelin convention)
+pub const GAP_LABEL: &str = "__gap";
+
+// Where a state variable lives in storage
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageEntry {
+    // VariableDeclaration (`astId` in the compiler's layout)
+    pub variable: NodeID,
+    // Name of the contract declaring the variable
+    pub contract: String,
+    pub label: String,
+    pub type_label: String,
+    pub slot: u64,
+    // Byte offset of the variable inside its first slot
+    pub offset: u64,
+    // Number of bytes occupied, a multiple of 32 for values spanning several slots
+    pub size: u64,
+    // Whether other variables can share its slot (value types smaller than a slot)
+    pub packable: bool,
+}
+
+// Storage layout of a contract, its bases' variables first, in declaration order
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StorageLayout {
+    pub entries: Vec<StorageEntry>,
+}
+
+impl StorageLayout {
+    // Reads the `storageLayout` output of a Foundry artifact
+    // (requires `extra_output = ["storageLayout"]` in foundry.toml)
+    pub fn from_artifact(artifact: &serde_json::Value) -> Option<Self> {
+        let layout = artifact.get("storageLayout")?;
+        let types = layout.get("types");
+
+        let mut entries = vec![];
+        for entry in layout.get("storage")?.as_array()? {
+            let type_id = entry.get("type")?.as_str()?;
+            let type_info = types.and_then(|types| types.get(type_id));
+            let type_label = type_info
+                .and_then(|t| t.get("label"))
+                .and_then(|l| l.as_str())
+                .unwrap_or(type_id)
+                .to_string();
+            let size = type_info
+                .and_then(|t| t.get("numberOfBytes"))
+                .and_then(|n| n.as_str())
+                .and_then(|n| n.parse::<u64>().ok())
+                .unwrap_or(SLOT_SIZE);
+            let inplace = type_info
+                .and_then(|t| t.get("encoding"))
+                .and_then(|e| e.as_str())
+                .is_none_or(|e| e == "inplace");
+
+            entries.push(StorageEntry {
+                variable: entry.get("astId")?.as_i64()? as NodeID,
+                // `src/File.sol:Contract`
+                contract: entry
+                    .get("contract")?
+                    .as_str()?
+                    .rsplit(':')
+                    .next()?
+                    .to_string(),
+                label: entry.get("label")?.as_str()?.to_string(),
+                packable: inplace && size < SLOT_SIZE && !is_composite(&type_label),
+                type_label,
+                slot: entry.get("slot")?.as_str()?.parse().ok()?,
+                offset: entry.get("offset")?.as_u64()?,
+                size,
+            });
+        }
+        Some(Self { entries })
+    }
+
+    pub fn from_artifact_file(filepath: &str) -> Option<Self> {
+        let content = std::fs::read_to_string(filepath).ok()?;
+        let artifact: serde_json::Value = serde_json::from_str(&content).ok()?;
+        Self::from_artifact(&artifact)
+    }
+
+    // Computes the layout of `contract` from the types of its state variables, the way solc
+    // assigns slots (see "Layout of State Variables in Storage" in the Solidity docs)
+    pub fn compute(context: &WorkspaceContext, contract: NodeID) -> Option<Self> {
+        let chain = linearize(context, contract, &mut HashMap::new()).ok()?;
+
+        let mut variables = vec![];
+        for base in chain.iter().rev() {
+            let Some(ASTNode::ContractDefinition(contract_definition)) = context.nodes.get(base)
+            else {
+                continue;
+            };
+            let mut declared = ExtractVariableDeclarations::from(contract_definition)
+                .extracted
+                .into_iter()
+                .filter(|v| v.state_variable && occupies_storage(v))
+                .collect::<Vec<_>>();
+            declared.sort_by_key(|v| v.id);
+            for variable in declared {
+                variables.push((contract_definition.name.clone(), variable));
+            }
+        }
+
+        let sizes = variables
+            .iter()
+            .map(|(_, v)| type_size(context, type_label(v)))
+            .collect::<Vec<_>>();
+        let positions = assign_slots(&sizes);
+
+        let entries = variables
+            .into_iter()
+            .zip(sizes)
+            .zip(positions)
+            .map(
+                |(((contract, variable), size), (slot, offset))| StorageEntry {
+                    variable: variable.id,
+                    contract,
+                    label: variable.name.clone(),
+                    type_label: type_label(&variable).to_string(),
+                    slot,
+                    offset,
+                    size: size.bytes,
+                    packable: size.packable(),
+                },
+            )
+            .collect();
+        Some(Self { entries })
+    }
+
+    // The layout once `variable` is removed, the other variables keeping their order
+    pub fn without(&self, variable: NodeID) -> Self {
+        let entries = self
+            .entries
+            .iter()
+            .filter(|e| e.variable != variable)
+            .cloned()
+            .collect::<Vec<_>>();
+        let sizes = entries
+            .iter()
+            .map(|e| TypeSize {
+                bytes: e.size,
+                composite: !e.packable,
+            })
+            .collect::<Vec<_>>();
+        let entries = entries
+            .into_iter()
+            .zip(assign_slots(&sizes))
+            .map(|(entry, (slot, offset))| StorageEntry {
+                slot,
+                offset,
+                ..entry
+            })
+            .collect();
+        Self { entries }
+    }
+
+    pub fn entry(&self, variable: NodeID) -> Option<&StorageEntry> {
+        self.entries.iter().find(|e| e.variable == variable)
+    }
+
+    pub fn slots_used(&self) -> u64 {
+        self.entries
+            .iter()
+            .map(StorageEntry::end_slot)
+            .max()
+            .unwrap_or(0)
+    }
+
+    // Bytes left unused in the slots holding packable variables
+    pub fn wasted_bytes(&self) -> u64 {
+        let mut used = BTreeMap::new();
+        for entry in self.entries.iter().filter(|e| e.packable) {
+            *used.entry(entry.slot).or_insert(0) += entry.size;
+        }
+        used.values()
+            .map(|bytes| SLOT_SIZE.saturating_sub(*bytes))
+            .sum()
+    }
+
+    // Number of slots the given entries would need with the best ordering (first fit decreasing)
+    pub fn optimal_slots<'a>(entries: impl IntoIterator<Item = &'a StorageEntry>) -> u64 {
+        let mut full_slots = 0;
+        let mut packable = vec![];
+        for entry in entries {
+            if entry.packable {
+                packable.push(entry.size);
+            } else {
+                full_slots += entry.size.div_ceil(SLOT_SIZE).max(1);
+            }
+        }
+
+        packable.sort_by(|a, b| b.cmp(a));
+        let mut bins: Vec<u64> = vec![];
+        for size in packable {
+            match bins.iter_mut().find(|used| **used + size <= SLOT_SIZE) {
+                Some(used) => *used += size,
+                None => bins.push(size),
+            }
+        }
+        full_slots + bins.len() as u64
+    }
+
+    // Number of distinct slots touched by the given entries
+    pub fn slots_of<'a>(entries: impl IntoIterator<Item = &'a StorageEntry>) -> u64 {
+        let mut slots = std::collections::BTreeSet::new();
+        for entry in entries {
+            for slot in entry.slot..entry.end_slot() {
+                slots.insert(slot);
+            }
+        }
+        slots.len() as u64
+    }
+}
+
+// Storage layouts of every contract of the context.
+//
+// Query it through `Analyses::storage_layouts()` so that it is only computed once per context.
+#[derive(Default, Debug)]
+pub struct StorageLayouts {
+    layouts: HashMap<NodeID, StorageLayout>,
+}
+
+impl StorageLayouts {
+    // The compiler's layout when the contract's artifact has one, otherwise the layout computed from the AST
+    pub fn build(context: &WorkspaceContext) -> Self {
+        let layouts = context
+            .contract_definitions()
+            .into_iter()
+            .filter_map(|c| {
+                artifact_layout(context, c)
+                    .or_else(|| StorageLayout::compute(context, c.id))
+                    .map(|layout| (c.id, layout))
+            })
+            .collect();
+        Self { layouts }
+    }
+
+    pub fn of(&self, contract: NodeID) -> Option<&StorageLayout> {
+        self.layouts.get(&contract)
+    }
+}
+
+// `storageLayout` of the artifact Foundry wrote for `contract`, `<root>/out/<File>.sol/<Contract>.json`, where
+// `<root>/src/<File>.sol` is the source recorded in the context by `load_contracts`
+fn artifact_layout(
+    context: &WorkspaceContext,
+    contract: &ContractDefinition,
+) -> Option<StorageLayout> {
+    let Some(ASTNode::SourceUnit(source_unit)) =
+        context.get_closest_parent(contract.id, NodeType::SourceUnit)
+    else {
+        return None;
+    };
+    let absolute_path = source_unit.absolute_path.as_deref()?;
+    let source_path = context
+        .src_filepaths
+        .iter()
+        .find(|path| path.ends_with(absolute_path))?;
+    let root = &source_path[..source_path.len() - absolute_path.len()];
+    let artifact = Path::new(root)
+        .join("out")
+        .join(Path::new(absolute_path).file_name()?)
+        .join(format!("{}.json", contract.name));
+    let layout = StorageLayout::from_artifact_file(artifact.to_str()?)?;
+
+    // An artifact from another build would not share the context's node ids
+    layout
+        .entries
+        .iter()
+        .all(|entry| {
+            matches!(
+                context.nodes.get(&entry.variable),
+                Some(ASTNode::VariableDeclaration(variable)) if variable.name == entry.label
+            )
+        })
+        .then_some(layout)
+}
+
+// A storage incompatibility between two versions of an upgradeable contract, or in one version
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StorageConflict {
+    Removed {
+        label: String,
+        slot: u64,
+    },
+    Moved {
+        label: String,
+        old_slot: u64,
+        old_offset: u64,
+        new_slot: u64,
+        new_offset: u64,
+    },
+    Retyped {
+        label: String,
+        slot: u64,
+        old_type: String,
+        new_type: String,
+    },
+    // New variable on storage used by the old version, without shrinking the `__gap` it sits in
+    Inserted {
+        label: String,
+        slot: u64,
+        overlaps: String,
+    },
+    // The end of a `__gap` moved, which shifts every variable of the contracts deriving from it
+    GapResized {
+        contract: String,
+        old_end: u64,
+        new_end: u64,
+    },
+    // A `__gap` followed by other variables of its contract, or not reserving any slot
+    MisusedGap {
+        contract: String,
+        slot: u64,
+        reason: String,
+    },
+}
+
+impl StorageConflict {
+    // Slot where the conflict starts
+    pub fn slot(&self) -> u64 {
+        match self {
+            StorageConflict::Removed { slot, .. }
+            | StorageConflict::Retyped { slot, .. }
+            | StorageConflict::Inserted { slot, .. }
+            | StorageConflict::MisusedGap { slot, .. } => *slot,
+            StorageConflict::Moved {
+                old_slot, new_slot, ..
+            } => *old_slot.min(new_slot),
+            StorageConflict::GapResized {
+                old_end, new_end, ..
+            } => *old_end.min(new_end),
+        }
+    }
+
+    // Variable (or `__gap`) of the new version involved in the conflict, if it still exists
+    pub fn label(&self) -> &str {
+        match self {
+            StorageConflict::Removed { label, .. }
+            | StorageConflict::Moved { label, .. }
+            | StorageConflict::Retyped { label, .. }
+            | StorageConflict::Inserted { label, .. } => label,
+            StorageConflict::GapResized { .. } | StorageConflict::MisusedGap { .. } => GAP_LABEL,
+        }
+    }
+}
+
+impl fmt::Display for StorageConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageConflict::Removed { label, slot } => {
+                write!(f, "`{}` (slot {}) was removed", label, slot)
+            }
+            StorageConflict::Moved {
+                label,
+                old_slot,
+                old_offset,
+                new_slot,
+                new_offset,
+            } => write!(
+                f,
+                "`{}` moved from slot {} (offset {}) to slot {} (offset {})",
+                label, old_slot, old_offset, new_slot, new_offset
+            ),
+            StorageConflict::Retyped {
+                label,
+                slot,
+                old_type,
+                new_type,
+            } => write!(
+                f,
+                "`{}` (slot {}) changed type from `{}` to `{}`",
+                label, slot, old_type, new_type
+            ),
+            StorageConflict::Inserted {
+                label,
+                slot,
+                overlaps,
+            } => write!(
+                f,
+                "`{}` was inserted at slot {}, previously used by `{}`",
+                label, slot, overlaps
+            ),
+            StorageConflict::GapResized {
+                contract,
+                old_end,
+                new_end,
+            } => write!(
+                f,
+                "`{}` of {} now ends at slot {} instead of {}",
+                GAP_LABEL, contract, new_end, old_end
+            ),
+            StorageConflict::MisusedGap {
+                contract,
+                slot,
+                reason,
+            } => write!(
+                f,
+                "`{}` of {} (slot {}) {}",
+                GAP_LABEL, contract, slot, reason
+            ),
+        }
+    }
+}
+
+impl StorageEntry {
+    pub fn is_gap(&self) -> bool {
+        self.label == GAP_LABEL
+    }
+
+    // First slot after the entry
+    pub fn end_slot(&self) -> u64 {
+        self.slot + self.size.div_ceil(SLOT_SIZE).max(1)
+    }
+}
+
+// Conflicts between the layout deployed behind a proxy (`old`) and the one of its new implementation.
+// Variables are matched by name (the nth `x` with the nth `x`) so that renamed contracts still compare.
+pub fn compare_layouts(old: &StorageLayout, new: &StorageLayout) -> Vec<StorageConflict> {
+    let matching = |layout: &StorageLayout, entry_index: usize, other: &StorageLayout| {
+        let label = &layout.entries[entry_index].label;
+        let occurrence = layout.entries[..entry_index]
+            .iter()
+            .filter(|e| &e.label == label)
+            .count();
+        other
+            .entries
+            .iter()
+            .filter(|e| &e.label == label)
+            .nth(occurrence)
+            .cloned()
+    };
+
+    let mut conflicts = vec![];
+    for (index, old_entry) in old.entries.iter().enumerate() {
+        let new_entry = matching(old, index, new);
+        if old_entry.is_gap() {
+            if let Some(new_entry) = new_entry {
+                if new_entry.end_slot() != old_entry.end_slot() {
+                    conflicts.push(StorageConflict::GapResized {
+                        contract: new_entry.contract.clone(),
+                        old_end: old_entry.end_slot(),
+                        new_end: new_entry.end_slot(),
+                    });
+                }
+            }
+            continue;
+        }
+        match new_entry {
+            None => conflicts.push(StorageConflict::Removed {
+                label: old_entry.label.clone(),
+                slot: old_entry.slot,
+            }),
+            Some(new_entry) if new_entry.type_label != old_entry.type_label => {
+                conflicts.push(StorageConflict::Retyped {
+                    label: old_entry.label.clone(),
+                    slot: old_entry.slot,
+                    old_type: old_entry.type_label.clone(),
+                    new_type: new_entry.type_label.clone(),
+                })
+            }
+            Some(new_entry)
+                if (new_entry.slot, new_entry.offset) != (old_entry.slot, old_entry.offset) =>
+            {
+                conflicts.push(StorageConflict::Moved {
+                    label: old_entry.label.clone(),
+                    old_slot: old_entry.slot,
+                    old_offset: old_entry.offset,
+                    new_slot: new_entry.slot,
+                    new_offset: new_entry.offset,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    for (index, new_entry) in new.entries.iter().enumerate() {
+        if new_entry.is_gap() || matching(new, index, old).is_some() {
+            continue;
+        }
+        let Some(overlapped) = old
+            .entries
+            .iter()
+            .find(|e| e.slot <= new_entry.slot && new_entry.slot < e.end_slot())
+        else {
+            // Appended after everything the old version used
+            continue;
+        };
+        // Taking slots out of a `__gap` is fine as long as the gap shrinks by as much
+        let gap_shrunk = overlapped.is_gap()
+            && new.entries.iter().any(|e| {
+                e.is_gap()
+                    && e.contract == new_entry.contract
+                    && e.end_slot() == overlapped.end_slot()
+            });
+        if !gap_shrunk {
+            conflicts.push(StorageConflict::Inserted {
+                label: new_entry.label.clone(),
+                slot: new_entry.slot,
+                overlaps: overlapped.label.clone(),
+            });
+        }
+    }
+
+    conflicts.extend(new.gap_misuses());
+    conflicts
+}
+
+impl StorageLayout {
+    // `__gap` declarations that do not reserve anything for the variables added later
+    pub fn gap_misuses(&self) -> Vec<StorageConflict> {
+        let mut misuses = vec![];
+        for (index, gap) in self.entries.iter().enumerate().filter(|(_, e)| e.is_gap()) {
+            let reason = if !gap.type_label.ends_with(']') || gap.type_label.ends_with("[]") {
+                "is not a fixed size array, it reserves no slot"
+            } else if self.entries[index + 1..]
+                .iter()
+                .any(|e| e.contract == gap.contract)
+            {
+                "is not the last variable of its contract"
+            } else {
+                continue;
+            };
+            misuses.push(StorageConflict::MisusedGap {
+                contract: gap.contract.clone(),
+                slot: gap.slot,
+                reason: reason.to_string(),
+            });
+        }
+        misuses
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct TypeSize {
+    bytes: u64,
+    // Structs and static arrays always start a new slot, and so does the variable after them
+    composite: bool,
+}
+
+impl TypeSize {
+    fn value(bytes: u64) -> Self {
+        Self {
+            bytes,
+            composite: false,
+        }
+    }
+
+    fn packable(&self) -> bool {
+        !self.composite && self.bytes < SLOT_SIZE
+    }
+}
+
+fn occupies_storage(variable: &VariableDeclaration) -> bool {
+    !variable.constant
+        && !matches!(
+            variable.mutability,
+            Some(Mutability::Immutable) | Some(Mutability::Constant)
+        )
+}
+
+fn type_label(variable: &VariableDeclaration) -> &str {
+    variable
+        .type_descriptions
+        .type_string
+        .as_deref()
+        .unwrap_or_default()
+}
+
+fn is_composite(type_label: &str) -> bool {
+    type_label.starts_with("struct ") || type_label.ends_with(']')
+}
+
+// Slot and offset of each value, in order
+fn assign_slots(sizes: &[TypeSize]) -> Vec<(u64, u64)> {
+    let mut positions = vec![];
+    let (mut slot, mut offset) = (0u64, 0u64);
+    for size in sizes {
+        if offset > 0 && (!size.packable() || offset + size.bytes > SLOT_SIZE) {
+            slot += 1;
+            offset = 0;
+        }
+        positions.push((slot, offset));
+        if size.packable() {
+            offset += size.bytes;
+        } else {
+            slot += size.bytes.div_ceil(SLOT_SIZE).max(1);
+        }
+    }
+    positions
+}
+
+fn type_size(context: &WorkspaceContext, type_label: &str) -> TypeSize {
+    let type_label = type_label
+        .trim_end_matches(" storage ref")
+        .trim_end_matches(" storage pointer");
+
+    // Static arrays (`uint8[40]`); dynamic ones only hold their length in place
+    if let Some(inner) = type_label.strip_suffix(']') {
+        if let Some((element, length)) = inner.rsplit_once('[') {
+            let Ok(length) = length.parse::<u64>() else {
+                return TypeSize::value(SLOT_SIZE);
+            };
+            let element = type_size(context, element);
+            let slots = if element.packable() {
+                length.div_ceil(SLOT_SIZE / element.bytes)
+            } else {
+                length.saturating_mul(element.bytes.div_ceil(SLOT_SIZE))
+            };
+            return TypeSize {
+                bytes: slots.saturating_mul(SLOT_SIZE),
+                composite: true,
+            };
+        }
+    }
+
+    if let Some(name) = type_label.strip_prefix("struct ") {
+        return struct_size(context, name);
+    }
+    if type_label.starts_with("enum ") || type_label == "bool" {
+        return TypeSize::value(1);
+    }
+    if type_label.starts_with("address") || type_label.starts_with("contract ") {
+        return TypeSize::value(20);
+    }
+    if type_label.starts_with("function ") {
+        // Address and selector for external function pointers, a code offset otherwise
+        let bytes = if type_label.contains(" external") {
+            24
+        } else {
+            8
+        };
+        return TypeSize::value(bytes);
+    }
+    for (prefix, bits_per_unit) in [("uint", 1), ("int", 1), ("bytes", 8)] {
+        if let Some(width) = type_label.strip_prefix(prefix) {
+            if let Ok(width) = width.parse::<u64>() {
+                return TypeSize::value(width * bits_per_unit / 8);
+            }
+        }
+    }
+
+    // uint256, mappings, dynamic arrays, string, bytes and anything not recognized take a full slot
+    TypeSize::value(SLOT_SIZE)
+}
+
+// `name` is the canonical name of the struct (`Contract.Struct` or `Struct`)
+fn struct_size(context: &WorkspaceContext, name: &str) -> TypeSize {
+    let short_name = name.rsplit('.').next().unwrap_or(name);
+    let members = context
+        .struct_definitions()
+        .into_iter()
+        .find(|s| s.canonical_name.as_deref() == Some(name) || s.name == short_name)
+        .map(|s| s.members.clone())
+        .unwrap_or_default();
+
+    let sizes = members
+        .iter()
+        .map(|member| type_size(context, type_label(member)))
+        .collect::<Vec<_>>();
+    let slots = assign_slots(&sizes)
+        .iter()
+        .zip(&sizes)
+        .map(|((slot, _), size)| slot + size.bytes.div_ceil(SLOT_SIZE).max(1))
+        .max()
+        .unwrap_or(1);
+    TypeSize {
+        bytes: slots * SLOT_SIZE,
+        composite: true,
+    }
+}
+
+#[cfg(test)]
+mod storage_layout_tests {
+
+    use crate::analysis::Analyses;
+    use crate::bot_utils::load_contracts;
+
+    use super::{compare_layouts, StorageConflict, StorageLayout};
+
+    const PACKING: &str = "./foundry_workspace/out/Packing.sol/Packed.json";
+
+    #[test]
+    fn test_computed_layout() {
+        let context = load_contracts(&[PACKING.into()]);
+        let analyses = Analyses::new(&context);
+        let layouts = analyses.storage_layouts();
+        let packed = context
+            .contract_definitions()
+            .into_iter()
+            .find(|c| c.name == "Packed")
+            .unwrap();
+        let layout = layouts.of(packed.id).unwrap();
+
+        let positions = layout
+            .entries
+            .iter()
+            .map(|e| (e.label.as_str(), e.slot, e.offset))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            vec![
+                ("a", 0, 0),
+                ("b", 0, 16),
+                ("c", 1, 0),
+                ("owner", 2, 0),
+                ("paused", 2, 20),
+                ("position", 3, 0),
+                ("small", 4, 0),
+                ("balances", 6, 0),
+            ]
+        );
+        assert_eq!(layout.slots_used(), 7);
+        // 11 bytes left in slot 2, the struct and the array are not packable
+        assert_eq!(layout.wasted_bytes(), 11);
+        assert_eq!(StorageLayout::optimal_slots(&layout.entries), 7);
+    }
+
+    #[test]
+    fn test_computed_layout_matches_the_compiler() {
+        let Some(expected) = StorageLayout::from_artifact_file(PACKING) else {
+            panic!(
+                "{} has no storageLayout, check `extra_output` in foundry.toml",
+                PACKING
+            );
+        };
+        let context = load_contracts(&[PACKING.into()]);
+        let packed = context
+            .contract_definitions()
+            .into_iter()
+            .find(|c| c.name == "Packed")
+            .unwrap();
+        let computed = StorageLayout::compute(&context, packed.id).unwrap();
+
+        let positions = |layout: &StorageLayout| {
+            layout
+                .entries
+                .iter()
+                .map(|e| (e.variable, e.slot, e.offset))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(positions(&computed), positions(&expected));
+    }
+
+    #[test]
+    fn test_compare_layouts() {
+        let context =
+            load_contracts(&["./foundry_workspace/out/Upgradeable.sol/VaultV1.json".into()]);
+        let analyses = Analyses::new(&context);
+        let layouts = analyses.storage_layouts();
+        let layout_of = |name: &str| {
+            let contract = context
+                .contract_definitions()
+                .into_iter()
+                .find(|c| c.name == name)
+                .unwrap();
+            layouts.of(contract.id).unwrap().clone()
+        };
+
+        // Shrinking the gap by the slot a new variable takes is compatible
+        assert!(compare_layouts(&layout_of("TokenV1"), &layout_of("TokenV2")).is_empty());
+
+        let conflicts = compare_layouts(&layout_of("VaultV1"), &layout_of("VaultV2"));
+        assert_eq!(conflicts.len(), 5);
+        assert!(conflicts.contains(&StorageConflict::Moved {
+            label: "owner".to_string(),
+            old_slot: 0,
+            old_offset: 0,
+            new_slot: 1,
+            new_offset: 0,
+        }));
+        assert!(conflicts.iter().any(
+            |c| matches!(c, StorageConflict::Retyped { label, slot: 2, .. } if label == "balances")
+        ));
+        assert!(conflicts.iter().any(
+            |c| matches!(c, StorageConflict::Inserted { label, slot: 3, overlaps } if label == "fee" && overlaps == "__gap")
+        ));
+        assert!(conflicts.contains(&StorageConflict::GapResized {
+            contract: "VaultV2".to_string(),
+            old_end: 50,
+            new_end: 51,
+        }));
+
+        assert_eq!(layout_of("MisplacedGap").gap_misuses().len(), 1);
+    }
+}
@@ -0,0 +1,539 @@
+use std::collections::{BTreeSet, HashMap};
+
+use aderyn_core::visitor::ast_visitor::Node;
+use aderyn_driver::context::browser::{
+    ExtractAssignments, ExtractFunctionCalls, ExtractIdentifiers, ExtractIndexAccesses,
+    ExtractMemberAccesses, ExtractVariableDeclarationStatements,
+};
+use aderyn_driver::context::workspace_context::{ASTNode, WorkspaceContext};
+use aderyn_driver::core_ast::{
+    BlockOrStatement, Expression, FunctionCall, FunctionDefinition, FunctionKind, Identifier,
+    NodeID, Statement, Visibility,
+};
+
+use super::call_graph::{CallGraph, CallKind};
+use super::cfg::is_revert_call;
+use super::expressions::{referenced_declaration, type_string};
+use super::Analyses;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TaintSource {
+    // Parameters of public / external functions (constructors excluded, the deployer picks them)
+    FunctionParameters,
+    MsgSender,
+    MsgData,
+    MsgValue,
+    TxOrigin,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TaintSink {
+    // Arguments of calls to functions with the given name (ex: `transferFrom`), all of them when
+    // `argument` is None
+    CallArgument {
+        function: String,
+        argument: Option<usize>,
+    },
+    // Address a `.call` / `.delegatecall` / `.staticcall` is made on
+    LowLevelCallTarget,
+    // Index used to access an array (mappings are not included)
+    ArrayIndex,
+}
+
+#[derive(Clone, Debug)]
+pub struct TaintConfig {
+    pub sources: Vec<TaintSource>,
+    pub sinks: Vec<TaintSink>,
+}
+
+impl Default for TaintConfig {
+    fn default() -> Self {
+        Self {
+            sources: vec![
+                TaintSource::FunctionParameters,
+                TaintSource::MsgSender,
+                TaintSource::MsgData,
+                TaintSource::MsgValue,
+                TaintSource::TxOrigin,
+            ],
+            sinks: vec![TaintSink::LowLevelCallTarget, TaintSink::ArrayIndex],
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SinkHit {
+    // FunctionDefinition the sink is in
+    pub function: NodeID,
+    // FunctionCall or IndexAccess node reaching the sink
+    pub node: NodeID,
+    pub sink: TaintSink,
+}
+
+// Taint propagation over function bodies.
+//
+// Taint starts at the configured sources and flows through local assignments, variable
+// declarations, struct / member accesses (`s.field` is tainted when `s` is) and internal call
+// arguments into the callee's parameters, until nothing changes anymore.
+//
+// Which variables are tainted is flow insensitive, each use of a variable is not: a use is clean when
+// the closest definition certainly made before it is (`from = msg.sender`, `require(from == msg.sender)`,
+// a clean initial value). Definitions under a branch, or in a loop around the use, are not certain, so
+// they leave the variable tainted.
+pub struct TaintAnalysis<'a> {
+    context: &'a WorkspaceContext,
+    call_graph: &'a CallGraph,
+    config: TaintConfig,
+    // Tainted declarations (parameters, locals, ...) per FunctionDefinition
+    tainted: HashMap<NodeID, BTreeSet<NodeID>>,
+}
+
+impl<'a> TaintAnalysis<'a> {
+    pub fn run(analyses: &'a Analyses, config: TaintConfig) -> Self {
+        let context = analyses.context();
+        let mut analysis = Self {
+            context,
+            call_graph: analyses.call_graph(),
+            config,
+            tainted: HashMap::new(),
+        };
+
+        let functions = context.function_definitions();
+        if analysis.has_source(TaintSource::FunctionParameters) {
+            for function in &functions {
+                let callable_from_outside = function.visibility == Visibility::External
+                    || function.visibility == Visibility::Public;
+                if callable_from_outside && function.kind != FunctionKind::Constructor {
+                    let parameters = function.parameters.parameters.iter().map(|p| p.id);
+                    analysis
+                        .tainted
+                        .entry(function.id)
+                        .or_default()
+                        .extend(parameters);
+                }
+            }
+        }
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for function in &functions {
+                changed |= analysis.propagate(function);
+            }
+        }
+
+        analysis
+    }
+
+    fn has_source(&self, source: TaintSource) -> bool {
+        self.config.sources.contains(&source)
+    }
+
+    fn taint(&mut self, function: NodeID, declaration: NodeID) -> bool {
+        self.tainted
+            .entry(function)
+            .or_default()
+            .insert(declaration)
+    }
+
+    // One propagation pass over `function`, returns whether anything new got tainted
+    fn propagate(&mut self, function: &FunctionDefinition) -> bool {
+        let mut changed = false;
+
+        for statement in ExtractVariableDeclarationStatements::from(function).extracted {
+            let tainted = statement
+                .initial_value
+                .as_ref()
+                .is_some_and(|value| self.is_tainted(function.id, value));
+            if tainted {
+                for declaration in statement.declarations.iter().flatten() {
+                    changed |= self.taint(function.id, declaration.id);
+                }
+            }
+        }
+
+        for assignment in ExtractAssignments::from(function).extracted {
+            if self.is_tainted(function.id, &assignment.right_hand_side) {
+                if let Some(declaration) = referenced_declaration(&assignment.left_hand_side) {
+                    changed |= self.taint(function.id, declaration);
+                }
+            }
+        }
+
+        let call_graph = self.call_graph;
+        for function_call in ExtractFunctionCalls::from(function).extracted {
+            // A call to a virtual function has an edge to each override it may run
+            let edges = call_graph
+                .callees(function.id)
+                .into_iter()
+                .filter(|e| e.call_site == function_call.id)
+                .filter(|e| e.kind != CallKind::External && e.kind != CallKind::Modifier);
+            for edge in edges {
+                let Some(ASTNode::FunctionDefinition(callee)) =
+                    self.context.nodes.get(&edge.callee)
+                else {
+                    continue;
+                };
+
+                // `x.f(a)` with `using L for T` passes `x` as the first parameter
+                let mut arguments = vec![];
+                if let (CallKind::Library, Expression::MemberAccess(member_access)) =
+                    (edge.kind, function_call.expression.as_ref())
+                {
+                    if callee.parameters.parameters.len() == function_call.arguments.len() + 1 {
+                        arguments.push(member_access.expression.as_ref());
+                    }
+                }
+                arguments.extend(function_call.arguments.iter());
+
+                for (argument, parameter) in arguments.iter().zip(&callee.parameters.parameters) {
+                    if self.is_tainted(function.id, argument) {
+                        changed |= self.taint(callee.id, parameter.id);
+                    }
+                }
+            }
+        }
+
+        changed
+    }
+
+    // Whether any part of `expression` (evaluated inside `function`) derives from a source
+    pub fn is_tainted(&self, function: NodeID, expression: &Expression) -> bool {
+        let tainted = self.tainted.get(&function);
+        let from_declaration =
+            ExtractIdentifiers::from(expression)
+                .extracted
+                .iter()
+                .any(|identifier| {
+                    tainted.is_some_and(|t| t.contains(&identifier.referenced_declaration))
+                        && !self.redefined_clean(function, identifier)
+                });
+        if from_declaration {
+            return true;
+        }
+
+        ExtractMemberAccesses::from(expression)
+            .extracted
+            .iter()
+            .any(|member_access| {
+                let Expression::Identifier(base) = member_access.expression.as_ref() else {
+                    return false;
+                };
+                let source = match (base.name.as_str(), member_access.member_name.as_str()) {
+                    ("msg", "sender") => TaintSource::MsgSender,
+                    ("msg", "data") | ("msg", "sig") => TaintSource::MsgData,
+                    ("msg", "value") => TaintSource::MsgValue,
+                    ("tx", "origin") => TaintSource::TxOrigin,
+                    _ => return false,
+                };
+                self.has_source(source)
+            })
+    }
+
+    // Whether the value `identifier` reads comes from a clean definition: the closest one found going
+    // back through the statements that certainly ran before it, block after enclosing block
+    fn redefined_clean(&self, function: NodeID, identifier: &Identifier) -> bool {
+        let variable = identifier.referenced_declaration;
+        let chain = self.context.get_parent_chain(identifier.id);
+        let enclosing = chain
+            .iter()
+            .filter_map(|node| node.id())
+            .collect::<BTreeSet<_>>();
+
+        for ancestor in &chain {
+            match ancestor {
+                ASTNode::Block(block) => {
+                    let position = block.statements.iter().position(|statement| {
+                        statement
+                            .get_node_id()
+                            .is_some_and(|id| enclosing.contains(&id))
+                    });
+                    let Some(position) = position else {
+                        continue;
+                    };
+                    for statement in block.statements[..position].iter().rev() {
+                        match self.definition(function, statement, variable) {
+                            Definition::Clean => return true,
+                            Definition::Tainted => return false,
+                            Definition::None => {}
+                        }
+                    }
+                }
+                // The next iterations run after the definitions made later in the loop
+                ASTNode::ForStatement(for_statement) if assigns(for_statement, variable) => {
+                    return false
+                }
+                ASTNode::WhileStatement(while_statement) if assigns(while_statement, variable) => {
+                    return false
+                }
+                ASTNode::FunctionDefinition(_) | ASTNode::ModifierDefinition(_) => return false,
+                _ => {}
+            }
+        }
+        false
+    }
+
+    // What `statement` certainly makes `variable` hold, evaluated inside `function`
+    fn definition(&self, function: NodeID, statement: &Statement, variable: NodeID) -> Definition {
+        let clean_if = |value: &Expression| {
+            if self.is_tainted(function, value) {
+                Definition::Tainted
+            } else {
+                Definition::Clean
+            }
+        };
+        match statement {
+            Statement::VariableDeclarationStatement(declaration_statement) => {
+                let declares = declaration_statement
+                    .declarations
+                    .iter()
+                    .flatten()
+                    .any(|declaration| declaration.id == variable);
+                match (&declaration_statement.initial_value, declares) {
+                    (_, false) => Definition::None,
+                    // Zero initialized
+                    (None, true) => Definition::Clean,
+                    (Some(value), true) if declaration_statement.declarations.len() == 1 => {
+                        clean_if(value)
+                    }
+                    (Some(_), true) => Definition::Tainted,
+                }
+            }
+            Statement::ExpressionStatement(expression_statement) => {
+                match &expression_statement.expression {
+                    Expression::Assignment(assignment)
+                        if assignment.operator == "="
+                            && is_variable(&assignment.left_hand_side, variable) =>
+                    {
+                        clean_if(&assignment.right_hand_side)
+                    }
+                    // `require(variable == value)`
+                    Expression::FunctionCall(function_call) if is_check(function_call) => {
+                        let condition = function_call.arguments.first();
+                        match condition.and_then(|condition| equal_to(condition, variable)) {
+                            Some(value) => clean_if(value),
+                            None => definition_in(statement, variable),
+                        }
+                    }
+                    _ => definition_in(statement, variable),
+                }
+            }
+            // `if (variable != value) revert(..)`
+            Statement::IfStatement(if_statement)
+                if if_statement.false_body.is_none() && reverts(&if_statement.true_body) =>
+            {
+                match different_from(&if_statement.condition, variable) {
+                    Some(value) => clean_if(value),
+                    None => definition_in(statement, variable),
+                }
+            }
+            _ => definition_in(statement, variable),
+        }
+    }
+
+    // Declarations tainted inside `function`
+    pub fn tainted_declarations(&self, function: NodeID) -> BTreeSet<NodeID> {
+        self.tainted.get(&function).cloned().unwrap_or_default()
+    }
+
+    // Every place a tainted value reaches one of the configured sinks
+    pub fn sink_hits(&self) -> Vec<SinkHit> {
+        let mut hits = vec![];
+        for function in self.context.function_definitions() {
+            for function_call in ExtractFunctionCalls::from(function).extracted {
+                for sink in &self.config.sinks {
+                    if self.call_reaches_sink(function.id, &function_call, sink) {
+                        hits.push(SinkHit {
+                            function: function.id,
+                            node: function_call.id,
+                            sink: sink.clone(),
+                        });
+                    }
+                }
+            }
+
+            if self.config.sinks.contains(&TaintSink::ArrayIndex) {
+                for index_access in ExtractIndexAccesses::from(function).extracted {
+                    let is_array = type_string(&index_access.base_expression)
+                        .is_some_and(|t| !t.starts_with("mapping(") && t.contains('['));
+                    let tainted_index =
+                        self.is_tainted(function.id, &index_access.index_expression);
+                    if is_array && tainted_index {
+                        hits.push(SinkHit {
+                            function: function.id,
+                            node: index_access.id,
+                            sink: TaintSink::ArrayIndex,
+                        });
+                    }
+                }
+            }
+        }
+        hits
+    }
+
+    fn call_reaches_sink(&self, function: NodeID, call: &FunctionCall, sink: &TaintSink) -> bool {
+        let callee = match call.expression.as_ref() {
+            Expression::FunctionCallOptions(options) => options.expression.as_ref(),
+            callee => callee,
+        };
+        match sink {
+            TaintSink::LowLevelCallTarget => match callee {
+                Expression::MemberAccess(member_access) => {
+                    matches!(
+                        member_access.member_name.as_str(),
+                        "call" | "delegatecall" | "staticcall"
+                    ) && self.is_tainted(function, &member_access.expression)
+                }
+                _ => false,
+            },
+            TaintSink::CallArgument {
+                function: name,
+                argument,
+            } => {
+                let called = match callee {
+                    Expression::Identifier(identifier) => &identifier.name,
+                    Expression::MemberAccess(member_access) => &member_access.member_name,
+                    _ => return false,
+                };
+                if called != name {
+                    return false;
+                }
+                match argument {
+                    Some(index) => call
+                        .arguments
+                        .get(*index)
+                        .is_some_and(|a| self.is_tainted(function, a)),
+                    None => call.arguments.iter().any(|a| self.is_tainted(function, a)),
+                }
+            }
+            TaintSink::ArrayIndex => false,
+        }
+    }
+}
+
+enum Definition {
+    // The variable holds a clean value after the statement
+    Clean,
+    // The statement may leave a tainted value in the variable
+    Tainted,
+    // The statement does not define the variable
+    None,
+}
+
+// Statements defining `variable` in a way `definition` does not follow may leave it tainted
+fn definition_in(statement: &Statement, variable: NodeID) -> Definition {
+    if assigns(statement, variable) {
+        Definition::Tainted
+    } else {
+        Definition::None
+    }
+}
+
+// Whether an assignment in `node` may change `variable` (`variable = ..`, `(variable, ..) = ..`,
+// `variable.field = ..`, ...)
+fn assigns<T: Node + ?Sized>(node: &T, variable: NodeID) -> bool {
+    ExtractAssignments::from(node)
+        .extracted
+        .iter()
+        .any(|assignment| {
+            ExtractIdentifiers::from(assignment.left_hand_side.as_ref())
+                .extracted
+                .iter()
+                .any(|identifier| identifier.referenced_declaration == variable)
+        })
+}
+
+fn is_variable(expression: &Expression, variable: NodeID) -> bool {
+    matches!(expression, Expression::Identifier(identifier) if identifier.referenced_declaration == variable)
+}
+
+fn is_check(function_call: &FunctionCall) -> bool {
+    matches!(
+        function_call.expression.as_ref(),
+        Expression::Identifier(identifier) if identifier.name == "require" || identifier.name == "assert"
+    )
+}
+
+// `value` of `variable == value` or `value == variable`
+fn equal_to(condition: &Expression, variable: NodeID) -> Option<&Expression> {
+    compared_to(condition, "==", variable)
+}
+
+// `value` of `variable != value` or `value != variable`
+fn different_from(condition: &Expression, variable: NodeID) -> Option<&Expression> {
+    compared_to(condition, "!=", variable)
+}
+
+fn compared_to<'a>(
+    condition: &'a Expression,
+    operator: &str,
+    variable: NodeID,
+) -> Option<&'a Expression> {
+    let Expression::BinaryOperation(operation) = condition else {
+        return None;
+    };
+    if operation.operator != operator {
+        return None;
+    }
+    let (left, right) = (
+        operation.left_expression.as_ref(),
+        operation.right_expression.as_ref(),
+    );
+    if is_variable(left, variable) {
+        Some(right)
+    } else if is_variable(right, variable) {
+        Some(left)
+    } else {
+        None
+    }
+}
+
+// `revert ..;` or `revert(..)`, alone or in a block
+fn reverts(body: &BlockOrStatement) -> bool {
+    let statement = match body {
+        BlockOrStatement::Block(block) => match block.statements.as_slice() {
+            [statement] => statement,
+            _ => return false,
+        },
+        BlockOrStatement::Statement(statement) => statement.as_ref(),
+    };
+    match statement {
+        Statement::RevertStatement(_) => true,
+        Statement::ExpressionStatement(expression_statement) => {
+            is_revert_call(&expression_statement.expression)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod taint_tests {
+
+    use crate::analysis::Analyses;
+    use crate::bot_utils::load_contracts;
+
+    use super::{TaintAnalysis, TaintConfig, TaintSink};
+
+    #[test]
+    fn test_taint_default_sinks() {
+        let context = load_contracts(&["./foundry_workspace/out/Taint.sol/Taint.json".into()]);
+        let analyses = Analyses::new(&context);
+        let analysis = TaintAnalysis::run(&analyses, TaintConfig::default());
+
+        // `target.call(data)` in `forward` and `prices[i]` in `price`, not `prices[initial]` in the
+        // constructor
+        let hits = analysis.sink_hits();
+        assert_eq!(hits.len(), 2);
+        assert!(hits.iter().any(|h| h.sink == TaintSink::LowLevelCallTarget));
+        assert!(hits.iter().any(|h| h.sink == TaintSink::ArrayIndex));
+
+        // `owner` of the internal `_pull` is tainted through the call in `pull`
+        let functions = context.function_definitions();
+        let pull = functions.iter().find(|f| f.name == "_pull").unwrap();
+        let owner = &pull.parameters.parameters[0];
+        assert!(analysis.tainted_declarations(pull.id).contains(&owner.id));
+
+        let constructor = functions.iter().find(|f| f.name.is_empty()).unwrap();
+        assert!(analysis.tainted_declarations(constructor.id).is_empty());
+    }
+}
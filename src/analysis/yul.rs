@@ -0,0 +1,74 @@
+use aderyn_driver::core_ast::{
+    InlineAssembly, YulBlock, YulExpression, YulFunctionCall, YulLiteralKind, YulStatement,
+};
+
+// Every Yul builtin or function call of an `assembly { ... }` block (ex: `call(gas(), a, 0, 0, 0, 0, 0)`),
+// read from its Yul AST, outer calls before the calls nested in their arguments
+pub fn yul_calls(assembly: &InlineAssembly) -> Vec<&YulFunctionCall> {
+    let mut calls = vec![];
+    if let Some(block) = &assembly.ast {
+        block_calls(block, &mut calls);
+    }
+    calls
+}
+
+// Whether `expression` is the literal `0` (or `0x0`, `false`, ...)
+pub fn is_zero(expression: &YulExpression) -> bool {
+    let YulExpression::YulLiteral(literal) = expression else {
+        return false;
+    };
+    match literal.kind {
+        YulLiteralKind::Number => literal.value.as_deref().is_some_and(|value| {
+            let digits = value.strip_prefix("0x").unwrap_or(value);
+            !digits.is_empty() && digits.chars().all(|c| c == '0')
+        }),
+        YulLiteralKind::Bool => literal.value.as_deref() == Some("false"),
+        _ => false,
+    }
+}
+
+fn block_calls<'a>(block: &'a YulBlock, calls: &mut Vec<&'a YulFunctionCall>) {
+    for statement in &block.statements {
+        statement_calls(statement, calls);
+    }
+}
+
+fn statement_calls<'a>(statement: &'a YulStatement, calls: &mut Vec<&'a YulFunctionCall>) {
+    match statement {
+        YulStatement::YulIf(node) => {
+            expression_calls(&node.condition, calls);
+            block_calls(&node.body, calls);
+        }
+        YulStatement::YulSwitch(node) => {
+            expression_calls(&node.expression, calls);
+            for case in &node.cases {
+                block_calls(&case.body, calls);
+            }
+        }
+        YulStatement::YulForLoop(node) => {
+            block_calls(&node.pre, calls);
+            expression_calls(&node.condition, calls);
+            block_calls(&node.post, calls);
+            block_calls(&node.body, calls);
+        }
+        YulStatement::YulAssignment(node) => expression_calls(&node.value, calls),
+        YulStatement::YulVariableDeclaration(node) => {
+            if let Some(value) = &node.value {
+                expression_calls(value, calls);
+            }
+        }
+        YulStatement::YulExpressionStatement(node) => expression_calls(&node.expression, calls),
+        YulStatement::YulFunctionDefinition(node) => block_calls(&node.body, calls),
+        YulStatement::YulBlock(node) => block_calls(node, calls),
+        YulStatement::YulLeave | YulStatement::YulBreak | YulStatement::YulContinue => {}
+    }
+}
+
+fn expression_calls<'a>(expression: &'a YulExpression, calls: &mut Vec<&'a YulFunctionCall>) {
+    if let YulExpression::YulFunctionCall(call) = expression {
+        calls.push(call);
+        for argument in &call.arguments {
+            expression_calls(argument, calls);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+// _   ___   _______ _   _  ________   __  _______   __  ___  ___  _________ _      _____
+// | \ | \ \ / /_   _| | | | | ___ \ \ / / |  ___\ \ / / / _ \ |  \/  || ___ \ |    |  ___|
+// |  \| |\ V /  | | | |_| | | |_/ /\ V /  | |__  \ V / / /_\ \| .  . || |_/ / |    | |__
+// | . ` | \ /   | | |  _  | | ___ \ \ /   |  __| /   \ |  _  || |\/| ||  __/| |    |  __|
+// | |\  | | |   | | | | | | | |_/ / | |   | |___/ /^\ \| | | || |  | || |   | |____| |___
+// \_| \_/ \_/   \_/ \_| |_/ \____/  \_/   \____/\/   \/\_| |_/\_|  |_/\_|   \_____/\____/
+//
+//  _____                    ______     _            _
+// |_   _|                   |  _  \   | |          | |
+//   | | ___ ___ _   _  ___  | | | |___| |_ ___  ___| |_ ___  _ __
+//   | |/ __/ __| | | |/ _ \ | | | / _ \ __/ _ \/ __| __/ _ \| '__|
+//  _| |\__ \__ \ |_| |  __/ | |/ /  __/ ||  __/ (__| || (_) | |
+//  \___/___/___/\__,_|\___| |___/ \___|\__\___|\___|\__\___/|_|
+//
+// This IssueDetector is responsible for finding `transferFrom` calls whose `from` is controlled by the caller.
+// The `detect` function does this following:
+//
+//   1. Accepts a WorkspaceContext as `context`.
+//   2. Runs the TaintAnalysis with the parameters of public / external functions as the only source
+//      (pulling from `msg.sender` is the expected pattern) and the first argument of `transferFrom` as the sink.
+//   3. Captures every `transferFrom` call the taint reaches, even through local variables, structs and internal calls,
+//      unless `from` is certainly overwritten with or checked against a clean value (ex: `msg.sender`) before the call.
+//   4. Returns true if any issues are found, otherwise false.
+//
+
+use std::{collections::BTreeMap, error::Error};
+
+use aderyn_driver::context::workspace_context::{ASTNode, WorkspaceContext};
+use aderyn_driver::core_ast::NodeID;
+use aderyn_driver::detection_modules::capture;
+use aderyn_driver::detector::{IssueDetector, IssueSeverity};
+
+use crate::analysis::taint::{TaintAnalysis, TaintConfig, TaintSink, TaintSource};
+use crate::analysis::Analyses;
+use crate::bot_detector::BotDetector;
+use crate::confidence::Confidence;
+
+#[derive(Default)]
+pub struct ArbitraryTransferFromDetector {
+    // Keys are source file name and line number
+    found_instances: BTreeMap<(String, usize, String), NodeID>,
+}
+
+impl IssueDetector for ArbitraryTransferFromDetector {
+    fn detect(&mut self, context: &WorkspaceContext) -> Result<bool, Box<dyn Error>> {
+        let config = TaintConfig {
+            sources: vec![TaintSource::FunctionParameters],
+            sinks: vec![TaintSink::CallArgument {
+                function: String::from("transferFrom"),
+                argument: Some(0),
+            }],
+        };
+
+        let analyses = Analyses::new(context);
+        for hit in TaintAnalysis::run(&analyses, config).sink_hits() {
+            if let Some(ASTNode::FunctionCall(function_call)) = context.nodes.get(&hit.node) {
+                capture!(self, context, function_call);
+            }
+        }
+
+        Ok(!self.found_instances.is_empty())
+    }
+
+    fn title(&self) -> String {
+        String::from("Arbitrary `from` passed to `transferFrom`")
+    }
+
+    fn description(&self) -> String {
+        String::from("The `from` address of a `transferFrom` call derives from a function parameter, so anyone can move tokens out of any account that approved this contract. Use `msg.sender` as `from`.")
+    }
+
+    fn severity(&self) -> IssueSeverity {
+        IssueSeverity::High
+    }
+
+    fn name(&self) -> String {
+        "arbitrary-transfer-from".to_string()
+    }
+
+    fn instances(&self) -> BTreeMap<(String, usize, String), NodeID> {
+        self.found_instances.clone()
+    }
+}
+
+impl BotDetector for ArbitraryTransferFromDetector {
+    // Only direct checks against a clean value are seen, not those made through allowances, signatures
+    // or conditions on other paths
+    fn confidence(&self) -> Confidence {
+        Confidence::Low
+    }
+}
+
+#[cfg(test)]
+mod arbitrary_transfer_from_tests {
+
+    use crate::bot_utils::load_contracts;
+    use crate::config_tests::tests_configuration;
+
+    use super::ArbitraryTransferFromDetector;
+
+    use aderyn_driver::context::workspace_context::WorkspaceContext;
+    use aderyn_driver::detector::IssueDetector;
+
+    fn test_arbitrary_transfer_from_for(
+        _contract_files: Vec<String>,
+        context: WorkspaceContext,
+        mut detector: impl IssueDetector,
+    ) {
+        // assert that the detector finds `_pull` and `pullEither`, not the calls pulling from a `from`
+        // overwritten with or checked against `msg.sender`
+        let found = detector.detect(&context).unwrap();
+        assert!(found);
+        let locations = detector
+            .instances()
+            .into_keys()
+            .map(|(file, line, _)| (file, line))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            locations,
+            vec![
+                (String::from("src/Taint.sol"), 32),
+                (String::from("src/Taint.sol"), 56)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_arbitrary_transfer_from() {
+        let detector = ArbitraryTransferFromDetector::default();
+        let contracts = tests_configuration().get_contracts_for(detector.name());
+
+        for contract_files in contracts {
+            let detector = ArbitraryTransferFromDetector::default();
+            let context = load_contracts(&contract_files);
+            test_arbitrary_transfer_from_for(contract_files, context, detector);
+        }
+    }
+}
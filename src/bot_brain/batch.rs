@@ -0,0 +1,281 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fmt::Write;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use aderyn_driver::context::workspace_context::WorkspaceContext;
+use aderyn_driver::detector::IssueDetector;
+use aderyn_driver::driver::{drive_with, Args};
+use serde::Deserialize;
+
+use crate::bot_detector::{BotDetector, DetectorLayer, Layered};
+
+// Manifest listing the projects of a batch run:
+//
+//   [[project]]
+//   root = "../contests/2024-01-vault"
+//   name = "vault"              # optional, defaults to the folder name of the root
+//   scope = ["src/"]            # optional
+//   exclude = ["src/mocks/"]    # optional
+//
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    project: Vec<Project>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Project {
+    root: String,
+    name: Option<String>,
+    scope: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+}
+
+// Instances found per detector during a project's run
+type Findings = Arc<Mutex<BTreeMap<String, usize>>>;
+
+enum Outcome {
+    Done(BTreeMap<String, usize>),
+    Failed(String),
+}
+
+// Records how many instances the wrapped detector found, as the driver consumes its subscriptions
+struct RecordingLayer {
+    findings: Findings,
+}
+
+impl DetectorLayer for RecordingLayer {
+    fn detect(
+        &mut self,
+        detector: &mut dyn BotDetector,
+        context: &WorkspaceContext,
+    ) -> Result<bool, Box<dyn Error>> {
+        let found = detector.detect(context)?;
+        self.findings
+            .lock()
+            .unwrap()
+            .insert(detector.name(), detector.instances().len());
+        Ok(found)
+    }
+}
+
+// Runs the detectors over every project of `manifest`, writing `<output>/<project>.md` for each and
+// `<output>/summary.md` with the number of findings per detector and project. A failing project is
+// reported in the summary and the batch moves on to the next one.
+pub fn batch(detectors: fn() -> Vec<Box<dyn BotDetector>>, manifest: &str, output: &str) {
+    let content = std::fs::read_to_string(manifest)
+        .unwrap_or_else(|err| panic!("Could not read manifest {}: {}", manifest, err));
+    let manifest: Manifest = toml::from_str(&content)
+        .unwrap_or_else(|err| panic!("Invalid manifest {}: {}", manifest, err));
+    std::fs::create_dir_all(output).unwrap();
+
+    let mut outcomes = vec![];
+    let mut names = BTreeSet::new();
+    for project in manifest.project {
+        let name = unique_name(&project, &mut names);
+        println!("[*] Running {} on {}", name, project.root);
+        let outcome = run_project(detectors, &project, &format!("{}/{}.md", output, name));
+        if let Outcome::Failed(reason) = &outcome {
+            eprintln!("[!] {} failed: {}", name, reason);
+        }
+        outcomes.push((name, outcome));
+    }
+
+    let detector_names = detectors().iter().map(|d| d.name()).collect::<Vec<_>>();
+    let summary = format!("{}/summary.md", output);
+    std::fs::write(&summary, summary_table(&detector_names, &outcomes)).unwrap();
+    println!("[*] Summary written to {}", summary);
+}
+
+fn run_project(
+    detectors: fn() -> Vec<Box<dyn BotDetector>>,
+    project: &Project,
+    report: &str,
+) -> Outcome {
+    if !Path::new(&project.root).is_dir() {
+        return Outcome::Failed(String::from("root folder not found"));
+    }
+
+    let findings = Findings::default();
+    let subscriptions = detectors()
+        .into_iter()
+        .map(|detector| {
+            let recording = Layered {
+                detector,
+                layer: RecordingLayer {
+                    findings: findings.clone(),
+                },
+            };
+            Box::new(recording) as Box<dyn IssueDetector>
+        })
+        .collect();
+    let args = Args {
+        root: project.root.clone(),
+        output: report.to_string(),
+        exclude: project.exclude.clone(),
+        scope: project.scope.clone(),
+        no_snippets: false,
+        stdout: false,
+    };
+
+    // Compilation errors and detector bugs panic, they must not end the batch
+    match catch_unwind(AssertUnwindSafe(|| drive_with(args, subscriptions))) {
+        Ok(()) => Outcome::Done(std::mem::take(&mut findings.lock().unwrap())),
+        Err(panic) => {
+            let reason = panic
+                .downcast_ref::<String>()
+                .cloned()
+                .or_else(|| panic.downcast_ref::<&str>().map(|s| s.to_string()))
+                .unwrap_or_else(|| String::from("panicked"));
+            Outcome::Failed(reason)
+        }
+    }
+}
+
+// Name of the project's report, suffixed when another project already uses it
+fn unique_name(project: &Project, names: &mut BTreeSet<String>) -> String {
+    let base = project.name.clone().unwrap_or_else(|| {
+        Path::new(&project.root)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| String::from("project"))
+    });
+    let base = base.replace(['/', '\\'], "-");
+    let mut name = base.clone();
+    let mut suffix = 2;
+    while !names.insert(name.clone()) {
+        name = format!("{}-{}", base, suffix);
+        suffix += 1;
+    }
+    name
+}
+
+fn summary_table(detector_names: &[String], outcomes: &[(String, Outcome)]) -> String {
+    let mut md = String::from("# Batch Summary\n\n");
+    _ = writeln!(md, "| Project | {} | Total |", detector_names.join(" | "));
+    _ = writeln!(md, "| --- |{} --- |", " --- |".repeat(detector_names.len()));
+
+    let mut totals = vec![0; detector_names.len()];
+    for (project, outcome) in outcomes {
+        let Outcome::Done(findings) = outcome else {
+            let failed = vec!["-"; detector_names.len()].join(" | ");
+            _ = writeln!(md, "| {} (failed) | {} | - |", project, failed);
+            continue;
+        };
+        let counts = detector_names
+            .iter()
+            .map(|name| findings.get(name).copied().unwrap_or_default())
+            .collect::<Vec<_>>();
+        counts
+            .iter()
+            .zip(totals.iter_mut())
+            .for_each(|(count, total)| *total += count);
+        let cells = counts.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        _ = writeln!(
+            md,
+            "| {} | {} | {} |",
+            project,
+            cells.join(" | "),
+            counts.iter().sum::<usize>()
+        );
+    }
+    let cells = totals.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+    _ = writeln!(
+        md,
+        "| **Total** | {} | {} |",
+        cells.join(" | "),
+        totals.iter().sum::<usize>()
+    );
+
+    let failures = outcomes
+        .iter()
+        .filter_map(|(project, outcome)| match outcome {
+            Outcome::Failed(reason) => Some((project, reason)),
+            Outcome::Done(_) => None,
+        })
+        .collect::<Vec<_>>();
+    if !failures.is_empty() {
+        md.push_str("\n## Failed projects\n\n");
+        for (project, reason) in failures {
+            _ = writeln!(
+                md,
+                "- {}: {}",
+                project,
+                reason.lines().next().unwrap_or_default()
+            );
+        }
+    }
+    md
+}
+
+#[cfg(test)]
+mod batch_tests {
+
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use super::{summary_table, unique_name, Outcome, Project};
+
+    fn project(root: &str, name: Option<&str>) -> Project {
+        Project {
+            root: root.to_string(),
+            name: name.map(str::to_string),
+            scope: None,
+            exclude: None,
+        }
+    }
+
+    #[test]
+    fn test_unique_names() {
+        let mut names = BTreeSet::new();
+        let vault = project("../contests/2024-01-vault", None);
+        assert_eq!(unique_name(&vault, &mut names), "2024-01-vault");
+        assert_eq!(unique_name(&vault, &mut names), "2024-01-vault-2");
+        assert_eq!(unique_name(&vault, &mut names), "2024-01-vault-3");
+
+        // Names become file names
+        let named = project("../contests/2024-02-token", Some("token/v2"));
+        assert_eq!(unique_name(&named, &mut names), "token-v2");
+        assert_eq!(unique_name(&project("/", None), &mut names), "project");
+    }
+
+    #[test]
+    fn test_summary_table() {
+        let detector_names = vec![String::from("a"), String::from("b")];
+        let outcomes = vec![
+            (
+                String::from("vault"),
+                Outcome::Done(BTreeMap::from([
+                    (String::from("a"), 2),
+                    (String::from("b"), 1),
+                ])),
+            ),
+            (
+                String::from("token"),
+                Outcome::Done(BTreeMap::from([(String::from("a"), 1)])),
+            ),
+            (
+                String::from("broken"),
+                Outcome::Failed(String::from("could not compile\nat src/Broken.sol")),
+            ),
+        ];
+
+        let expected = "# Batch Summary\n\n\
+            | Project | a | b | Total |\n\
+            | --- | --- | --- | --- |\n\
+            | vault | 2 | 1 | 3 |\n\
+            | token | 1 | 0 | 1 |\n\
+            | broken (failed) | - | - | - |\n\
+            | **Total** | 3 | 1 | 4 |\n\
+            \n## Failed projects\n\n\
+            - broken: could not compile\n";
+        assert_eq!(summary_table(&detector_names, &outcomes), expected);
+
+        // No failure, no section
+        let summary = summary_table(&detector_names, &outcomes[..2]);
+        assert!(!summary.contains("Failed projects"));
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::bot_detector::BotDetector;
+use crate::bot_utils::load_project;
+
+pub struct BenchmarkOptions {
+    // Foundry projects to run the detectors on
+    pub roots: Vec<String>,
+    // Where the timings of a known good run are stored
+    pub baseline: String,
+    // Overwrite the baseline with the timings of this run
+    pub save_baseline: bool,
+    // Number of times `detect()` is run per detector (the median is kept)
+    pub iterations: usize,
+    // Allowed slowdown (in percent) before a detector is reported as a regression
+    pub tolerance: f64,
+}
+
+// Slowdowns below this are considered noise no matter the percentage
+const NOISE_FLOOR_MS: f64 = 1.0;
+
+// root -> detector name -> median detect() time in milliseconds
+#[derive(Serialize, Deserialize, Default)]
+struct Baseline {
+    timings: BTreeMap<String, BTreeMap<String, f64>>,
+}
+
+pub fn benchmark(detectors: fn() -> Vec<Box<dyn BotDetector>>, options: BenchmarkOptions) {
+    let baseline = read_baseline(&options.baseline);
+    let mut current = Baseline::default();
+    let mut regressions = 0;
+    let mut failures = 0;
+
+    println!("| Root | Detector | Baseline (ms) | Current (ms) | Change | Status |");
+    println!("| --- | --- | --- | --- | --- | --- |");
+
+    for root in &options.roots {
+        let context = load_project(root);
+
+        let names = detectors().iter().map(|d| d.name()).collect::<Vec<_>>();
+        for (index, name) in names.into_iter().enumerate() {
+            let samples = (0..options.iterations.max(1))
+                .map(|_| {
+                    // Detectors accumulate instances, so every sample gets a fresh one
+                    let mut detector = detectors().remove(index);
+                    let start = Instant::now();
+                    detector.detect(&context).map(|_| start.elapsed())
+                })
+                .collect::<Result<Vec<Duration>, _>>();
+            // The time of a failed run says nothing, so it is neither compared nor saved
+            let mut samples = match samples {
+                Ok(samples) => samples,
+                Err(err) => {
+                    failures += 1;
+                    println!("| {} | {} | - | - | - | FAILED: {} |", root, name, err);
+                    continue;
+                }
+            };
+            samples.sort();
+            let median = samples[samples.len() / 2].as_secs_f64() * 1000.0;
+
+            let previous = baseline
+                .timings
+                .get(root)
+                .and_then(|timings| timings.get(&name))
+                .copied();
+
+            let status = compare(previous, median, options.tolerance);
+            if status == Status::Regression {
+                regressions += 1;
+            }
+            let change = previous.map_or(String::from("-"), |previous| {
+                format!("{:+.1}%", change_percent(previous, median))
+            });
+
+            println!(
+                "| {} | {} | {} | {:.3} | {} | {} |",
+                root,
+                name,
+                previous.map_or(String::from("-"), |p| format!("{:.3}", p)),
+                median,
+                change,
+                status.label()
+            );
+
+            current
+                .timings
+                .entry(root.to_string())
+                .or_default()
+                .insert(name, median);
+        }
+    }
+
+    if baseline.timings.is_empty() && !options.save_baseline {
+        println!(
+            "\n[!] {} holds no timings, record one with --save-baseline",
+            options.baseline
+        );
+    }
+
+    if options.save_baseline {
+        write_baseline(&options.baseline, &current);
+        println!("\n[*] Baseline saved to {}", options.baseline);
+    }
+
+    if failures > 0 {
+        println!("\n[!] {} detector run(s) failed", failures);
+    }
+    if regressions > 0 {
+        println!(
+            "\n[!] {} detector(s) slower than the baseline by more than {}%",
+            regressions, options.tolerance
+        );
+    }
+    if failures > 0 || regressions > 0 {
+        std::process::exit(1);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Status {
+    New,
+    Ok,
+    Regression,
+}
+
+impl Status {
+    fn label(&self) -> &'static str {
+        match self {
+            Status::New => "new",
+            Status::Ok => "ok",
+            Status::Regression => "REGRESSION",
+        }
+    }
+}
+
+fn change_percent(previous: f64, median: f64) -> f64 {
+    if previous > 0.0 {
+        (median - previous) / previous * 100.0
+    } else {
+        0.0
+    }
+}
+
+// A detector regressed when it is slower than `tolerance` percent and by more than the noise floor
+fn compare(previous: Option<f64>, median: f64, tolerance: f64) -> Status {
+    let Some(previous) = previous else {
+        return Status::New;
+    };
+    let regressed =
+        change_percent(previous, median) > tolerance && median - previous > NOISE_FLOOR_MS;
+    if regressed {
+        Status::Regression
+    } else {
+        Status::Ok
+    }
+}
+
+fn read_baseline(path: &str) -> Baseline {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_baseline(path: &str, baseline: &Baseline) {
+    if let Some(parent) = Path::new(path).parent() {
+        _ = std::fs::create_dir_all(parent);
+    }
+    let content = serde_json::to_string_pretty(baseline).unwrap();
+    std::fs::write(path, content).unwrap();
+}
+
+#[cfg(test)]
+mod benchmark_tests {
+
+    use super::{compare, read_baseline, write_baseline, Baseline, Status};
+
+    #[test]
+    fn test_regressions() {
+        assert_eq!(compare(None, 10.0, 20.0), Status::New);
+        assert_eq!(compare(Some(10.0), 11.5, 20.0), Status::Ok);
+        assert_eq!(compare(Some(10.0), 13.0, 20.0), Status::Regression);
+        // Faster is never a regression
+        assert_eq!(compare(Some(10.0), 2.0, 20.0), Status::Ok);
+    }
+
+    #[test]
+    fn test_noise_floor() {
+        // +100% but only half a millisecond slower
+        assert_eq!(compare(Some(0.5), 1.0, 20.0), Status::Ok);
+        assert_eq!(compare(Some(0.5), 1.6, 20.0), Status::Regression);
+        // A zero baseline has no meaningful percentage
+        assert_eq!(compare(Some(0.0), 5.0, 20.0), Status::Ok);
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let path = std::env::temp_dir().join(format!("nyth-baseline-{}.json", std::process::id()));
+        let path = path.to_string_lossy().to_string();
+        let mut baseline = Baseline::default();
+        baseline
+            .timings
+            .entry(String::from("./foundry_workspace"))
+            .or_default()
+            .insert(String::from("public-state-variables"), 0.25);
+        write_baseline(&path, &baseline);
+
+        let read = read_baseline(&path);
+        assert_eq!(
+            read.timings["./foundry_workspace"]["public-state-variables"],
+            0.25
+        );
+        assert!(read_baseline("./does-not-exist.json").timings.is_empty());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,5 +1,18 @@
+use crate::arbitrary_transfer_from::detector::ArbitraryTransferFromDetector;
+use crate::bot_detector::BotDetector;
+use crate::confidence::{
+    add_to_json_report, filter_by_confidence, rate_by_confidence, Confidence, SharedRatings,
+};
+use crate::floating_pragma::detector::FloatingPragmaDetector;
+use crate::inefficient_storage_packing::detector::InefficientStoragePackingDetector;
+use crate::outdated_solidity_version::detector::OutdatedSolidityVersionDetector;
 use crate::public_state_variables::detector::PublicStateVariablesDetector;
+use crate::push0_incompatible_version::detector::Push0IncompatibleVersionDetector;
+use crate::rule_engine::{rule_detectors, RULES_DIR};
 use crate::state_variable_is_never_used::detector::StateVariableIsNeverUsedDetector;
+use crate::state_variable_is_never_written::detector::StateVariableIsNeverWrittenDetector;
+use crate::state_variable_is_write_only::detector::StateVariableIsWriteOnlyDetector;
+use crate::upgradeable_storage_collision::detector::UpgradeableStorageCollisionDetector;
 // ADERYN-PILOT: 0x01 (Please feel free to fix above imports if they mess up)
 
 use std::{fs::OpenOptions, io::BufWriter, path::PathBuf};
@@ -21,12 +34,26 @@ use aderyn_driver::{
 };
 use serde::Serialize;
 
-fn custom_detectors() -> Vec<Box<dyn IssueDetector>> {
-    vec![
+use super::batch::batch;
+use super::benchmark::{benchmark, BenchmarkOptions};
+use super::evaluate::evaluate;
+
+fn custom_detectors() -> Vec<Box<dyn BotDetector>> {
+    let mut detectors: Vec<Box<dyn BotDetector>> = vec![
         Box::<StateVariableIsNeverUsedDetector>::default(),
 		Box::<PublicStateVariablesDetector>::default(),
+        Box::<StateVariableIsNeverWrittenDetector>::default(),
+        Box::<StateVariableIsWriteOnlyDetector>::default(),
+        Box::<ArbitraryTransferFromDetector>::default(),
+        Box::<InefficientStoragePackingDetector>::default(),
+        Box::<UpgradeableStorageCollisionDetector>::default(),
+        Box::<FloatingPragmaDetector>::default(),
+        Box::<OutdatedSolidityVersionDetector>::default(),
+        Box::<Push0IncompatibleVersionDetector>::default(),
         // ADERYN-PILOT: 0x02 CUSTOM DETECTORS - Do not remove this comment even if the array is empty
-    ]
+    ];
+    detectors.extend(rule_detectors(RULES_DIR));
+    detectors
 }
 
 pub fn refresh_metadata() {
@@ -47,6 +74,13 @@ pub fn refresh_metadata() {
 }
 
 pub fn generate_report_for_judge(root: &str, output: &str) {
+    let ratings = SharedRatings::default();
+    let json = output.ends_with(".json");
+    let detectors = if json {
+        rate_by_confidence(custom_detectors(), Confidence::Low, &ratings)
+    } else {
+        filter_by_confidence(custom_detectors(), Confidence::Low)
+    };
     drive_with(
         Args {
             root: root.to_string(),
@@ -56,17 +90,36 @@ pub fn generate_report_for_judge(root: &str, output: &str) {
             no_snippets: false,
             stdout: false,
         },
-        custom_detectors(),
-    )
+        detectors
+            .into_iter()
+            .map(|detector| detector as Box<dyn IssueDetector>)
+            .collect(),
+    );
+    if json {
+        add_to_json_report(output, &ratings.lock().unwrap()).unwrap();
+    }
+}
+
+pub fn benchmark_detectors(options: BenchmarkOptions) {
+    benchmark(custom_detectors, options)
+}
+
+pub fn evaluate_detectors(corpus: &str, output: &str) {
+    evaluate(custom_detectors, corpus, output)
+}
+
+pub fn batch_detectors(manifest: &str, output: &str) {
+    batch(custom_detectors, manifest, output)
 }
 
-impl From<Vec<Box<dyn IssueDetector>>> for Metadata {
-    fn from(detectors: Vec<Box<dyn IssueDetector>>) -> Self {
+impl From<Vec<Box<dyn BotDetector>>> for Metadata {
+    fn from(detectors: Vec<Box<dyn BotDetector>>) -> Self {
         let mut custom_bots = vec![];
         for detector in detectors {
             let custom_bot = CustomBot {
                 title: detector.title(),
                 severity: detector.severity().to_string(),
+                confidence: detector.confidence().to_string(),
                 description: detector.description(),
             };
             custom_bots.push(custom_bot);
@@ -83,6 +136,7 @@ struct Metadata {
 #[derive(Serialize)]
 struct CustomBot {
     severity: String,
+    confidence: String,
     title: String,
     description: String,
 }
\ No newline at end of file
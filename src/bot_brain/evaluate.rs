@@ -0,0 +1,273 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::bot_detector::BotDetector;
+use crate::bot_utils::load_project;
+
+// Sidecar file expected at the root of every labeled project
+pub const LABELS_FILE: &str = "nyth-labels.json";
+
+#[derive(Deserialize)]
+struct Labels {
+    findings: Vec<LabeledFinding>,
+}
+
+#[derive(Deserialize)]
+struct LabeledFinding {
+    detector: String,
+    file: String,
+    line: usize,
+    label: Label,
+}
+
+#[derive(Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum Label {
+    TruePositive,
+    FalsePositive,
+}
+
+type Location = (String, String, usize); // project, file, line
+
+#[derive(Default)]
+struct Score {
+    true_positives: usize,
+    false_positives: usize,
+    unlabeled: BTreeSet<Location>,
+    missed: BTreeSet<Location>,
+    // Projects the detector failed on, with the error
+    failed: BTreeMap<String, String>,
+}
+
+impl Score {
+    fn precision(&self) -> Option<f64> {
+        let reported = self.true_positives + self.false_positives;
+        (reported > 0).then(|| self.true_positives as f64 / reported as f64)
+    }
+
+    fn recall(&self) -> Option<f64> {
+        let expected = self.true_positives + self.missed.len();
+        (expected > 0).then(|| self.true_positives as f64 / expected as f64)
+    }
+
+    // Scores the (file, line) locations `detector` reported on `project` against its labels
+    fn record(
+        &mut self,
+        project: &str,
+        detector: &str,
+        reported: &BTreeSet<(String, usize)>,
+        labels: &Labels,
+    ) {
+        for (file, line) in reported {
+            let label = labels
+                .findings
+                .iter()
+                .find(|f| f.detector == detector && &f.file == file && &f.line == line)
+                .map(|f| &f.label);
+            match label {
+                Some(Label::TruePositive) => self.true_positives += 1,
+                Some(Label::FalsePositive) => self.false_positives += 1,
+                None => {
+                    self.unlabeled
+                        .insert((project.to_string(), file.clone(), *line));
+                }
+            }
+        }
+
+        labels
+            .findings
+            .iter()
+            .filter(|f| f.detector == detector && f.label == Label::TruePositive)
+            .filter(|f| !reported.contains(&(f.file.clone(), f.line)))
+            .for_each(|f| {
+                self.missed
+                    .insert((project.to_string(), f.file.clone(), f.line));
+            });
+    }
+}
+
+// Runs every detector over each labeled project found in `corpus` and writes a markdown
+// scorecard with per detector precision, recall and missed locations to `output`
+pub fn evaluate(detectors: fn() -> Vec<Box<dyn BotDetector>>, corpus: &str, output: &str) {
+    let mut scores: BTreeMap<String, Score> = BTreeMap::new();
+    for detector in detectors() {
+        scores.insert(detector.name(), Score::default());
+    }
+
+    for project in labeled_projects(Path::new(corpus)) {
+        let project_name = project.file_name().unwrap().to_string_lossy().to_string();
+        let labels = read_labels(&project);
+        println!("[*] Evaluating {}", project_name);
+
+        let context = load_project(&project.to_string_lossy());
+
+        for mut detector in detectors() {
+            let name = detector.name();
+            let score = scores.entry(name.clone()).or_default();
+
+            // A failed run reports nothing, so its labeled findings still count as missed
+            let reported = match detector.detect(&context) {
+                Ok(_) => detector
+                    .instances()
+                    .into_keys()
+                    .map(|(file, line, _)| (file, line))
+                    .collect::<BTreeSet<_>>(),
+                Err(err) => {
+                    eprintln!("[!] {} failed on {}: {}", name, project_name, err);
+                    score.failed.insert(project_name.clone(), err.to_string());
+                    BTreeSet::new()
+                }
+            };
+            score.record(&project_name, &name, &reported, &labels);
+        }
+    }
+
+    std::fs::write(output, scorecard(&scores)).unwrap();
+    println!("[*] Scorecard written to {}", output);
+}
+
+fn labeled_projects(corpus: &Path) -> Vec<std::path::PathBuf> {
+    let mut projects = std::fs::read_dir(corpus)
+        .unwrap_or_else(|err| panic!("Could not read corpus {}: {}", corpus.display(), err))
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join(LABELS_FILE).is_file())
+        .collect::<Vec<_>>();
+    projects.sort();
+    projects
+}
+
+fn read_labels(project: &Path) -> Labels {
+    let path = project.join(LABELS_FILE);
+    let content = std::fs::read_to_string(&path).unwrap();
+    serde_json::from_str(&content)
+        .unwrap_or_else(|err| panic!("Invalid labels in {}: {}", path.display(), err))
+}
+
+fn percentage(value: Option<f64>) -> String {
+    value.map_or(String::from("n/a"), |v| format!("{:.1}%", v * 100.0))
+}
+
+fn scorecard(scores: &BTreeMap<String, Score>) -> String {
+    let mut md = String::from("# Detector Scorecard\n\n");
+    md.push_str("| Detector | TP | FP | Unlabeled | Missed | Precision | Recall |\n");
+    md.push_str("| --- | --- | --- | --- | --- | --- | --- |\n");
+    for (name, score) in scores {
+        _ = writeln!(
+            md,
+            "| {} | {} | {} | {} | {} | {} | {} |",
+            name,
+            score.true_positives,
+            score.false_positives,
+            score.unlabeled.len(),
+            score.missed.len(),
+            percentage(score.precision()),
+            percentage(score.recall()),
+        );
+    }
+
+    for (name, score) in scores {
+        if score.missed.is_empty() && score.unlabeled.is_empty() && score.failed.is_empty() {
+            continue;
+        }
+        _ = writeln!(md, "\n## {}\n", name);
+        for (project, err) in &score.failed {
+            _ = writeln!(md, "- Failed: {} {}", project, err);
+        }
+        for (project, file, line) in &score.missed {
+            _ = writeln!(md, "- Missed: {} {}:{}", project, file, line);
+        }
+        for (project, file, line) in &score.unlabeled {
+            _ = writeln!(md, "- Unlabeled: {} {}:{}", project, file, line);
+        }
+    }
+
+    md
+}
+
+#[cfg(test)]
+mod evaluate_tests {
+
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use super::{scorecard, Labels, Score};
+
+    const LABELS: &str = r#"{
+        "findings": [
+            { "detector": "public-state-variables", "file": "src/Vault.sol", "line": 5, "label": "true_positive" },
+            { "detector": "public-state-variables", "file": "src/Vault.sol", "line": 6, "label": "false_positive" },
+            { "detector": "public-state-variables", "file": "src/Token.sol", "line": 9, "label": "true_positive" },
+            { "detector": "floating-pragma", "file": "src/Vault.sol", "line": 1, "label": "true_positive" }
+        ]
+    }"#;
+
+    fn reported(locations: &[(&str, usize)]) -> BTreeSet<(String, usize)> {
+        locations
+            .iter()
+            .map(|(file, line)| (file.to_string(), *line))
+            .collect()
+    }
+
+    #[test]
+    fn test_score_against_labels() {
+        let labels: Labels = serde_json::from_str(LABELS).unwrap();
+
+        let mut score = Score::default();
+        let found = reported(&[
+            ("src/Vault.sol", 5),
+            ("src/Vault.sol", 6),
+            ("src/Vault.sol", 7),
+        ]);
+        score.record("vault", "public-state-variables", &found, &labels);
+
+        assert_eq!(score.true_positives, 1);
+        assert_eq!(score.false_positives, 1);
+        assert_eq!(
+            score.unlabeled,
+            BTreeSet::from([(String::from("vault"), String::from("src/Vault.sol"), 7)])
+        );
+        assert_eq!(
+            score.missed,
+            BTreeSet::from([(String::from("vault"), String::from("src/Token.sol"), 9)])
+        );
+        assert_eq!(score.precision(), Some(0.5));
+        assert_eq!(score.recall(), Some(0.5));
+    }
+
+    #[test]
+    fn test_score_without_findings() {
+        let labels: Labels = serde_json::from_str(LABELS).unwrap();
+
+        // Nothing reported: precision is undefined and every true positive is missed
+        let mut score = Score::default();
+        score.record("vault", "floating-pragma", &BTreeSet::new(), &labels);
+        assert_eq!(score.precision(), None);
+        assert_eq!(score.recall(), Some(0.0));
+        assert_eq!(score.missed.len(), 1);
+
+        let scores = BTreeMap::from([(String::from("floating-pragma"), score)]);
+        let md = scorecard(&scores);
+        assert!(md.contains("| floating-pragma | 0 | 0 | 0 | 1 | n/a | 0.0% |"));
+        assert!(md.contains("- Missed: vault src/Vault.sol:1"));
+    }
+
+    #[test]
+    fn test_score_of_failed_run() {
+        let labels: Labels = serde_json::from_str(LABELS).unwrap();
+
+        let mut score = Score::default();
+        score
+            .failed
+            .insert(String::from("vault"), String::from("unresolved import"));
+        score.record("vault", "floating-pragma", &BTreeSet::new(), &labels);
+
+        let scores = BTreeMap::from([(String::from("floating-pragma"), score)]);
+        let md = scorecard(&scores);
+        assert!(md.contains("| floating-pragma | 0 | 0 | 0 | 1 | n/a | 0.0% |"));
+        assert!(md.contains("- Failed: vault unresolved import"));
+        assert!(md.contains("- Missed: vault src/Vault.sol:1"));
+    }
+}
@@ -1,3 +1,14 @@
+pub(crate) mod batch;
+pub(crate) mod benchmark;
 pub(crate) mod custom_detectors;
+pub(crate) mod evaluate;
+pub(crate) mod query;
+pub(crate) mod storage_diff;
+pub use benchmark::BenchmarkOptions;
+pub use custom_detectors::batch_detectors;
+pub use custom_detectors::benchmark_detectors;
+pub use custom_detectors::evaluate_detectors;
 pub use custom_detectors::generate_report_for_judge;
 pub use custom_detectors::refresh_metadata;
+pub use query::{promote_query, run_query};
+pub use storage_diff::storage_diff;
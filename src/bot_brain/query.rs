@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+
+use aderyn_driver::context::workspace_context::WorkspaceContext;
+use aderyn_driver::core_ast::NodeID;
+
+use crate::bot_utils::load_project;
+use crate::query::Query;
+use crate::rule_engine::{Rule, RuleFile, RULES_DIR};
+
+// Prints every match of `expression` in the project at `root` as `file:line`, followed by its captures
+pub fn run_query(expression: &str, root: &str) {
+    let query = Query::parse(expression).unwrap_or_else(|err| panic!("Invalid query: {}", err));
+    let context = load_project(root);
+
+    let matches = query.evaluate(&context);
+    for query_match in &matches {
+        let mut line = location(&context, query_match.node);
+        for (name, id) in &query_match.captures {
+            line.push_str(&format!("  @{}={}", name, location(&context, *id)));
+        }
+        println!("{}", line);
+    }
+    println!("[*] {} matches", matches.len());
+}
+
+// Saves `expression` as `rules/<name>.toml`, so that it runs as a detector like the other rules
+pub fn promote_query(expression: &str, name: &str, title: &str, description: &str, severity: &str) {
+    let rule = RuleFile {
+        name: name.to_string(),
+        title: title.to_string(),
+        description: description.to_string(),
+        severity: severity.to_string(),
+        confidence: None,
+        pattern: None,
+        query: Some(expression.to_string()),
+    };
+    let source = toml::to_string(&rule).unwrap();
+    // Fail before writing anything the runner could not load
+    if let Err(err) = Rule::parse(&source) {
+        panic!("Cannot promote the query: {}", err);
+    }
+
+    let path = PathBuf::from(RULES_DIR).join(format!("{}.toml", name));
+    if path.exists() {
+        panic!("{} already exists", path.display());
+    }
+    std::fs::create_dir_all(RULES_DIR).unwrap();
+    std::fs::write(&path, source).unwrap();
+    println!("[*] Query saved as {}", path.display());
+}
+
+fn location(context: &WorkspaceContext, id: NodeID) -> String {
+    match context.nodes.get(&id) {
+        Some(node) => {
+            let (file, line, _) = context.get_node_sort_key(node);
+            format!("{}:{}", file, line)
+        }
+        None => format!("<node {}>", id),
+    }
+}
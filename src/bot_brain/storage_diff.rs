@@ -0,0 +1,69 @@
+use std::path::Path;
+
+use aderyn_driver::context::workspace_context::WorkspaceContext;
+
+use crate::analysis::storage_layout::{compare_layouts, StorageLayout};
+use crate::bot_utils::{load_contracts, load_project};
+
+// Compares the storage layout of two versions of an upgradeable contract and prints the
+// conflicting slots. Each version is either a Foundry artifact (`out/Vault.sol/Vault.json`)
+// or a project root, in which case `contract` names the contract to compare.
+// Exits with 1 when the layouts are incompatible.
+pub fn storage_diff(old: &str, new: &str, contract: Option<&str>) {
+    let old_layout = load_layout(old, contract);
+    let new_layout = load_layout(new, contract);
+
+    let mut conflicts = compare_layouts(&old_layout, &new_layout);
+    conflicts.sort_by_key(|conflict| conflict.slot());
+
+    if conflicts.is_empty() {
+        println!("[*] Storage layouts are compatible");
+        return;
+    }
+
+    println!("| Slot | Variable | Conflict |");
+    println!("| --- | --- | --- |");
+    for conflict in &conflicts {
+        println!(
+            "| {} | {} | {} |",
+            conflict.slot(),
+            conflict.label(),
+            conflict
+        );
+    }
+    eprintln!("[!] {} storage conflicts found", conflicts.len());
+    std::process::exit(1);
+}
+
+fn load_layout(version: &str, contract: Option<&str>) -> StorageLayout {
+    let path = Path::new(version);
+    if path.is_file() {
+        // Prefer the compiler's own layout, when the artifact was built with it
+        if let Some(layout) = StorageLayout::from_artifact_file(version) {
+            return layout;
+        }
+        let name = contract
+            .map(|c| c.to_string())
+            .or_else(|| path.file_stem().map(|s| s.to_string_lossy().to_string()))
+            .unwrap();
+        return compute_layout(&load_contracts(&[version.to_string()]), &name, version);
+    }
+
+    let Some(name) = contract else {
+        panic!(
+            "--contract is required to compare project roots ({})",
+            version
+        );
+    };
+    let context = load_project(version);
+    compute_layout(&context, name, version)
+}
+
+fn compute_layout(context: &WorkspaceContext, name: &str, version: &str) -> StorageLayout {
+    context
+        .contract_definitions()
+        .into_iter()
+        .find(|c| c.name == name)
+        .and_then(|c| StorageLayout::compute(context, c.id))
+        .unwrap_or_else(|| panic!("Contract {} not found in {}", name, version))
+}
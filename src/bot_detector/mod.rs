@@ -0,0 +1,105 @@
+// Detectors as the bot subscribes them.
+//
+// The driver only knows `IssueDetector`, and a `Box<dyn IssueDetector>` does not tell what else the
+// detector behind it can do. The bot's own layers (confidence, fixes, cache) ask through `BotDetector`
+// instead.
+//
+// Each layer wraps the detector in a `Layered`, which implements both traits by forwarding to the
+// detector, except for what the `DetectorLayer` changes: how it runs, its description and instances.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use aderyn_driver::context::workspace_context::WorkspaceContext;
+use aderyn_driver::core_ast::NodeID;
+use aderyn_driver::detector::{IssueDetector, IssueSeverity};
+
+use crate::cache::UnitScopedDetector;
+use crate::confidence::Confidence;
+use crate::fixes::FixableDetector;
+
+pub trait BotDetector: IssueDetector {
+    // How likely the instances are to be real issues, where `severity` says how bad they are when they are
+    fn confidence(&self) -> Confidence;
+
+    // Confidence of one instance, given the node the detector captured
+    fn instance_confidence(&self, _context: &WorkspaceContext, _instance: NodeID) -> Confidence {
+        self.confidence()
+    }
+
+    // The detector itself when it suggests fixes for its instances
+    fn fixable(&self) -> Option<&dyn FixableDetector> {
+        None
+    }
+
+    // The detector itself when the cache may rerun it on the changed source units only
+    fn unit_scoped(&mut self) -> Option<&mut dyn UnitScopedDetector> {
+        None
+    }
+}
+
+// What a layer does around the detector it wraps. Layered detectors are handed to the driver, so layers
+// must be Send + Sync + 'static like any `IssueDetector`
+pub trait DetectorLayer: Send + Sync + 'static {
+    // Runs the detector, or replaces its run (ex: with saved instances)
+    fn detect(
+        &mut self,
+        detector: &mut dyn BotDetector,
+        context: &WorkspaceContext,
+    ) -> Result<bool, Box<dyn Error>>;
+
+    fn description(&self, detector: &dyn BotDetector) -> String {
+        detector.description()
+    }
+
+    // Keys are source file name and line number
+    fn instances(&self, detector: &dyn BotDetector) -> BTreeMap<(String, usize, String), NodeID> {
+        detector.instances()
+    }
+}
+
+pub struct Layered<L> {
+    pub(crate) detector: Box<dyn BotDetector>,
+    pub(crate) layer: L,
+}
+
+impl<L: DetectorLayer> IssueDetector for Layered<L> {
+    fn detect(&mut self, context: &WorkspaceContext) -> Result<bool, Box<dyn Error>> {
+        self.layer.detect(self.detector.as_mut(), context)
+    }
+
+    fn title(&self) -> String {
+        self.detector.title()
+    }
+
+    fn description(&self) -> String {
+        self.layer.description(self.detector.as_ref())
+    }
+
+    fn severity(&self) -> IssueSeverity {
+        self.detector.severity()
+    }
+
+    fn name(&self) -> String {
+        self.detector.name()
+    }
+
+    fn instances(&self) -> BTreeMap<(String, usize, String), NodeID> {
+        self.layer.instances(self.detector.as_ref())
+    }
+}
+
+// Not unit scoped: the layer decides what the instances are
+impl<L: DetectorLayer> BotDetector for Layered<L> {
+    fn confidence(&self) -> Confidence {
+        self.detector.confidence()
+    }
+
+    fn instance_confidence(&self, context: &WorkspaceContext, instance: NodeID) -> Confidence {
+        self.detector.instance_confidence(context, instance)
+    }
+
+    fn fixable(&self) -> Option<&dyn FixableDetector> {
+        self.detector.fixable()
+    }
+}
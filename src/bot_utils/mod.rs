@@ -1,36 +1,117 @@
-use std::collections::HashMap;
+pub mod solc;
 
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use aderyn_core::framework::foundry::load_foundry;
+use aderyn_core::visitor::ast_visitor::Node;
+use aderyn_driver::context::workspace_context::{ASTNode, WorkspaceContext};
+use aderyn_driver::core_ast::{NodeType, SourceUnit};
 use aderyn_driver::detector::{IssueDetector, ReusableDetector};
 
 pub struct Grounded; // Contract has been given but no detectors attached
 pub struct Launchable; // Contract has been given + detectors are attached
 
+// Describes how to pick the `using` / `within` nodes handed to a ReusableDetector in tests
+#[derive(Clone, Debug)]
+pub enum NodeSelector {
+    Contract(String), // ContractDefinition with the given name
+    Function(String), // FunctionDefinition with the given name
+    Kind(NodeType),   // Every node of the given kind
+}
+
+impl NodeSelector {
+    pub fn contract(name: &str) -> Self {
+        NodeSelector::Contract(name.to_string())
+    }
+
+    pub fn function(name: &str) -> Self {
+        NodeSelector::Function(name.to_string())
+    }
+
+    pub fn kind(node_type: NodeType) -> Self {
+        NodeSelector::Kind(node_type)
+    }
+
+    pub fn select(&self, context: &WorkspaceContext) -> Vec<ASTNode> {
+        let mut ids = match self {
+            NodeSelector::Contract(name) => context
+                .contract_definitions()
+                .into_iter()
+                .filter(|c| &c.name == name)
+                .map(|c| c.id)
+                .collect::<Vec<_>>(),
+            NodeSelector::Function(name) => context
+                .function_definitions()
+                .into_iter()
+                .filter(|f| &f.name == name)
+                .map(|f| f.id)
+                .collect::<Vec<_>>(),
+            NodeSelector::Kind(node_type) => context
+                .nodes
+                .iter()
+                .filter(|(_, node)| &node.node_type() == node_type)
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>(),
+        };
+
+        // Keep the selection in a stable order so that test runs are deterministic
+        ids.sort();
+        ids.iter()
+            .filter_map(|id| context.nodes.get(id))
+            .cloned()
+            .collect()
+    }
+}
+
 pub struct TestsTarget<Stage = Grounded> {
     stage: std::marker::PhantomData<Stage>,
-    pub filepath: String,
+    pub filepaths: Vec<ContractJSONFilepath>,
     pub issue_detectors: Vec<Box<dyn IssueDetector>>,
     pub reusables_detectors: Vec<Box<dyn ReusableDetector>>,
+    pub using: Vec<NodeSelector>,
+    pub within: Vec<NodeSelector>,
+    pub expected: Option<Vec<String>>,
 }
 
 impl TestsTarget<Grounded> {
     pub fn new(filepath: &str) -> Self {
+        Self::new_with_artifacts(&[filepath])
+    }
+
+    // Several artifacts loaded into one WorkspaceContext (ex: a contract and the base it imports)
+    pub fn new_with_artifacts(filepaths: &[&str]) -> Self {
         Self {
             stage: std::marker::PhantomData::<Grounded>,
-            filepath: filepath.to_string(),
+            filepaths: filepaths.iter().map(|f| f.to_string()).collect(),
             issue_detectors: vec![],
             reusables_detectors: vec![],
+            using: vec![],
+            within: vec![],
+            expected: None,
         }
     }
 
+    // Every artifact under `directory` (ex: `./foundry_workspace/out`) loaded into one WorkspaceContext.
+    // The directory is only listed when a test asks for the target, so a missing build fails that test
+    // alone rather than the whole `tests_configuration()`.
+    pub fn new_with_directory(directory: &str) -> Self {
+        Self::new_with_artifacts(&[directory])
+    }
+
     pub fn with_issue_detector(self, detector: Box<dyn IssueDetector>) -> TestsTarget<Launchable> {
         let mut detectors_so_far = self.issue_detectors;
         detectors_so_far.push(detector);
 
         TestsTarget::<Launchable> {
             stage: std::marker::PhantomData::<Launchable>,
-            filepath: self.filepath,
+            filepaths: self.filepaths,
             issue_detectors: detectors_so_far,
             reusables_detectors: self.reusables_detectors,
+            using: self.using,
+            within: self.within,
+            expected: self.expected,
         }
     }
 
@@ -43,9 +124,12 @@ impl TestsTarget<Grounded> {
 
         TestsTarget::<Launchable> {
             stage: std::marker::PhantomData::<Launchable>,
-            filepath: self.filepath,
+            filepaths: self.filepaths,
             issue_detectors: self.issue_detectors,
             reusables_detectors: detectors_so_far,
+            using: self.using,
+            within: self.within,
+            expected: self.expected,
         }
     }
 }
@@ -56,9 +140,12 @@ impl TestsTarget<Launchable> {
         detectors_so_far.push(detector);
         TestsTarget::<Launchable> {
             stage: std::marker::PhantomData::<Launchable>,
-            filepath: self.filepath,
+            filepaths: self.filepaths,
             issue_detectors: detectors_so_far,
             reusables_detectors: self.reusables_detectors,
+            using: self.using,
+            within: self.within,
+            expected: self.expected,
         }
     }
 
@@ -70,47 +157,260 @@ impl TestsTarget<Launchable> {
         detectors_so_far.push(detector);
         TestsTarget::<Launchable> {
             stage: std::marker::PhantomData::<Launchable>,
-            filepath: self.filepath,
+            filepaths: self.filepaths,
             issue_detectors: self.issue_detectors,
             reusables_detectors: detectors_so_far,
+            using: self.using,
+            within: self.within,
+            expected: self.expected,
+        }
+    }
+}
+
+impl<Stage> TestsTarget<Stage> {
+    // Nodes passed to the reusable detectors as `using`
+    pub fn using(mut self, selector: NodeSelector) -> Self {
+        self.using.push(selector);
+        self
+    }
+
+    // Nodes passed to the reusable detectors as `within`
+    pub fn within(mut self, selector: NodeSelector) -> Self {
+        self.within.push(selector);
+        self
+    }
+
+    // Names of the nodes the reusable detectors are expected to return (order does not matter)
+    pub fn expecting(mut self, names: &[&str]) -> Self {
+        self.expected = Some(names.iter().map(|n| n.to_string()).collect());
+        self
+    }
+}
+
+// Everything needed to drive a ReusableDetector against one TestsTarget
+#[derive(Clone, Debug)]
+pub struct ReusableTestCase {
+    pub filepaths: Vec<ContractJSONFilepath>,
+    pub using: Vec<NodeSelector>,
+    pub within: Vec<NodeSelector>,
+    pub expected: Option<Vec<String>>,
+}
+
+impl ReusableTestCase {
+    pub fn run(&self, detector: &mut impl ReusableDetector) {
+        let context = load_contracts(&self.filepaths);
+        let using = select_all(&self.using, &context);
+        let within = select_all(&self.within, &context);
+
+        let results = detector.detect(&context, &using, &within).unwrap();
+
+        match &self.expected {
+            Some(expected) => {
+                let mut found = results.iter().filter_map(node_name).collect::<Vec<_>>();
+                let mut expected = expected.clone();
+                found.sort();
+                expected.sort();
+                assert_eq!(
+                    found,
+                    expected,
+                    "{} returned unexpected nodes for {}",
+                    detector.name(),
+                    self.filepaths.join(", ")
+                );
+            }
+            None => assert!(!results.is_empty()),
         }
     }
 }
 
+fn select_all(selectors: &[NodeSelector], context: &WorkspaceContext) -> Vec<ASTNode> {
+    selectors.iter().flat_map(|s| s.select(context)).collect()
+}
+
+// Name used to compare a detector's output against `TestsTarget::expecting`
+pub fn node_name(node: &ASTNode) -> Option<String> {
+    match node {
+        ASTNode::ContractDefinition(n) => Some(n.name.clone()),
+        ASTNode::FunctionDefinition(n) => Some(n.name.clone()),
+        ASTNode::ModifierDefinition(n) => Some(n.name.clone()),
+        ASTNode::VariableDeclaration(n) => Some(n.name.clone()),
+        ASTNode::EventDefinition(n) => Some(n.name.clone()),
+        ASTNode::ErrorDefinition(n) => Some(n.name.clone()),
+        ASTNode::StructDefinition(n) => Some(n.name.clone()),
+        ASTNode::EnumDefinition(n) => Some(n.name.clone()),
+        ASTNode::Identifier(n) => Some(n.name.clone()),
+        ASTNode::MemberAccess(n) => Some(n.member_name.clone()),
+        _ => None,
+    }
+}
+
+// Loads a Foundry project the way the driver does before running its detectors: `forge build --ast`
+// (skipped when ADERYN_SKIP_BUILD=1), then the artifacts of the sources under its `src` folder.
+pub fn load_project(root: &str) -> WorkspaceContext {
+    let foundry = load_foundry(&PathBuf::from(root))
+        .unwrap_or_else(|err| panic!("Could not load Foundry project {}: {}", root, err));
+    let artifacts = foundry
+        .output_filepaths
+        .iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    load_contracts(&artifacts)
+}
+
+// Loads every given Foundry artifact into a single WorkspaceContext. Artifacts that share a
+// source file (one per contract in that file) are only absorbed once.
+pub fn load_contracts(filepaths: &[ContractJSONFilepath]) -> WorkspaceContext {
+    let mut context = WorkspaceContext::default();
+    let mut seen_sources = HashSet::new();
+
+    for filepath in filepaths {
+        let mut ast = match read_artifact_ast(filepath) {
+            Ok(Some(ast)) => ast,
+            Ok(None) => continue,
+            Err(err) => {
+                eprintln!("[!] Skipping artifact {}: {}", filepath, err);
+                continue;
+            }
+        };
+        if !seen_sources.insert(ast.absolute_path.clone()) {
+            continue;
+        }
+
+        // Artifacts live in `<root>/out/..`, their sources are relative to `<root>`
+        let mut source_path = PathBuf::new();
+        for component in Path::new(filepath).components() {
+            if component.as_os_str() == "out" {
+                break;
+            }
+            source_path.push(component);
+        }
+        if let Some(absolute_path) = &ast.absolute_path {
+            source_path.push(absolute_path);
+        }
+        ast.source = std::fs::read_to_string(&source_path).ok();
+        // Lets the analyses find the artifacts next to the sources (see `StorageLayouts::build`)
+        context
+            .src_filepaths
+            .push(source_path.to_string_lossy().to_string());
+
+        ast.accept(&mut context).unwrap_or_default();
+    }
+
+    context
+}
+
+// Ok(None) for build-info and other non contract files, which do not carry an AST
+fn read_artifact_ast(filepath: &str) -> Result<Option<SourceUnit>, Box<dyn Error>> {
+    let content =
+        std::fs::read_to_string(filepath).map_err(|err| format!("could not read it: {}", err))?;
+    let artifact: serde_json::Value =
+        serde_json::from_str(&content).map_err(|err| format!("not valid JSON: {}", err))?;
+
+    let Some(ast) = artifact.get("ast") else {
+        return Ok(None);
+    };
+    let ast =
+        serde_json::from_value(ast.clone()).map_err(|err| format!("unreadable AST: {}", err))?;
+    Ok(Some(ast))
+}
+
+fn artifacts_in(directory: &Path) -> Vec<ContractJSONFilepath> {
+    let mut artifacts = vec![];
+    let entries = std::fs::read_dir(directory)
+        .unwrap_or_else(|err| panic!("Could not read {}: {}", directory.display(), err));
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            artifacts.extend(artifacts_in(&path));
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            artifacts.push(path.to_string_lossy().to_string());
+        }
+    }
+    artifacts.sort();
+    artifacts
+}
+
+// Directories given to `TestsTarget::new_with_directory` replaced by the artifacts they hold
+fn expand_directories(filepaths: &[ContractJSONFilepath]) -> ContractJSONFilepaths {
+    filepaths
+        .iter()
+        .flat_map(|filepath| {
+            let path = Path::new(filepath);
+            if path.extension().is_some_and(|ext| ext == "json") {
+                vec![filepath.clone()]
+            } else {
+                artifacts_in(path)
+            }
+        })
+        .collect()
+}
+
 pub type DetectorName = String;
 pub type ContractJSONFilepath = String;
+pub type ContractJSONFilepaths = Vec<ContractJSONFilepath>;
 pub struct TestsConfig {
-    store: HashMap<DetectorName, Vec<ContractJSONFilepath>>,
+    store: HashMap<DetectorName, Vec<ContractJSONFilepaths>>,
+    reusable_store: HashMap<DetectorName, Vec<ReusableTestCase>>,
 }
 
 impl From<Vec<TestsTarget<Launchable>>> for TestsConfig {
     fn from(tests_targets: Vec<TestsTarget<Launchable>>) -> Self {
-        let mut store: HashMap<DetectorName, Vec<ContractJSONFilepath>> = HashMap::default();
+        let mut store: HashMap<DetectorName, Vec<ContractJSONFilepaths>> = HashMap::default();
+        let mut reusable_store: HashMap<DetectorName, Vec<ReusableTestCase>> =
+            HashMap::default();
 
         tests_targets.iter().for_each(|t| {
-            let filepath = t.filepath.to_string();
+            let filepaths = t.filepaths.clone();
             t.issue_detectors.iter().for_each(|d| {
                 if let Some(ptr) = store.get_mut(&d.name()) {
-                    ptr.push(filepath.clone());
+                    ptr.push(filepaths.clone());
                 } else {
-                    store.insert(d.name(), vec![filepath.clone()]);
+                    store.insert(d.name(), vec![filepaths.clone()]);
                 }
             });
             t.reusables_detectors.iter().for_each(|d| {
                 if let Some(ptr) = store.get_mut(&d.name()) {
-                    ptr.push(filepath.clone());
+                    ptr.push(filepaths.clone());
                 } else {
-                    store.insert(d.name(), vec![filepath.clone()]);
+                    store.insert(d.name(), vec![filepaths.clone()]);
                 }
+                let case = ReusableTestCase {
+                    filepaths: filepaths.clone(),
+                    using: t.using.clone(),
+                    within: t.within.clone(),
+                    expected: t.expected.clone(),
+                };
+                reusable_store.entry(d.name()).or_default().push(case);
             });
         });
 
-        Self { store }
+        Self {
+            store,
+            reusable_store,
+        }
     }
 }
 
 impl TestsConfig {
-    pub fn get_contracts_for(&self, detector_id: DetectorName) -> Vec<ContractJSONFilepath> {
-        self.store.get(&detector_id).unwrap_or(&vec![]).clone()
+    // Each entry is one TestsTarget: the artifacts to load together with `load_contracts`
+    pub fn get_contracts_for(&self, detector_id: DetectorName) -> Vec<ContractJSONFilepaths> {
+        self.store
+            .get(&detector_id)
+            .unwrap_or(&vec![])
+            .iter()
+            .map(|filepaths| expand_directories(filepaths))
+            .collect()
+    }
+
+    pub fn get_reusable_cases_for(&self, detector_id: DetectorName) -> Vec<ReusableTestCase> {
+        self.reusable_store
+            .get(&detector_id)
+            .unwrap_or(&vec![])
+            .iter()
+            .map(|case| ReusableTestCase {
+                filepaths: expand_directories(&case.filepaths),
+                ..case.clone()
+            })
+            .collect()
     }
 }
@@ -0,0 +1,107 @@
+use std::io::{ErrorKind, Write};
+use std::process::{Command, Stdio};
+
+use aderyn_core::visitor::ast_visitor::Node;
+use aderyn_driver::context::workspace_context::WorkspaceContext;
+use aderyn_driver::core_ast::SourceUnit;
+use serde_json::{json, Value};
+
+// Set this environment variable to use a `solc` binary that is not on the PATH
+pub const SOLC_PATH_ENV: &str = "NYTH_SOLC";
+
+// Name given to the inline source when handing it to solc (shows up in findings)
+pub const INLINE_SOURCE_NAME: &str = "Inline.sol";
+
+pub fn solc_path() -> String {
+    std::env::var(SOLC_PATH_ENV).unwrap_or_else(|_| "solc".to_string())
+}
+
+pub enum SolcError {
+    // The solc binary could not be found / started
+    Unavailable(String),
+    // solc ran but rejected the source or returned something unexpected
+    Compilation(String),
+}
+
+// Compiles `source` with the local solc (standard JSON over stdin) and returns its AST
+pub fn compile_solidity(source: &str) -> Result<SourceUnit, SolcError> {
+    let solc = solc_path();
+    let input = json!({
+        "language": "Solidity",
+        "sources": { INLINE_SOURCE_NAME: { "content": source } },
+        "settings": { "outputSelection": { "*": { "": ["ast"] } } }
+    });
+
+    let mut child = Command::new(&solc)
+        .arg("--standard-json")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| match err.kind() {
+            ErrorKind::NotFound => SolcError::Unavailable(format!("`{}` was not found", solc)),
+            _ => SolcError::Unavailable(format!("`{}` could not be started: {}", solc, err)),
+        })?;
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.to_string().as_bytes())
+        .map_err(|err| SolcError::Unavailable(err.to_string()))?;
+
+    let output = child
+        .wait_with_output()
+        .map_err(|err| SolcError::Unavailable(err.to_string()))?;
+
+    let output: Value = serde_json::from_slice(&output.stdout)
+        .map_err(|err| SolcError::Compilation(format!("unreadable solc output: {}", err)))?;
+
+    let errors = output["errors"]
+        .as_array()
+        .map(|errors| {
+            errors
+                .iter()
+                .filter(|e| e["severity"] == "error")
+                .filter_map(|e| e["formattedMessage"].as_str())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    if !errors.is_empty() {
+        return Err(SolcError::Compilation(errors.join("\n")));
+    }
+
+    let ast = output["sources"][INLINE_SOURCE_NAME]["ast"].clone();
+    let mut source_unit: SourceUnit = serde_json::from_value(ast)
+        .map_err(|err| SolcError::Compilation(format!("unreadable AST: {}", err)))?;
+    source_unit.source = Some(source.to_string());
+
+    Ok(source_unit)
+}
+
+// Builds a WorkspaceContext out of an inline Solidity string so small focused cases can live
+// right next to the detector they test.
+//
+// Returns None (after printing why) when solc is not installed, so callers can skip:
+//
+//   let Some(context) = load_solidity(SOURCE) else { return; };
+//
+pub fn load_solidity(source: &str) -> Option<WorkspaceContext> {
+    match compile_solidity(source) {
+        Ok(source_unit) => {
+            let mut context = WorkspaceContext::default();
+            source_unit.accept(&mut context).unwrap_or_default();
+            Some(context)
+        }
+        Err(SolcError::Unavailable(reason)) => {
+            eprintln!(
+                "[!] Skipping inline Solidity test: {}. Install solc or set {} to its path.",
+                reason, SOLC_PATH_ENV
+            );
+            None
+        }
+        Err(SolcError::Compilation(reason)) => {
+            panic!("Inline Solidity failed to compile:\n{}", reason)
+        }
+    }
+}
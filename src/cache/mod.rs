@@ -0,0 +1,517 @@
+// Incremental runs: the instances of every detector are saved in `.nyth-cache`, grouped by source unit
+// and keyed by a fingerprint of the unit's contents and of everything it imports, transitively.
+//
+// Saved instances are dropped when the detector itself changed: a rebuild of the bot for Rust detectors,
+// a rebuild or an edit of the rule file for rules. Otherwise, the instances of the units whose key did
+// not change are mapped back onto the nodes of the new context, and:
+//
+//   - unit scoped detectors (`UnitScopedDetector`: pragma checks, rules) only run on the changed units,
+//     as their instances in a unit depend on nothing but the unit and its imports;
+//   - other detectors see the whole workspace (ex: a variable of `Base` is used in `Child`), so they
+//     run again on all of it as soon as one unit changed, and only reuse their instances when none did.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use aderyn_driver::context::workspace_context::{ASTNode, WorkspaceContext};
+use aderyn_driver::core_ast::{NodeID, NodeType};
+use aderyn_driver::detector::IssueDetector;
+use serde::{Deserialize, Serialize};
+
+use crate::bot_detector::{BotDetector, DetectorLayer, Layered};
+use crate::rule_engine::{load_rules, RULES_DIR};
+
+pub const CACHE_DIR: &str = ".nyth-cache";
+
+// Instances as detectors keep them, keys are source file name, line number and description
+type Instances = BTreeMap<(String, usize, String), NodeID>;
+
+// 64 bit FNV-1a. Fingerprints are saved to disk, so unlike `DefaultHasher` they must not change with
+// the Rust release or the platform.
+pub fn fingerprint(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+// Detectors whose instances in a source unit only depend on that unit and the units it imports
+pub trait UnitScopedDetector: IssueDetector {
+    // Same as `detect`, only capturing the instances of the source units whose `absolute_path` is in `units`
+    fn detect_units(
+        &mut self,
+        context: &WorkspaceContext,
+        units: &BTreeSet<String>,
+    ) -> Result<bool, Box<dyn Error>>;
+}
+
+// Keys of the source units of a context, by `absolute_path`
+pub struct SourceKeys {
+    keys: BTreeMap<String, u64>,
+    // Transitive imports of each source unit
+    imports: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl SourceKeys {
+    pub fn build(context: &WorkspaceContext) -> Self {
+        let mut contents = BTreeMap::new();
+        let mut direct_imports: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for source_unit in context.source_units() {
+            if let Some(path) = &source_unit.absolute_path {
+                let source = source_unit.source.as_deref().unwrap_or_default();
+                contents.insert(path.clone(), fingerprint(source.as_bytes()));
+                direct_imports.entry(path.clone()).or_default();
+            }
+        }
+        for import in context.import_directives() {
+            let Some(ASTNode::SourceUnit(source_unit)) =
+                context.get_closest_parent(import.id, NodeType::SourceUnit)
+            else {
+                continue;
+            };
+            if let (Some(importer), Some(imported)) =
+                (&source_unit.absolute_path, &import.absolute_path)
+            {
+                direct_imports
+                    .entry(importer.clone())
+                    .or_default()
+                    .insert(imported.clone());
+            }
+        }
+
+        let mut keys = BTreeMap::new();
+        let mut imports = BTreeMap::new();
+        for path in contents.keys() {
+            let mut reached = BTreeSet::new();
+            let mut pending = vec![path];
+            while let Some(current) = pending.pop() {
+                for imported in direct_imports.get(current).into_iter().flatten() {
+                    if imported != path && reached.insert(imported.clone()) {
+                        pending.push(imported);
+                    }
+                }
+            }
+            let mut inputs = vec![];
+            for input in std::iter::once(path).chain(reached.iter()) {
+                inputs.extend_from_slice(input.as_bytes());
+                inputs.push(0);
+                // Imports missing from the context have no contents, so that they show up once compiled
+                if let Some(content) = contents.get(input) {
+                    inputs.extend_from_slice(&content.to_le_bytes());
+                }
+            }
+            keys.insert(path.clone(), fingerprint(&inputs));
+            imports.insert(path.clone(), reached);
+        }
+        Self { keys, imports }
+    }
+
+    pub fn key(&self, file: &str) -> Option<u64> {
+        self.keys.get(file).copied()
+    }
+
+    pub fn imports(&self, file: &str) -> Option<&BTreeSet<String>> {
+        self.imports.get(file)
+    }
+}
+
+// Instance keys (file, line, src) of the context, to map saved instances back onto nodes
+struct NodeKeys(HashMap<(String, usize, String), NodeID>);
+
+impl NodeKeys {
+    fn build(context: &WorkspaceContext) -> Self {
+        Self(
+            context
+                .nodes
+                .iter()
+                .map(|(id, node)| (context.get_node_sort_key(node), *id))
+                .collect(),
+        )
+    }
+}
+
+// Versions of the detectors: Rust detectors change with the bot's binary, rules with their file and with
+// the binary (which holds the rule engine)
+pub struct DetectorVersions {
+    binary: String,
+    rules: HashMap<String, u64>,
+}
+
+impl DetectorVersions {
+    pub fn load(rules_directory: &str) -> Self {
+        let binary = std::env::current_exe()
+            .and_then(std::fs::metadata)
+            .map(|metadata| format!("{}:{:?}", metadata.len(), metadata.modified().ok()))
+            .unwrap_or_default();
+        let rules = load_rules(Path::new(rules_directory))
+            .into_iter()
+            .flatten()
+            .map(|rule| (rule.name, rule.fingerprint))
+            .collect();
+        Self {
+            binary: format!("{}:{}", env!("CARGO_PKG_VERSION"), binary),
+            rules,
+        }
+    }
+
+    pub fn of(&self, detector: &dyn BotDetector) -> String {
+        match self.rules.get(&detector.name()) {
+            Some(rule) => format!("{}:rule:{:x}", self.binary, rule),
+            None => self.binary.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct CacheEntry {
+    version: String,
+    units: BTreeMap<String, UnitEntry>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct UnitEntry {
+    // Hex, as JSON numbers cannot hold every u64
+    key: String,
+    // Line and src of each instance
+    instances: Vec<(usize, String)>,
+}
+
+impl CacheEntry {
+    fn new(version: String, keys: &SourceKeys, instances: &Instances) -> Self {
+        let mut units = keys
+            .keys
+            .iter()
+            .map(|(file, key)| {
+                let unit = UnitEntry {
+                    key: format!("{:x}", key),
+                    instances: vec![],
+                };
+                (file.clone(), unit)
+            })
+            .collect::<BTreeMap<_, _>>();
+        for (file, line, src) in instances.keys() {
+            // Units without a key never match, so their instances are recomputed next time
+            units
+                .entry(file.clone())
+                .or_default()
+                .instances
+                .push((*line, src.clone()));
+        }
+        Self { version, units }
+    }
+
+    // Units of `keys` whose key differs from the saved one, or that were not saved.
+    // Saved units missing from `keys` are gone, along with their instances.
+    fn changed_units(&self, keys: &SourceKeys) -> BTreeSet<String> {
+        keys.keys
+            .iter()
+            .filter(|(file, key)| {
+                self.units
+                    .get(*file)
+                    .is_none_or(|unit| unit.key != format!("{:x}", key))
+            })
+            .map(|(file, _)| file.clone())
+            .collect()
+    }
+
+    // Saved instances of `units`, `None` when one of them no longer maps onto a node
+    fn resolve(&self, context: &WorkspaceContext, units: &BTreeSet<String>) -> Option<Instances> {
+        let nodes = NodeKeys::build(context);
+        let mut instances = BTreeMap::new();
+        for (file, unit) in self.units.iter().filter(|(file, _)| units.contains(*file)) {
+            for (line, src) in &unit.instances {
+                let key = (file.clone(), *line, src.clone());
+                let id = nodes.0.get(&key)?;
+                instances.insert(key, *id);
+            }
+        }
+        Some(instances)
+    }
+}
+
+// Folder of the cache entries of the project at `root`
+pub fn project_directory(cache_directory: &Path, root: &str) -> PathBuf {
+    let root = std::fs::canonicalize(root).unwrap_or_else(|_| PathBuf::from(root));
+    let root = root.to_string_lossy();
+    cache_directory.join(format!("{:x}", fingerprint(root.as_bytes())))
+}
+
+// How the last `detect` of a `CachedDetector` got its instances
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum CacheUse {
+    // The detector ran on the whole workspace
+    #[default]
+    Miss,
+    // Every instance was saved
+    Hit,
+    // The detector only ran on these units, the instances of the others were saved
+    Partial(BTreeSet<String>),
+}
+
+pub struct CacheLayer {
+    directory: PathBuf,
+    version: String,
+    // Keys are source file name and line number
+    found_instances: Instances,
+    cache_use: CacheUse,
+}
+
+pub type CachedDetector = Layered<CacheLayer>;
+
+impl CachedDetector {
+    pub fn new(detector: Box<dyn BotDetector>, directory: PathBuf, version: String) -> Self {
+        let layer = CacheLayer {
+            directory,
+            version,
+            found_instances: BTreeMap::new(),
+            cache_use: CacheUse::Miss,
+        };
+        Self { detector, layer }
+    }
+
+    pub fn cache_use(&self) -> &CacheUse {
+        &self.layer.cache_use
+    }
+
+    // Whether the last `detect` reused the saved instances instead of running the detector
+    pub fn from_cache(&self) -> bool {
+        self.layer.cache_use == CacheUse::Hit
+    }
+}
+
+impl CacheLayer {
+    // Runs the detector on the changed units of `saved` when it is unit scoped, on the whole workspace
+    // otherwise
+    fn run(
+        &mut self,
+        detector: &mut dyn BotDetector,
+        context: &WorkspaceContext,
+        saved: Option<(Instances, BTreeSet<String>)>,
+    ) -> Result<CacheUse, Box<dyn Error>> {
+        if let (Some((mut instances, changed)), Some(detector)) = (saved, detector.unit_scoped()) {
+            detector.detect_units(context, &changed)?;
+            instances.extend(detector.instances());
+            self.found_instances = instances;
+            return Ok(CacheUse::Partial(changed));
+        }
+        detector.detect(context)?;
+        self.found_instances = detector.instances();
+        Ok(CacheUse::Miss)
+    }
+
+    fn entry_path(&self, detector: &dyn BotDetector) -> PathBuf {
+        self.directory.join(format!("{}.json", detector.name()))
+    }
+}
+
+impl DetectorLayer for CacheLayer {
+    fn detect(
+        &mut self,
+        detector: &mut dyn BotDetector,
+        context: &WorkspaceContext,
+    ) -> Result<bool, Box<dyn Error>> {
+        let keys = SourceKeys::build(context);
+        let saved = std::fs::read_to_string(self.entry_path(detector))
+            .ok()
+            .and_then(|content| serde_json::from_str::<CacheEntry>(&content).ok())
+            .filter(|entry| entry.version == self.version)
+            .and_then(|entry| {
+                let changed = entry.changed_units(&keys);
+                let unchanged = keys
+                    .keys
+                    .keys()
+                    .filter(|file| !changed.contains(*file))
+                    .cloned()
+                    .collect();
+                Some((entry.resolve(context, &unchanged)?, changed))
+            });
+
+        match saved {
+            Some((instances, changed)) if changed.is_empty() => {
+                self.found_instances = instances;
+                self.cache_use = CacheUse::Hit;
+                return Ok(!self.found_instances.is_empty());
+            }
+            saved => self.cache_use = self.run(detector, context, saved)?,
+        }
+
+        let entry = CacheEntry::new(self.version.clone(), &keys, &self.found_instances);
+        let path = self.entry_path(detector);
+        let written = std::fs::create_dir_all(&self.directory)
+            .and_then(|_| std::fs::write(path, serde_json::to_string(&entry).unwrap()));
+        if let Err(err) = written {
+            eprintln!("[!] Could not cache {}: {}", detector.name(), err);
+        }
+        Ok(!self.found_instances.is_empty())
+    }
+
+    fn instances(&self, _detector: &dyn BotDetector) -> Instances {
+        self.found_instances.clone()
+    }
+}
+
+// Wraps `detectors` so that they reuse the instances saved for the project at `root`
+pub fn with_cache(detectors: Vec<Box<dyn BotDetector>>, root: &str) -> Vec<Box<dyn BotDetector>> {
+    let directory = project_directory(Path::new(CACHE_DIR), root);
+    let versions = DetectorVersions::load(RULES_DIR);
+    detectors
+        .into_iter()
+        .map(|detector| {
+            let version = versions.of(detector.as_ref());
+            let cached = CachedDetector::new(detector, directory.clone(), version);
+            Box::new(cached) as Box<dyn BotDetector>
+        })
+        .collect()
+}
+
+// Deletes the saved instances of `detector`, or the whole cache
+pub fn clear(cache_directory: &Path, detector: Option<&str>) {
+    let Some(detector) = detector else {
+        _ = std::fs::remove_dir_all(cache_directory); // OK to fail, there may be no cache yet
+        println!("[*] Cleared {}", cache_directory.display());
+        return;
+    };
+    let Ok(projects) = std::fs::read_dir(cache_directory) else {
+        return;
+    };
+    for project in projects.flatten() {
+        _ = std::fs::remove_file(project.path().join(format!("{}.json", detector)));
+    }
+    println!("[*] Cleared {} from {}", detector, cache_directory.display());
+}
+
+#[cfg(test)]
+mod cache_tests {
+
+    use std::collections::BTreeSet;
+    use std::path::PathBuf;
+
+    use crate::bot_utils::load_contracts;
+    use crate::floating_pragma::detector::FloatingPragmaDetector;
+    use crate::public_state_variables::detector::PublicStateVariablesDetector;
+    use crate::state_variable_is_never_used::detector::StateVariableIsNeverUsedDetector;
+
+    use super::{clear, fingerprint, CacheUse, CachedDetector, SourceKeys};
+
+    use aderyn_driver::detector::IssueDetector;
+
+    const BASE: &str = "./foundry_workspace/out/Base.sol/Base.json";
+    const CHILD: &str = "./foundry_workspace/out/Child.sol/Child.json";
+    const COUNTER: &str = "./foundry_workspace/out/Counter.sol/Counter.json";
+
+    fn temporary_cache(name: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("nyth-cache-{}-{}", name, std::process::id()));
+        _ = std::fs::remove_dir_all(&directory);
+        directory
+    }
+
+    #[test]
+    fn test_fingerprint_is_fnv1a() {
+        assert_eq!(fingerprint(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fingerprint(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_source_keys_follow_imports() {
+        let context = load_contracts(&[BASE.into(), CHILD.into()]);
+        let keys = SourceKeys::build(&context);
+
+        let child = keys.imports("src/inheritance/Child.sol").unwrap();
+        assert!(child.contains("src/inheritance/Base.sol"));
+        assert!(keys.imports("src/inheritance/Base.sol").unwrap().is_empty());
+
+        // Same sources, same keys
+        let again = load_contracts(&[BASE.into(), CHILD.into()]);
+        assert_eq!(
+            keys.key("src/inheritance/Child.sol"),
+            SourceKeys::build(&again).key("src/inheritance/Child.sol")
+        );
+        assert_ne!(
+            keys.key("src/inheritance/Child.sol"),
+            keys.key("src/inheritance/Base.sol")
+        );
+    }
+
+    #[test]
+    fn test_cached_detector() {
+        let directory = temporary_cache("detector");
+        let context = load_contracts(&[BASE.into(), CHILD.into()]);
+
+        let mut first = CachedDetector::new(
+            Box::<StateVariableIsNeverUsedDetector>::default(),
+            directory.clone(),
+            String::from("1"),
+        );
+        assert!(first.detect(&context).unwrap());
+        assert!(!first.from_cache());
+
+        let mut second = CachedDetector::new(
+            Box::<StateVariableIsNeverUsedDetector>::default(),
+            directory.clone(),
+            String::from("1"),
+        );
+        assert!(second.detect(&context).unwrap());
+        assert!(second.from_cache());
+        assert_eq!(second.instances(), first.instances());
+
+        // Another version of the detector, or another project, runs again
+        let mut upgraded = CachedDetector::new(
+            Box::<StateVariableIsNeverUsedDetector>::default(),
+            directory.clone(),
+            String::from("2"),
+        );
+        upgraded.detect(&context).unwrap();
+        assert!(!upgraded.from_cache());
+
+        let counter = load_contracts(&[COUNTER.into()]);
+        let mut other = CachedDetector::new(
+            Box::<PublicStateVariablesDetector>::default(),
+            directory.clone(),
+            String::from("2"),
+        );
+        other.detect(&counter).unwrap();
+        assert!(!other.from_cache());
+        let mut moved = CachedDetector::new(
+            Box::<PublicStateVariablesDetector>::default(),
+            directory.clone(),
+            String::from("2"),
+        );
+        moved.detect(&context).unwrap();
+        assert!(!moved.from_cache());
+
+        clear(&directory, None);
+        assert!(!directory.exists());
+    }
+    #[test]
+    fn test_unit_scoped_detectors_rerun_changed_units() {
+        let directory = temporary_cache("units");
+        let before = load_contracts(&[BASE.into(), CHILD.into()]);
+        let after = load_contracts(&[BASE.into(), CHILD.into(), COUNTER.into()]);
+        let cached = |detector| CachedDetector::new(detector, directory.clone(), String::from("1"));
+
+        let mut first = cached(Box::<FloatingPragmaDetector>::default());
+        first.detect(&before).unwrap();
+        assert_eq!(first.cache_use(), &CacheUse::Miss);
+
+        // Only the new unit is checked, the pragmas of the others are reused
+        let mut second = cached(Box::<FloatingPragmaDetector>::default());
+        second.detect(&after).unwrap();
+        let rerun = BTreeSet::from([String::from("src/Counter.sol")]);
+        assert_eq!(second.cache_use(), &CacheUse::Partial(rerun));
+        let mut uncached = FloatingPragmaDetector::default();
+        uncached.detect(&after).unwrap();
+        assert_eq!(second.instances(), uncached.instances());
+
+        // Detectors looking at the whole workspace run on all of it again
+        let mut whole = cached(Box::<StateVariableIsNeverUsedDetector>::default());
+        whole.detect(&before).unwrap();
+        let mut whole = cached(Box::<StateVariableIsNeverUsedDetector>::default());
+        whole.detect(&after).unwrap();
+        assert_eq!(whole.cache_use(), &CacheUse::Miss);
+
+        clear(&directory, None);
+    }
+}
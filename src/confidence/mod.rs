@@ -0,0 +1,326 @@
+// Confidence of findings: how likely an instance is to be a real issue, where `IssueSeverity` says how
+// bad it is when it is one.
+//
+// Detectors declare theirs with `BotDetector::confidence`, rules through their `confidence` key, and may
+// refine it for each instance with `BotDetector::instance_confidence`.
+//
+// `ConfidenceFilter` wraps a subscribed detector to drop the instances under a minimum confidence. Markdown
+// reports state the confidence in the description. For JSON reports, the filters record it instead, and
+// `add_to_json_report` adds it as a `confidence` field of the issues once the driver wrote the report.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use aderyn_driver::context::workspace_context::WorkspaceContext;
+use aderyn_driver::core_ast::NodeID;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::bot_detector::{BotDetector, DetectorLayer, Layered};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum Confidence {
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl Display for Confidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let confidence = match self {
+            Confidence::Low => "Low",
+            Confidence::Medium => "Medium",
+            Confidence::High => "High",
+        };
+        write!(f, "{}", confidence)
+    }
+}
+
+impl FromStr for Confidence {
+    type Err = String;
+
+    fn from_str(confidence: &str) -> Result<Self, Self::Err> {
+        match confidence.to_ascii_lowercase().as_str() {
+            "low" => Ok(Confidence::Low),
+            "medium" => Ok(Confidence::Medium),
+            "high" => Ok(Confidence::High),
+            _ => Err(format!(
+                "unknown confidence `{}`, expected High, Medium or Low",
+                confidence
+            )),
+        }
+    }
+}
+
+// Confidences of the reported instances, for JSON reports
+#[derive(Debug, Default)]
+pub struct Ratings {
+    // By detector name
+    detectors: BTreeMap<String, Confidence>,
+    // Instances whose confidence differs from their detector's, by detector name, file and line
+    exceptions: BTreeMap<(String, String, usize), Confidence>,
+}
+
+// Shared with the `ConfidenceFilter`s, as the driver consumes its subscriptions
+pub type SharedRatings = Arc<Mutex<Ratings>>;
+
+impl Ratings {
+    // Issues are the objects with a `detector_name`, wherever the report nests them
+    fn rate(&self, value: &mut Value) {
+        match value {
+            Value::Array(values) => values.iter_mut().for_each(|value| self.rate(value)),
+            Value::Object(object) => {
+                let name = object.get("detector_name").and_then(Value::as_str);
+                match name.map(str::to_string) {
+                    Some(name) if self.detectors.contains_key(&name) => {
+                        self.rate_issue(&name, object)
+                    }
+                    _ => object.values_mut().for_each(|value| self.rate(value)),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Instances are located by their `contract_path` and `line_no`
+    fn rate_issue(&self, name: &str, issue: &mut Map<String, Value>) {
+        issue.insert(
+            String::from("confidence"),
+            Value::from(self.detectors[name].to_string()),
+        );
+        let instances = issue.get_mut("instances").and_then(Value::as_array_mut);
+        for instance in instances.into_iter().flatten() {
+            let file = instance.get("contract_path").and_then(Value::as_str);
+            let line = instance.get("line_no").and_then(Value::as_u64);
+            let (Some(file), Some(line)) = (file, line) else {
+                continue;
+            };
+            let key = (name.to_string(), file.to_string(), line as usize);
+            if let (Some(confidence), Value::Object(instance)) =
+                (self.exceptions.get(&key), instance)
+            {
+                instance.insert(
+                    String::from("confidence"),
+                    Value::from(confidence.to_string()),
+                );
+            }
+        }
+    }
+}
+
+pub struct ConfidenceLayer {
+    minimum: Confidence,
+    // Keys are source file name and line number
+    found_instances: BTreeMap<(String, usize, String), NodeID>,
+    // Kept instances whose confidence differs from the detector's
+    exceptions: BTreeMap<(String, usize, String), Confidence>,
+    // Where the confidences go for JSON reports, `None` states them in the description
+    ratings: Option<SharedRatings>,
+}
+
+pub type ConfidenceFilter = Layered<ConfidenceLayer>;
+
+impl ConfidenceFilter {
+    pub fn new(detector: Box<dyn BotDetector>, minimum: Confidence) -> Self {
+        let layer = ConfidenceLayer {
+            minimum,
+            found_instances: BTreeMap::new(),
+            exceptions: BTreeMap::new(),
+            ratings: None,
+        };
+        Self { detector, layer }
+    }
+
+    // Records the confidences in `ratings` instead of stating them in the description
+    pub fn rated(mut self, ratings: &SharedRatings) -> Self {
+        self.layer.ratings = Some(ratings.clone());
+        self
+    }
+}
+
+impl DetectorLayer for ConfidenceLayer {
+    fn detect(
+        &mut self,
+        detector: &mut dyn BotDetector,
+        context: &WorkspaceContext,
+    ) -> Result<bool, Box<dyn Error>> {
+        detector.detect(context)?;
+        let confidence = detector.confidence();
+        for (key, id) in detector.instances() {
+            let instance_confidence = detector.instance_confidence(context, id);
+            if instance_confidence < self.minimum {
+                continue;
+            }
+            if instance_confidence != confidence {
+                self.exceptions.insert(key.clone(), instance_confidence);
+            }
+            self.found_instances.insert(key, id);
+        }
+
+        if let Some(ratings) = &self.ratings {
+            let name = detector.name();
+            let mut ratings = ratings.lock().unwrap();
+            ratings.detectors.insert(name.clone(), confidence);
+            for ((file, line, _), instance_confidence) in &self.exceptions {
+                let key = (name.clone(), file.clone(), *line);
+                ratings.exceptions.insert(key, *instance_confidence);
+            }
+        }
+        Ok(!self.found_instances.is_empty())
+    }
+
+    fn description(&self, detector: &dyn BotDetector) -> String {
+        if self.ratings.is_some() {
+            return detector.description();
+        }
+        let mut description = format!(
+            "{}\n\nConfidence: {}",
+            detector.description(),
+            detector.confidence()
+        );
+        for ((file, line, _), confidence) in &self.exceptions {
+            description.push_str(&format!(" ({} at `{}:{}`)", confidence, file, line));
+        }
+        description
+    }
+
+    fn instances(&self, _detector: &dyn BotDetector) -> BTreeMap<(String, usize, String), NodeID> {
+        self.found_instances.clone()
+    }
+}
+
+// Wraps `detectors` so that they only report instances of at least `minimum` confidence, for markdown reports
+pub fn filter_by_confidence(
+    detectors: Vec<Box<dyn BotDetector>>,
+    minimum: Confidence,
+) -> Vec<Box<dyn BotDetector>> {
+    detectors
+        .into_iter()
+        .map(|detector| Box::new(ConfidenceFilter::new(detector, minimum)) as Box<dyn BotDetector>)
+        .collect()
+}
+
+// Same as `filter_by_confidence` for JSON reports, recording the confidences in `ratings`
+pub fn rate_by_confidence(
+    detectors: Vec<Box<dyn BotDetector>>,
+    minimum: Confidence,
+    ratings: &SharedRatings,
+) -> Vec<Box<dyn BotDetector>> {
+    detectors
+        .into_iter()
+        .map(|detector| {
+            let filter = ConfidenceFilter::new(detector, minimum).rated(ratings);
+            Box::new(filter) as Box<dyn BotDetector>
+        })
+        .collect()
+}
+
+// Adds a `confidence` field to the issues of the JSON report at `path`, and to their instances whose
+// confidence differs from the issue's
+pub fn add_to_json_report(path: &str, ratings: &Ratings) -> Result<(), Box<dyn Error>> {
+    let mut report: Value = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+    ratings.rate(&mut report);
+    std::fs::write(path, serde_json::to_string_pretty(&report)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod confidence_tests {
+
+    use crate::arbitrary_transfer_from::detector::ArbitraryTransferFromDetector;
+    use crate::bot_detector::BotDetector;
+    use crate::bot_utils::load_contracts;
+    use crate::public_state_variables::detector::PublicStateVariablesDetector;
+    use crate::rule_engine::{rule_detectors, RULES_DIR};
+    use crate::state_variable_is_never_used::detector::StateVariableIsNeverUsedDetector;
+
+    use super::{Confidence, ConfidenceFilter, SharedRatings};
+
+    use aderyn_driver::detector::IssueDetector;
+    use serde_json::json;
+
+    const COUNTER: &str = "./foundry_workspace/out/Counter.sol/Counter.json";
+
+    #[test]
+    fn test_declared_confidences() {
+        let public = PublicStateVariablesDetector::default();
+        assert_eq!(public.confidence(), Confidence::High);
+        let transfer = ArbitraryTransferFromDetector::default();
+        assert_eq!(transfer.confidence(), Confidence::Low);
+        let rule = rule_detectors(RULES_DIR)
+            .into_iter()
+            .find(|rule| rule.name() == "mutable-uppercase-state-variable")
+            .unwrap();
+        assert_eq!(rule.confidence(), Confidence::High);
+
+        assert_eq!("high".parse::<Confidence>(), Ok(Confidence::High));
+        assert!("certain".parse::<Confidence>().is_err());
+        assert!(Confidence::Low < Confidence::Medium && Confidence::Medium < Confidence::High);
+    }
+
+    #[test]
+    fn test_confidence_filter() {
+        let context = load_contracts(&[COUNTER.into()]);
+
+        let mut detector = ConfidenceFilter::new(
+            Box::<PublicStateVariablesDetector>::default(),
+            Confidence::High,
+        );
+        assert!(detector.detect(&context).unwrap());
+        assert_eq!(detector.instances().len(), 2);
+        assert!(detector.description().ends_with("Confidence: High"));
+
+        // `unused_number` is public, it may be read through its getter from outside the workspace
+        let mut detector = ConfidenceFilter::new(
+            Box::<StateVariableIsNeverUsedDetector>::default(),
+            Confidence::High,
+        );
+        assert!(!detector.detect(&context).unwrap());
+
+        let mut detector = ConfidenceFilter::new(
+            Box::<StateVariableIsNeverUsedDetector>::default(),
+            Confidence::Medium,
+        );
+        assert!(detector.detect(&context).unwrap());
+        assert!(detector
+            .description()
+            .ends_with("Confidence: High (Medium at `src/Counter.sol:8`)"));
+    }
+
+    #[test]
+    fn test_json_report_ratings() {
+        let context = load_contracts(&[COUNTER.into()]);
+        let ratings = SharedRatings::default();
+        let mut detector = ConfidenceFilter::new(
+            Box::<StateVariableIsNeverUsedDetector>::default(),
+            Confidence::Medium,
+        )
+        .rated(&ratings);
+        assert!(detector.detect(&context).unwrap());
+        assert!(!detector.description().contains("Confidence"));
+
+        let mut report = json!({
+            "nc_issues": {
+                "issues": [{
+                    "title": "State Variable is never used",
+                    "detector_name": "state-variable-is-never-set",
+                    "instances": [{"contract_path": "src/Counter.sol", "line_no": 8}],
+                }, {
+                    "title": "Not rated",
+                    "detector_name": "public-state-variables",
+                    "instances": [{"contract_path": "src/Counter.sol", "line_no": 5}],
+                }],
+            },
+        });
+        ratings.lock().unwrap().rate(&mut report);
+
+        let issues = &report["nc_issues"]["issues"];
+        assert_eq!(issues[0]["confidence"], "High");
+        assert_eq!(issues[0]["instances"][0]["confidence"], "Medium");
+        assert!(issues[1].get("confidence").is_none());
+    }
+}
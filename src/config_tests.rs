@@ -1,19 +1,123 @@
 #[allow(unused_imports)]
-use crate::bot_utils::{TestsConfig, TestsTarget};
+use crate::bot_utils::{NodeSelector, TestsConfig, TestsTarget};
 use crate::{
+    access_control_guards::detector::AccessControlGuardsDetector,
+    arbitrary_transfer_from::detector::ArbitraryTransferFromDetector,
+    external_call_sites::detector::ExternalCallSitesDetector,
+    floating_pragma::detector::FloatingPragmaDetector,
+    inefficient_storage_packing::detector::InefficientStoragePackingDetector,
+    inheritance_chain::detector::InheritanceChainDetector,
+    inline_assembly::detector::InlineAssemblyDetector,
+    outdated_solidity_version::detector::OutdatedSolidityVersionDetector,
     public_state_variables::detector::PublicStateVariablesDetector,
+    push0_incompatible_version::detector::Push0IncompatibleVersionDetector,
+    reachable_functions::detector::ReachableFunctionsDetector,
     state_variable_is_never_used::detector::StateVariableIsNeverUsedDetector,
+    state_variable_is_never_written::detector::StateVariableIsNeverWrittenDetector,
+    state_variable_is_write_only::detector::StateVariableIsWriteOnlyDetector,
+    state_variable_reads_and_writes::detector::StateVariableReadsAndWritesDetector,
     state_variables_in_contract_definition::detector::StateVariablesInContractDefinitionDetector,
+    state_variables_written_by_function::detector::StateVariablesWrittenByFunctionDetector,
+    upgradeable_storage_collision::detector::UpgradeableStorageCollisionDetector,
 };
+use aderyn_driver::core_ast::NodeType;
 
 pub fn tests_configuration() -> TestsConfig {
     vec![
         // This test configuration passes the Counter.sol contract to the
         // StateVariablesInContractDefinitionDetector and the StateVariableIsNeverUsedDetector tests.
+        // Reusable detectors are handed the `Counter` contract as `within` and are expected to
+        // return exactly the nodes named in `expecting`.
         TestsTarget::new("./foundry_workspace/out/Counter.sol/Counter.json")
+            .within(NodeSelector::contract("Counter"))
+            .expecting(&["number", "unused_number"])
             .with_reusable_detector(Box::<StateVariablesInContractDefinitionDetector>::default())
             .with_issue_detector(Box::<StateVariableIsNeverUsedDetector>::default())
             .with_issue_detector(Box::<PublicStateVariablesDetector>::default()),
+        // Base.sol and Child.sol are loaded into the same context so that usages of `Base`'s
+        // state variables inside `Child` are visible to the detectors.
+        TestsTarget::new_with_artifacts(&[
+            "./foundry_workspace/out/Base.sol/Base.json",
+            "./foundry_workspace/out/Child.sol/Child.json",
+        ])
+        .within(NodeSelector::contract("Base"))
+        .expecting(&["sharedValue", "unusedInBase"])
+        .with_reusable_detector(Box::<StateVariablesInContractDefinitionDetector>::default())
+        .with_issue_detector(Box::<StateVariableIsNeverUsedDetector>::default()),
+        // C3 linearization of the diamond `D is B, C` where both `B` and `C` inherit `A`. `D` also
+        // declares an unused internal state variable as a leaf contract.
+        TestsTarget::new("./foundry_workspace/out/Diamond.sol/D.json")
+            .within(NodeSelector::contract("D"))
+            .expecting(&["D", "C", "B", "A"])
+            .with_reusable_detector(Box::<InheritanceChainDetector>::default())
+            .with_issue_detector(Box::<StateVariableIsNeverUsedDetector>::default()),
+        // `entry` reaches its modifier, the internal function it calls, the `super` implementation
+        // and a library function bound with `using for`
+        TestsTarget::new("./foundry_workspace/out/CallGraph.sol/Caller.json")
+            .within(NodeSelector::function("entry"))
+            .expecting(&["entry", "checked", "_check", "_store", "_store", "double"])
+            .with_reusable_detector(Box::<ReachableFunctionsDetector>::default()),
+        // Only `pull` lets the caller choose `from` (through a struct and the internal `_pull`)
+        TestsTarget::new("./foundry_workspace/out/Taint.sol/Taint.json")
+            .with_issue_detector(Box::<ArbitraryTransferFromDetector>::default()),
+        // `deposit` writes `accounts` through a storage pointer, `total` through an internal call and
+        // `calls` through its modifier
+        TestsTarget::new("./foundry_workspace/out/StorageWrites.sol/StorageWrites.json")
+            .within(NodeSelector::function("deposit"))
+            .expecting(&["accounts", "calls", "total"])
+            .with_reusable_detector(Box::<StateVariablesWrittenByFunctionDetector>::default()),
+        // High level, low level, transfer / send and inline assembly calls
+        TestsTarget::new("./foundry_workspace/out/ExternalCalls.sol/ExternalCalls.json")
+            .within(NodeSelector::contract("ExternalCalls"))
+            .with_reusable_detector(Box::<ExternalCallSitesDetector>::default()),
+        // Every function of `Guarded` is protected except `hasRole` and those meant to be public, `onlyInitializing`
+        // and `onlyProxy` do not check the caller
+        TestsTarget::new("./foundry_workspace/out/AccessControl.sol/Guarded.json")
+            .within(NodeSelector::kind(NodeType::FunctionDefinition))
+            .expecting(&[
+                "hasRole",
+                "unguarded",
+                "alsoUnguarded",
+                "byDisjunction",
+                "byInequality",
+                "readsRole",
+                "checksOtherRole",
+                "initialize",
+                "upgrade",
+            ])
+            .with_reusable_detector(Box::<AccessControlGuardsDetector>::default()),
+        // The four assembly blocks of Assembly.sol
+        TestsTarget::new("./foundry_workspace/out/Assembly.sol/Assembly.json")
+            .within(NodeSelector::contract("Assembly"))
+            .with_reusable_detector(Box::<InlineAssemblyDetector>::default()),
+        // Every reference to `number` in Counter.sol (`number = newNumber` and `number++`)
+        TestsTarget::new("./foundry_workspace/out/Counter.sol/Counter.json")
+            .within(NodeSelector::contract("Counter"))
+            .expecting(&["number", "number"])
+            .with_reusable_detector(Box::<StateVariableReadsAndWritesDetector>::default()),
+        // ReadsAndWrites.sol has exactly one write-only and one never written state variable, `escrow` is
+        // only accessed through a storage parameter
+        TestsTarget::new("./foundry_workspace/out/ReadsAndWrites.sol/ReadsAndWrites.json")
+            .with_issue_detector(Box::<StateVariableIsNeverWrittenDetector>::default())
+            .with_issue_detector(Box::<StateVariableIsWriteOnlyDetector>::default()),
+        // Packing.sol has one contract packed optimally and one wasting a slot
+        TestsTarget::new("./foundry_workspace/out/Packing.sol/Packed.json")
+            .with_issue_detector(Box::<InefficientStoragePackingDetector>::default()),
+        // Upgradeable.sol has an incompatible VaultV2, a compatible TokenV2, an unpaired PoolV2
+        // and a misplaced gap, UpgradeableLegacy.sol another TokenV1
+        TestsTarget::new_with_artifacts(&[
+            "./foundry_workspace/out/Upgradeable.sol/VaultV1.json",
+            "./foundry_workspace/out/UpgradeableLegacy.sol/TokenV1.json",
+        ])
+        .with_issue_detector(Box::<UpgradeableStorageCollisionDetector>::default()),
+        // Legacy.sol floats over buggy 0.7 releases, Pinned.sol is pinned to 0.8.24 (PUSH0)
+        TestsTarget::new_with_artifacts(&[
+            "./foundry_workspace/out/Legacy.sol/Legacy.json",
+            "./foundry_workspace/out/Pinned.sol/Pinned.json",
+        ])
+        .with_issue_detector(Box::<FloatingPragmaDetector>::default())
+        .with_issue_detector(Box::<OutdatedSolidityVersionDetector>::default())
+        .with_issue_detector(Box::<Push0IncompatibleVersionDetector>::default()),
     ]
     .into()
 }
@@ -0,0 +1,285 @@
+// _   ___   _______ _   _  ________   __  _______   __  ___  ___  _________ _      _____
+// | \ | \ \ / /_   _| | | | | ___ \ \ / / |  ___\ \ / / / _ \ |  \/  || ___ \ |    |  ___|
+// |  \| |\ V /  | | | |_| | | |_/ /\ V /  | |__  \ V / / /_\ \| .  . || |_/ / |    | |__
+// | . ` | \ /   | | |  _  | | ___ \ \ /   |  __| /   \ |  _  || |\/| ||  __/| |    |  __|
+// | |\  | | |   | | | | | | | |_/ / | |   | |___/ /^\ \| | | || |  | || |   | |____| |___
+// \_| \_/ \_/   \_/ \_| |_/ \____/  \_/   \____/\/   \/\_| |_/\_|  |_/\_|   \_____/\____/
+// ______                     _     _       ______     _            _
+// | ___ \                   | |   | |      |  _  \   | |          | |
+// | |_/ /___ _   _ ___  __ _| |__ | | ___  | | | |___| |_ ___  ___| |_ ___  _ __
+// |    // _ \ | | / __|/ _` | '_ \| |/ _ \ | | | / _ \ __/ _ \/ __| __/ _ \| '__|
+// | |\ \  __/ |_| \__ \ (_| | |_) | |  __/ | |/ /  __/ ||  __/ (__| || (_) | |
+// \_| \_\___|\__,_|___/\__,_|_.__/|_|\___| |___/ \___|\__\___|\___|\__\___/|_|
+//
+// This ReusableDetector is responsible for finding every external call site within the given scope.
+// The `detect` function does this following:
+//
+//   1. Accept ContractDefinition, FunctionDefinition or ModifierDefinition nodes via the `within` parameter.
+//   2. For each FunctionCall inside them, classify it as a high level call (resolved through the shared CallGraph),
+//      `.call` / `.delegatecall` / `.staticcall` or `.transfer` / `.send` on an address, or a call to a public /
+//      external library function (a DELEGATECALL to the library), and note whether value is sent.
+//   3. For each InlineAssembly block inside them, classify its `call` / `callcode` / `delegatecall` / `staticcall` builtins.
+//   4. Add each call site (the FunctionCall, or the InlineAssembly block once per call in it) to the `found_instances` vector.
+//   5. Return the `found_instances` vector.
+//
+// Use `call_sites()` after `detect` to get the kind of each call site and whether it sends value.
+//
+
+use aderyn_driver::context::browser::ExtractFunctionCalls;
+use aderyn_driver::context::workspace_context::ASTNode;
+use aderyn_driver::context::workspace_context::WorkspaceContext;
+use aderyn_driver::core_ast::{
+    ContractKind, Expression, FunctionCall, NodeID, NodeType, Visibility,
+};
+use aderyn_driver::detector::ReusableDetector;
+
+use crate::analysis::call_graph::{CallGraph, CallKind};
+use crate::analysis::expressions::type_string;
+use crate::analysis::yul::{is_zero, yul_calls};
+use crate::analysis::Analyses;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExternalCallKind {
+    // `token.transfer(...)`, `IERC20(token).transfer(...)`, `this.f()`
+    HighLevel,
+    Call,
+    DelegateCall,
+    StaticCall,
+    Transfer,
+    Send,
+    // `L.f()` or `x.f()` with `using L for T` where `f` is a public / external library function, which
+    // runs the deployed library through a DELEGATECALL
+    LibraryDelegateCall,
+    AssemblyCall,
+    AssemblyCallCode,
+    AssemblyDelegateCall,
+    AssemblyStaticCall,
+}
+
+#[derive(Clone, Debug)]
+pub struct ExternalCallSite {
+    // FunctionCall or InlineAssembly node
+    pub node: NodeID,
+    pub kind: ExternalCallKind,
+    pub sends_value: bool,
+}
+
+#[derive(Default)]
+pub struct ExternalCallSitesDetector {
+    found_instances: Vec<ASTNode>,
+    call_sites: Vec<ExternalCallSite>,
+}
+
+impl ExternalCallSitesDetector {
+    pub fn call_sites(&self) -> &[ExternalCallSite] {
+        &self.call_sites
+    }
+
+    // Same as `detect`, reusing the call graph of `analyses`
+    pub fn detect_with(
+        &mut self,
+        analyses: &Analyses,
+        _: &[ASTNode],
+        within: &[ASTNode],
+    ) -> Result<&[ASTNode], Box<dyn std::error::Error>> {
+        let context = analyses.context();
+        for node in within {
+            let (scope, function_calls) = match node {
+                ASTNode::ContractDefinition(n) => (n.id, ExtractFunctionCalls::from(n).extracted),
+                ASTNode::FunctionDefinition(n) => (n.id, ExtractFunctionCalls::from(n).extracted),
+                ASTNode::ModifierDefinition(n) => (n.id, ExtractFunctionCalls::from(n).extracted),
+                _ => continue,
+            };
+
+            for function_call in function_calls {
+                if let Some((kind, sends_value)) =
+                    classify_call(context, analyses.call_graph(), &function_call)
+                {
+                    self.record(context, function_call.id, kind, sends_value);
+                }
+            }
+
+            let assemblies = context.inline_assemblies().into_iter().filter(|assembly| {
+                context
+                    .get_parent_chain(assembly.id)
+                    .iter()
+                    .any(|ancestor| ancestor.id() == Some(scope))
+            });
+            for assembly in assemblies {
+                for call in yul_calls(assembly) {
+                    // call(g, a, v, ...), callcode(g, a, v, ...), delegatecall(g, a, ...), staticcall(g, a, ...)
+                    let (kind, value) = match call.function_name.name.as_str() {
+                        "call" => (ExternalCallKind::AssemblyCall, call.arguments.get(2)),
+                        "callcode" => (ExternalCallKind::AssemblyCallCode, call.arguments.get(2)),
+                        "delegatecall" => (ExternalCallKind::AssemblyDelegateCall, None),
+                        "staticcall" => (ExternalCallKind::AssemblyStaticCall, None),
+                        _ => continue,
+                    };
+                    let sends_value = value.is_some_and(|v| !is_zero(v));
+                    self.record(context, assembly.id, kind, sends_value);
+                }
+            }
+        }
+
+        Ok(&self.found_instances)
+    }
+}
+
+impl ReusableDetector for ExternalCallSitesDetector {
+    fn detect(
+        &mut self,
+        context: &WorkspaceContext,
+        using: &[ASTNode],
+        within: &[ASTNode],
+    ) -> Result<&[ASTNode], Box<dyn std::error::Error>> {
+        self.detect_with(&Analyses::new(context), using, within)
+    }
+
+    fn name(&self) -> String {
+        "external-call-sites".to_string()
+    }
+}
+
+impl ExternalCallSitesDetector {
+    fn record(
+        &mut self,
+        context: &WorkspaceContext,
+        node: NodeID,
+        kind: ExternalCallKind,
+        sends_value: bool,
+    ) {
+        if let Some(ast_node) = context.nodes.get(&node) {
+            self.found_instances.push(ast_node.clone());
+            self.call_sites.push(ExternalCallSite {
+                node,
+                kind,
+                sends_value,
+            });
+        }
+    }
+}
+
+fn classify_call(
+    context: &WorkspaceContext,
+    call_graph: &CallGraph,
+    function_call: &FunctionCall,
+) -> Option<(ExternalCallKind, bool)> {
+    // `x.f{value: v}()`, where `{value: 0}` sends nothing
+    let (callee, value_option) = match function_call.expression.as_ref() {
+        Expression::FunctionCallOptions(options) => (
+            options.expression.as_ref(),
+            options
+                .names
+                .iter()
+                .zip(options.options.iter())
+                .any(|(name, value)| name == "value" && !is_literal_zero(value)),
+        ),
+        callee => (callee, false),
+    };
+    let Expression::MemberAccess(member_access) = callee else {
+        return None;
+    };
+
+    let on_address =
+        type_string(&member_access.expression).is_some_and(|t| t.starts_with("address"));
+    if on_address {
+        let kind = match member_access.member_name.as_str() {
+            "call" => (ExternalCallKind::Call, value_option),
+            "delegatecall" => (ExternalCallKind::DelegateCall, false),
+            "staticcall" => (ExternalCallKind::StaticCall, false),
+            "transfer" => (ExternalCallKind::Transfer, true),
+            "send" => (ExternalCallKind::Send, true),
+            _ => return None,
+        };
+        return Some(kind);
+    }
+
+    if is_linked_library_function(context, member_access.referenced_declaration) {
+        return Some((ExternalCallKind::LibraryDelegateCall, false));
+    }
+
+    let has_external_edge = call_graph
+        .edges()
+        .iter()
+        .any(|edge| edge.call_site == function_call.id && edge.kind == CallKind::External);
+    // Public getters and functions of interfaces missing from the context have no edge in the
+    // call graph, so fall back to the types: an external function reached through a contract instance
+    let on_contract =
+        type_string(&member_access.expression).is_some_and(|t| t.starts_with("contract "));
+    let external_function =
+        type_string(callee).is_some_and(|t| t.starts_with("function ") && t.contains(" external"));
+    let is_external = has_external_edge || (on_contract && external_function);
+    is_external.then_some((ExternalCallKind::HighLevel, value_option))
+}
+
+// Internal library functions are compiled into the caller, the others are called on the deployed library
+fn is_linked_library_function(context: &WorkspaceContext, declaration: Option<NodeID>) -> bool {
+    let Some(ASTNode::FunctionDefinition(function)) =
+        declaration.and_then(|id| context.nodes.get(&id))
+    else {
+        return false;
+    };
+    let in_library = matches!(
+        context.get_closest_parent(function.id, NodeType::ContractDefinition),
+        Some(ASTNode::ContractDefinition(contract)) if contract.kind == ContractKind::Library
+    );
+    in_library
+        && matches!(
+            function.visibility,
+            Visibility::Public | Visibility::External
+        )
+}
+
+fn is_literal_zero(expression: &Expression) -> bool {
+    matches!(expression, Expression::Literal(literal) if literal.value.as_deref() == Some("0"))
+}
+
+#[cfg(test)]
+mod external_call_sites_tests {
+
+    use crate::bot_utils::{load_contracts, NodeSelector};
+    use crate::config_tests::tests_configuration;
+
+    use super::{ExternalCallKind, ExternalCallSitesDetector};
+
+    use aderyn_driver::detector::ReusableDetector;
+
+    #[test]
+    fn test_external_call_sites() {
+        let detector = ExternalCallSitesDetector::default();
+        let cases = tests_configuration().get_reusable_cases_for(detector.name());
+
+        for case in cases {
+            let mut detector = ExternalCallSitesDetector::default();
+            case.run(&mut detector);
+        }
+    }
+
+    #[test]
+    fn test_external_call_sites_classification() {
+        let context = load_contracts(&[
+            "./foundry_workspace/out/ExternalCalls.sol/ExternalCalls.json".into(),
+        ]);
+        let within = NodeSelector::contract("ExternalCalls").select(&context);
+
+        let mut detector = ExternalCallSitesDetector::default();
+        detector.detect(&context, &[], &within).unwrap();
+        let call_sites = detector.call_sites();
+
+        let count = |kind: ExternalCallKind| call_sites.iter().filter(|c| c.kind == kind).count();
+        // `balance`, both `deposit` calls, the `admin` getter and `owner`
+        assert_eq!(count(ExternalCallKind::HighLevel), 5);
+        assert_eq!(count(ExternalCallKind::Call), 2);
+        assert_eq!(count(ExternalCallKind::DelegateCall), 1);
+        assert_eq!(count(ExternalCallKind::StaticCall), 1);
+        assert_eq!(count(ExternalCallKind::Transfer), 1);
+        assert_eq!(count(ExternalCallKind::Send), 1);
+        // `Math.double(x)` and `x.double()`, not the internal `x.half()`
+        assert_eq!(count(ExternalCallKind::LibraryDelegateCall), 2);
+        assert_eq!(count(ExternalCallKind::AssemblyCall), 2);
+        assert_eq!(count(ExternalCallKind::AssemblyDelegateCall), 1);
+
+        // `deposit{value: ..}`, `call{value: 1}`, `transfer`, `send` and the assembly call with value 1
+        assert_eq!(call_sites.iter().filter(|c| c.sends_value).count(), 5);
+    }
+}
@@ -0,0 +1,535 @@
+// Mechanical fixes attached to findings, as byte range replacements in the Solidity sources.
+//
+// Detectors opt in by implementing `FixableDetector`, which computes the fix of an instance from the
+// context alone. The runner wraps its subscriptions in `FixCollector`s, so that fixes are suggested
+// for the instances actually reported: those kept by `--min-confidence`, restored from the cache or not.
+// It renders them as diff blocks at the end of the report, and `--apply-fixes` writes them to the
+// sources (or prints them as a patch with `--dry-run`).
+
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use aderyn_driver::context::workspace_context::{ASTNode, WorkspaceContext};
+use aderyn_driver::core_ast::{NodeID, NodeType};
+use aderyn_driver::detector::IssueDetector;
+
+use crate::bot_detector::{BotDetector, DetectorLayer, Layered};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fix {
+    // `absolute_path` of the SourceUnit, relative to the project root for Foundry projects
+    pub file: String,
+    // Byte range replaced, `end` excluded
+    pub start: usize,
+    pub end: usize,
+    pub replacement: String,
+    // What the fix does, ex: "Make `number` internal"
+    pub title: String,
+}
+
+pub trait FixableDetector: IssueDetector {
+    // Fix of an instance reported by the detector, `None` when it cannot be fixed mechanically and safely
+    fn fix(&self, context: &WorkspaceContext, instance: NodeID) -> Option<Fix>;
+}
+
+// Fixes of the reported instances, with the sources they apply to
+#[derive(Debug, Default)]
+pub struct FoundFixes {
+    // Title of the detector, and the fixes of its instances
+    pub by_detector: Vec<(String, Vec<Fix>)>,
+    pub sources: HashMap<String, String>,
+}
+
+impl FoundFixes {
+    pub fn all(&self) -> Vec<Fix> {
+        self.by_detector
+            .iter()
+            .flat_map(|(_, fixes)| fixes.iter().cloned())
+            .collect()
+    }
+}
+
+// Shared with the `FixCollector`s, as the driver consumes its subscriptions
+pub type SharedFixes = Arc<Mutex<FoundFixes>>;
+
+// Records the fixes of the instances the wrapped detector reports
+pub struct FixLayer {
+    fixes: SharedFixes,
+}
+
+pub type FixCollector = Layered<FixLayer>;
+
+impl DetectorLayer for FixLayer {
+    fn detect(
+        &mut self,
+        detector: &mut dyn BotDetector,
+        context: &WorkspaceContext,
+    ) -> Result<bool, Box<dyn Error>> {
+        let found = detector.detect(context)?;
+        let Some(fixable) = detector.fixable() else {
+            return Ok(found);
+        };
+        let fixes = detector
+            .instances()
+            .into_values()
+            .filter_map(|instance| fixable.fix(context, instance))
+            .collect::<Vec<_>>();
+        if !fixes.is_empty() {
+            let mut found_fixes = self.fixes.lock().unwrap();
+            if found_fixes.sources.is_empty() {
+                found_fixes.sources = sources(context);
+            }
+            found_fixes.by_detector.push((detector.title(), fixes));
+        }
+        Ok(found)
+    }
+}
+
+// Wraps `detectors` so that the fixes of the instances they report end up in `fixes`
+pub fn collect_fixes(
+    detectors: Vec<Box<dyn BotDetector>>,
+    fixes: &SharedFixes,
+) -> Vec<Box<dyn IssueDetector>> {
+    detectors
+        .into_iter()
+        .map(|detector| {
+            let collector = FixCollector {
+                detector,
+                layer: FixLayer {
+                    fixes: fixes.clone(),
+                },
+            };
+            Box::new(collector) as Box<dyn IssueDetector>
+        })
+        .collect()
+}
+
+impl Fix {
+    // Replaces the first occurrence of the whole word `word` in the source of the node whose `src` is given
+    pub fn replace_word(
+        context: &WorkspaceContext,
+        node: NodeID,
+        src: &str,
+        word: &str,
+        replacement: &str,
+        title: String,
+    ) -> Option<Self> {
+        let (file, source) = source_of(context, node)?;
+        let (start, length) = parse_src(src)?;
+        let text = source.get(start..start + length)?;
+
+        let is_boundary = |c: Option<char>| !c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+        let offset = text.match_indices(word).map(|(i, _)| i).find(|&i| {
+            is_boundary(text[..i].chars().next_back())
+                && is_boundary(text[i + word.len()..].chars().next())
+        })?;
+
+        Some(Self {
+            file,
+            start: start + offset,
+            end: start + offset + word.len(),
+            replacement: replacement.to_string(),
+            title,
+        })
+    }
+
+    // Removes the node whose `src` is given, along with its line when nothing else is on it
+    // (besides the `;` ending the declaration and a trailing comment)
+    pub fn remove(
+        context: &WorkspaceContext,
+        node: NodeID,
+        src: &str,
+        title: String,
+    ) -> Option<Self> {
+        let (file, source) = source_of(context, node)?;
+        let (mut start, length) = parse_src(src)?;
+        let mut end = start + length;
+
+        let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[end..]
+            .find('\n')
+            .map_or(source.len(), |i| end + i + 1);
+        let before = &source[line_start..start];
+        let after = source[end..line_end].trim();
+        let after = after.strip_prefix(';').unwrap_or(after).trim_start();
+        if before.trim().is_empty() && (after.is_empty() || after.starts_with("//")) {
+            start = line_start;
+            end = line_end;
+        } else if source[end..].starts_with(';') {
+            end += 1;
+        }
+
+        Some(Self {
+            file,
+            start,
+            end,
+            replacement: String::new(),
+            title,
+        })
+    }
+
+    // 1 based line of the first replaced byte
+    pub fn line(&self, source: &str) -> usize {
+        source[..self.start.min(source.len())].matches('\n').count() + 1
+    }
+
+    // Lines touched by the fix, before and after
+    fn lines(&self, source: &str) -> (usize, String, String) {
+        let line_start = source[..self.start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = if self.end > self.start && source[..self.end].ends_with('\n') {
+            self.end
+        } else {
+            source[self.end..]
+                .find('\n')
+                .map_or(source.len(), |i| self.end + i + 1)
+        };
+        let before = source[line_start..line_end].to_string();
+        let after = format!(
+            "{}{}{}",
+            &source[line_start..self.start],
+            self.replacement,
+            &source[self.end..line_end]
+        );
+        (self.line(source), before, after)
+    }
+
+    // Markdown diff block of the fix
+    pub fn to_diff(&self, source: &str) -> String {
+        let (_, before, after) = self.lines(source);
+        let mut diff = String::from("```diff\n");
+        before.lines().for_each(|l| _ = writeln!(diff, "-{}", l));
+        after.lines().for_each(|l| _ = writeln!(diff, "+{}", l));
+        diff.push_str("```\n");
+        diff
+    }
+}
+
+// Sources of the context, by `absolute_path`
+pub fn sources(context: &WorkspaceContext) -> HashMap<String, String> {
+    context
+        .source_units()
+        .into_iter()
+        .filter_map(|source_unit| {
+            let path = source_unit.absolute_path.clone()?;
+            let source = source_unit.source.clone()?;
+            Some((path, source))
+        })
+        .collect()
+}
+
+// Applies the fixes to `source`. Fixes overlapping an earlier one are skipped.
+pub fn apply_to_source(source: &str, fixes: &[&Fix]) -> String {
+    let mut fixes = fixes.to_vec();
+    fixes.sort_by_key(|fix| (fix.start, fix.end));
+
+    let mut fixed = String::with_capacity(source.len());
+    let mut copied_up_to = 0;
+    for fix in fixes {
+        if fix.start < copied_up_to || fix.end > source.len() {
+            continue;
+        }
+        fixed.push_str(&source[copied_up_to..fix.start]);
+        fixed.push_str(&fix.replacement);
+        copied_up_to = fix.end;
+    }
+    fixed.push_str(&source[copied_up_to..]);
+    fixed
+}
+
+// Unified diff of the fixes, one hunk per fix (without context lines)
+pub fn to_patch(fixes: &[Fix], sources: &HashMap<String, String>) -> String {
+    let mut patch = String::new();
+    for (file, fixes) in by_file(fixes) {
+        let Some(source) = sources.get(&file) else {
+            continue;
+        };
+        _ = writeln!(patch, "--- a/{}\n+++ b/{}", file, file);
+
+        // Line shift introduced by the hunks above
+        let mut shift: isize = 0;
+        let mut copied_up_to = 0;
+        for fix in fixes {
+            if fix.start < copied_up_to {
+                continue;
+            }
+            copied_up_to = fix.end;
+
+            let (line, before, after) = fix.lines(source);
+            let (removed, added) = (before.lines().count(), after.lines().count());
+            // Empty ranges are numbered after the line preceding them
+            let old_start = if removed == 0 { line - 1 } else { line };
+            let new_start = (line as isize + shift) as usize - usize::from(added == 0);
+            _ = writeln!(
+                patch,
+                "@@ -{},{} +{},{} @@",
+                old_start, removed, new_start, added
+            );
+            before.lines().for_each(|l| _ = writeln!(patch, "-{}", l));
+            after.lines().for_each(|l| _ = writeln!(patch, "+{}", l));
+            shift += added as isize - removed as isize;
+        }
+    }
+    patch
+}
+
+// Markdown section listing the fixes of each detector, to append to a report
+pub fn report_section(found: &FoundFixes) -> String {
+    let mut section = String::new();
+    for (title, fixes) in &found.by_detector {
+        _ = writeln!(section, "\n## {}\n", title);
+        for fix in fixes {
+            let Some(source) = found.sources.get(&fix.file) else {
+                continue;
+            };
+            _ = writeln!(
+                section,
+                "{} (`{}:{}`)\n",
+                fix.title,
+                fix.file,
+                fix.line(source)
+            );
+            section.push_str(&fix.to_diff(source));
+            section.push('\n');
+        }
+    }
+    if section.is_empty() {
+        return section;
+    }
+    format!("\n# Suggested Fixes\n{}", section)
+}
+
+// Writes the fixes to the files under `root`, or prints them as a patch when `dry_run`
+pub fn apply(fixes: &[Fix], root: &Path, dry_run: bool) {
+    let mut sources = HashMap::new();
+    for file in by_file(fixes).keys() {
+        let path = root.join(file);
+        match std::fs::read_to_string(&path) {
+            Ok(source) => {
+                sources.insert(file.clone(), source);
+            }
+            Err(err) => eprintln!("[!] Skipping {}: {}", path.display(), err),
+        }
+    }
+
+    if dry_run {
+        print!("{}", to_patch(fixes, &sources));
+        return;
+    }
+    for (file, fixes) in by_file(fixes) {
+        let Some(source) = sources.get(&file) else {
+            continue;
+        };
+        std::fs::write(root.join(&file), apply_to_source(source, &fixes)).unwrap();
+        println!("[*] Fixed {} ({} fixes)", file, fixes.len());
+    }
+}
+
+fn by_file(fixes: &[Fix]) -> BTreeMap<String, Vec<&Fix>> {
+    let mut by_file: BTreeMap<String, Vec<&Fix>> = BTreeMap::new();
+    for fix in fixes {
+        by_file.entry(fix.file.clone()).or_default().push(fix);
+    }
+    by_file
+        .values_mut()
+        .for_each(|fixes| fixes.sort_by_key(|fix| (fix.start, fix.end)));
+    by_file
+}
+
+fn source_of(context: &WorkspaceContext, node: NodeID) -> Option<(String, &str)> {
+    let Some(ASTNode::SourceUnit(source_unit)) =
+        context.get_closest_parent(node, NodeType::SourceUnit)
+    else {
+        return None;
+    };
+    Some((
+        source_unit.absolute_path.clone()?,
+        source_unit.source.as_deref()?,
+    ))
+}
+
+// `src` is `start:length:file_index` in bytes
+pub(crate) fn parse_src(src: &str) -> Option<(usize, usize)> {
+    let mut parts = src.split(':');
+    let start = parts.next()?.parse().ok()?;
+    let length = parts.next()?.parse().ok()?;
+    Some((start, length))
+}
+
+#[cfg(test)]
+mod fixes_tests {
+
+    use crate::bot_detector::BotDetector;
+    use crate::bot_utils::load_contracts;
+    use crate::bot_utils::solc::load_solidity;
+    use crate::confidence::{filter_by_confidence, Confidence};
+    use crate::public_state_variables::detector::PublicStateVariablesDetector;
+    use crate::state_variable_is_never_used::detector::StateVariableIsNeverUsedDetector;
+
+    use super::{
+        apply_to_source, collect_fixes, sources, to_patch, Fix, FixableDetector, SharedFixes,
+    };
+
+    use aderyn_driver::context::workspace_context::WorkspaceContext;
+    use aderyn_driver::detector::IssueDetector;
+
+    const COUNTER: &str = "./foundry_workspace/out/Counter.sol/Counter.json";
+
+    fn fixes_of(detector: &mut impl FixableDetector, context: &WorkspaceContext) -> Vec<Fix> {
+        detector.detect(context).unwrap();
+        detector
+            .instances()
+            .into_values()
+            .filter_map(|instance| detector.fix(context, instance))
+            .collect()
+    }
+
+    #[test]
+    fn test_public_state_variable_fixes() {
+        let context = load_contracts(&[COUNTER.into()]);
+        let fixes = SharedFixes::default();
+        let detectors: Vec<Box<dyn BotDetector>> =
+            vec![Box::<PublicStateVariablesDetector>::default()];
+        for mut detector in collect_fixes(detectors, &fixes) {
+            detector.detect(&context).unwrap();
+        }
+
+        let found = std::mem::take(&mut *fixes.lock().unwrap());
+        let fixes = found.all();
+        assert_eq!(fixes.len(), 2);
+        let source = &found.sources[&fixes[0].file];
+
+        let fixed = apply_to_source(source, &fixes.iter().collect::<Vec<_>>());
+        assert!(fixed.contains("uint256 internal number;"));
+        assert!(fixed.contains("uint256 internal unused_number;"));
+        assert!(!fixed.contains(" public number"));
+
+        assert!(fixes[0]
+            .to_diff(source)
+            .contains("-    uint256 public number;\n+    uint256 internal number;"));
+    }
+
+    #[test]
+    fn test_unused_state_variable_fixes() {
+        let context = load_contracts(&[COUNTER.into()]);
+        let fixes = fixes_of(&mut StateVariableIsNeverUsedDetector::default(), &context);
+        assert_eq!(fixes.len(), 1);
+        let sources = sources(&context);
+        let source = &sources[&fixes[0].file];
+
+        // The whole line goes, trailing comment included
+        let fixed = apply_to_source(source, &fixes.iter().collect::<Vec<_>>());
+        assert!(!fixed.contains("unused_number"));
+        assert_eq!(fixed.lines().count(), source.lines().count() - 1);
+
+        let patch = to_patch(&fixes, &sources);
+        assert!(
+            patch.starts_with("--- a/src/Counter.sol\n+++ b/src/Counter.sol\n@@ -8,1 +7,0 @@\n-")
+        );
+    }
+
+    #[test]
+    fn test_fixes_of_reported_instances_only() {
+        let context = load_contracts(&[COUNTER.into()]);
+        let fixes = SharedFixes::default();
+        // `unused_number` is public, so of Medium confidence
+        let detectors: Vec<Box<dyn BotDetector>> =
+            vec![Box::<StateVariableIsNeverUsedDetector>::default()];
+        let detectors = filter_by_confidence(detectors, Confidence::High);
+        for mut detector in collect_fixes(detectors, &fixes) {
+            detector.detect(&context).unwrap();
+        }
+        assert!(std::mem::take(&mut *fixes.lock().unwrap()).all().is_empty());
+    }
+
+    #[test]
+    fn test_interface_getters_are_not_fixed() {
+        let Some(context) = load_solidity(
+            r#"
+            pragma solidity ^0.8.0;
+            interface IVault {
+                function owner() external view returns (address);
+            }
+            contract Vault is IVault {
+                address public override owner;
+                uint256 public total;
+            }
+            "#,
+        ) else {
+            return;
+        };
+
+        let mut detector = PublicStateVariablesDetector::default();
+        let fixes = fixes_of(&mut detector, &context);
+        assert_eq!(detector.instances().len(), 2);
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(fixes[0].title, "Make `total` internal");
+    }
+
+    #[test]
+    fn test_unused_variables_followed_by_storage_are_not_removed() {
+        let Some(context) = load_solidity(
+            r#"
+            pragma solidity ^0.8.0;
+            contract Layout {
+                uint256 internal unusedFirst;
+                uint256 internal used;
+                uint256 internal unusedLast;
+
+                function touch() external {
+                    used = 1;
+                }
+            }
+            contract Extended is Layout {
+                uint256 internal extra;
+
+                function touchExtra() external {
+                    extra = 1;
+                }
+            }
+            "#,
+        ) else {
+            return;
+        };
+
+        // `unusedLast` is stored last in `Layout`, but `extra` comes after it in `Extended`
+        let mut detector = StateVariableIsNeverUsedDetector::default();
+        let fixes = fixes_of(&mut detector, &context);
+        assert_eq!(detector.instances().len(), 2);
+        assert!(fixes.is_empty());
+    }
+
+    #[test]
+    fn test_unused_variables_moving_nothing_are_removed() {
+        let Some(context) = load_solidity(
+            r#"
+            pragma solidity ^0.8.0;
+            contract Layout {
+                uint128 internal used;
+                uint128 internal unusedPacked;
+                uint256 internal total;
+                uint64 internal unusedShifting;
+                uint64 internal flags;
+
+                function touch() external {
+                    used = 1;
+                    total = 1;
+                    flags = 1;
+                }
+            }
+            "#,
+        ) else {
+            return;
+        };
+
+        // `total` starts a new slot with or without `unusedPacked`, `flags` would take the offset of
+        // `unusedShifting`
+        let mut detector = StateVariableIsNeverUsedDetector::default();
+        let fixes = fixes_of(&mut detector, &context);
+        assert_eq!(detector.instances().len(), 2);
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(fixes[0].title, "Remove `unusedPacked`");
+    }
+}
@@ -0,0 +1,164 @@
+// _   ___   _______ _   _  ________   __  _______   __  ___  ___  _________ _      _____
+// | \ | \ \ / /_   _| | | | | ___ \ \ / / |  ___\ \ / / / _ \ |  \/  || ___ \ |    |  ___|
+// |  \| |\ V /  | | | |_| | | |_/ /\ V /  | |__  \ V / / /_\ \| .  . || |_/ / |    | |__
+// | . ` | \ /   | | |  _  | | ___ \ \ /   |  __| /   \ |  _  || |\/| ||  __/| |    |  __|
+// | |\  | | |   | | | | | | | |_/ / | |   | |___/ /^\ \| | | || |  | || |   | |____| |___
+// \_| \_/ \_/   \_/ \_| |_/ \____/  \_/   \____/\/   \/\_| |_/\_|  |_/\_|   \_____/\____/
+//
+//  _____                    ______     _            _
+// |_   _|                   |  _  \   | |          | |
+//   | | ___ ___ _   _  ___  | | | |___| |_ ___  ___| |_ ___  _ __
+//   | |/ __/ __| | | |/ _ \ | | | / _ \ __/ _ \/ __| __/ _ \| '__|
+//  _| |\__ \__ \ |_| |  __/ | |/ /  __/ ||  __/ (__| || (_) | |
+//  \___/___/___/\__,_|\___| |___/ \___|\__\___|\___|\__\___/|_|
+//
+// This IssueDetector is responsible for finding `pragma solidity` directives accepting more than one compiler version.
+// The `detect` function does this following:
+//
+//   1. Accepts a WorkspaceContext as `context`.
+//   2. Retrieves the `pragma solidity` directives of every SourceUnit (only the changed ones when the cache reruns it,
+//      see `UnitScopedDetector`) from the shared `SolidityVersions` analysis.
+//   3. Captures the directives whose range is not pinned to a single version.
+//   4. Returns true if any issues are found, otherwise false.
+//
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+
+use aderyn_driver::context::workspace_context::{ASTNode, WorkspaceContext};
+use aderyn_driver::core_ast::{NodeID, SourceUnit};
+use aderyn_driver::detection_modules::capture;
+use aderyn_driver::detector::{IssueDetector, IssueSeverity};
+
+use crate::analysis::Analyses;
+use crate::bot_detector::BotDetector;
+use crate::cache::UnitScopedDetector;
+use crate::confidence::Confidence;
+
+#[derive(Default)]
+pub struct FloatingPragmaDetector {
+    // Keys are source file name and line number
+    found_instances: BTreeMap<(String, usize, String), NodeID>,
+}
+
+impl IssueDetector for FloatingPragmaDetector {
+    fn detect(&mut self, context: &WorkspaceContext) -> Result<bool, Box<dyn Error>> {
+        self.capture_floating(context, |_| true);
+        Ok(!self.found_instances.is_empty())
+    }
+
+    fn title(&self) -> String {
+        String::from("Solidity pragma is floating")
+    }
+
+    fn description(&self) -> String {
+        String::from("The contract can be compiled by several compiler versions, so the deployed bytecode may come from a version other than the one it was tested with. Pin the version (ex: `pragma solidity 0.8.24;`) for contracts that get deployed.")
+    }
+
+    fn severity(&self) -> IssueSeverity {
+        IssueSeverity::NC
+    }
+
+    fn name(&self) -> String {
+        "floating-pragma".to_string()
+    }
+
+    fn instances(&self) -> BTreeMap<(String, usize, String), NodeID> {
+        self.found_instances.clone()
+    }
+}
+
+impl BotDetector for FloatingPragmaDetector {
+    fn confidence(&self) -> Confidence {
+        Confidence::High
+    }
+
+    fn unit_scoped(&mut self) -> Option<&mut dyn UnitScopedDetector> {
+        Some(self)
+    }
+}
+
+// The pragmas of a source unit only concern that unit
+impl UnitScopedDetector for FloatingPragmaDetector {
+    fn detect_units(
+        &mut self,
+        context: &WorkspaceContext,
+        units: &BTreeSet<String>,
+    ) -> Result<bool, Box<dyn Error>> {
+        self.capture_floating(context, |source_unit| {
+            source_unit
+                .absolute_path
+                .as_ref()
+                .is_some_and(|path| units.contains(path))
+        });
+        Ok(!self.found_instances.is_empty())
+    }
+}
+
+impl FloatingPragmaDetector {
+    // Captures the floating pragmas of the source units `in_scope` accepts
+    fn capture_floating(
+        &mut self,
+        context: &WorkspaceContext,
+        in_scope: impl Fn(&SourceUnit) -> bool,
+    ) {
+        let analyses = Analyses::new(context);
+        let versions = analyses.solidity_versions();
+        for source_unit in context.source_units() {
+            if !in_scope(source_unit) {
+                continue;
+            }
+            for (pragma, range) in versions.pragmas_of(source_unit.id) {
+                if !range.is_pinned() {
+                    self.capture_pragma(context, *pragma);
+                }
+            }
+        }
+    }
+
+    fn capture_pragma(&mut self, context: &WorkspaceContext, pragma: NodeID) {
+        if let Some(ASTNode::PragmaDirective(pragma)) = context.nodes.get(&pragma) {
+            capture!(self, context, pragma);
+        }
+    }
+}
+
+#[cfg(test)]
+mod floating_pragma_tests {
+
+    use crate::bot_utils::load_contracts;
+    use crate::config_tests::tests_configuration;
+
+    use super::FloatingPragmaDetector;
+
+    use aderyn_driver::context::workspace_context::WorkspaceContext;
+    use aderyn_driver::detector::IssueDetector;
+
+    fn test_floating_pragma_for(
+        _contract_files: Vec<String>,
+        context: WorkspaceContext,
+        mut detector: impl IssueDetector,
+    ) {
+        // assert that the detector finds the range of Legacy.sol, not the pinned version of Pinned.sol
+        let found = detector.detect(&context).unwrap();
+        assert!(found);
+        let locations = detector
+            .instances()
+            .into_keys()
+            .map(|(file, line, _)| (file, line))
+            .collect::<Vec<_>>();
+        assert_eq!(locations, vec![(String::from("src/pragma/Legacy.sol"), 2)]);
+    }
+
+    #[test]
+    fn test_floating_pragma() {
+        let detector = FloatingPragmaDetector::default();
+        let contracts = tests_configuration().get_contracts_for(detector.name());
+
+        for contract_files in contracts {
+            let detector = FloatingPragmaDetector::default();
+            let context = load_contracts(&contract_files);
+            test_floating_pragma_for(contract_files, context, detector);
+        }
+    }
+}
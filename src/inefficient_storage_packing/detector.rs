@@ -0,0 +1,132 @@
+// _   ___   _______ _   _  ________   __  _______   __  ___  ___  _________ _      _____
+// | \ | \ \ / /_   _| | | | | ___ \ \ / / |  ___\ \ / / / _ \ |  \/  || ___ \ |    |  ___|
+// |  \| |\ V /  | | | |_| | | |_/ /\ V /  | |__  \ V / / /_\ \| .  . || |_/ / |    | |__
+// | . ` | \ /   | | |  _  | | ___ \ \ /   |  __| /   \ |  _  || |\/| ||  __/| |    |  __|
+// | |\  | | |   | | | | | | | |_/ / | |   | |___/ /^\ \| | | || |  | || |   | |____| |___
+// \_| \_/ \_/   \_/ \_| |_/ \____/  \_/   \____/\/   \/\_| |_/\_|  |_/\_|   \_____/\____/
+//
+//  _____                    ______     _            _
+// |_   _|                   |  _  \   | |          | |
+//   | | ___ ___ _   _  ___  | | | |___| |_ ___  ___| |_ ___  _ __
+//   | |/ __/ __| | | |/ _ \ | | | / _ \ __/ _ \/ __| __/ _ \| '__|
+//  _| |\__ \__ \ |_| |  __/ | |/ /  __/ ||  __/ (__| || (_) | |
+//  \___/___/___/\__,_|\___| |___/ \___|\__\___|\___|\__\___/|_|
+//
+// This IssueDetector is responsible for finding contracts whose state variables could use fewer storage slots.
+// The `detect` function does this following:
+//
+//   1. Accepts a WorkspaceContext as `context`.
+//   2. Retrieves all ContractDefinition nodes from the `context`.
+//   3. For each ContractDefinition, gets its storage layout from the shared `StorageLayouts` analysis (the
+//      compiler's `storageLayout` when the artifact has one).
+//   4. Captures the contract when the variables it declares itself touch more slots than they would
+//      with the best ordering (inherited variables cannot be reordered from the contract).
+//   5. Returns true if any issues are found, otherwise false.
+//
+
+use std::{collections::BTreeMap, error::Error};
+
+use aderyn_driver::context::workspace_context::WorkspaceContext;
+use aderyn_driver::core_ast::NodeID;
+use aderyn_driver::detection_modules::capture;
+use aderyn_driver::detector::{IssueDetector, IssueSeverity};
+
+use crate::analysis::storage_layout::StorageLayout;
+use crate::analysis::Analyses;
+use crate::bot_detector::BotDetector;
+use crate::confidence::Confidence;
+
+#[derive(Default)]
+pub struct InefficientStoragePackingDetector {
+    // Keys are source file name and line number
+    found_instances: BTreeMap<(String, usize, String), NodeID>,
+}
+
+impl IssueDetector for InefficientStoragePackingDetector {
+    fn detect(&mut self, context: &WorkspaceContext) -> Result<bool, Box<dyn Error>> {
+        let analyses = Analyses::new(context);
+        let layouts = analyses.storage_layouts();
+        for contract_definition in context.contract_definitions() {
+            let Some(layout) = layouts.of(contract_definition.id) else {
+                continue;
+            };
+            let declared = layout
+                .entries
+                .iter()
+                .filter(|e| e.contract == contract_definition.name)
+                .collect::<Vec<_>>();
+            if StorageLayout::optimal_slots(declared.iter().copied())
+                < StorageLayout::slots_of(declared.iter().copied())
+            {
+                capture!(self, context, contract_definition);
+            }
+        }
+        Ok(!self.found_instances.is_empty())
+    }
+
+    fn title(&self) -> String {
+        String::from("State variables could be packed into fewer storage slots")
+    }
+
+    fn description(&self) -> String {
+        String::from("The state variables of this contract can be reordered so that small values share slots. Every slot saved avoids a cold SLOAD / SSTORE.")
+    }
+
+    fn severity(&self) -> IssueSeverity {
+        IssueSeverity::NC
+    }
+
+    fn name(&self) -> String {
+        "inefficient-storage-packing".to_string()
+    }
+
+    fn instances(&self) -> BTreeMap<(String, usize, String), NodeID> {
+        self.found_instances.clone()
+    }
+}
+
+impl BotDetector for InefficientStoragePackingDetector {
+    fn confidence(&self) -> Confidence {
+        Confidence::High
+    }
+}
+
+#[cfg(test)]
+mod inefficient_storage_packing_tests {
+
+    use crate::bot_utils::load_contracts;
+    use crate::config_tests::tests_configuration;
+
+    use super::InefficientStoragePackingDetector;
+
+    use aderyn_driver::context::workspace_context::WorkspaceContext;
+    use aderyn_driver::detector::IssueDetector;
+
+    fn test_inefficient_storage_packing_for(
+        _contract_files: Vec<String>,
+        context: WorkspaceContext,
+        mut detector: impl IssueDetector,
+    ) {
+        // assert that the detector finds `Unpacked` only, `Packed` already uses the fewest slots
+        let found = detector.detect(&context).unwrap();
+        assert!(found);
+        let locations = detector
+            .instances()
+            .into_keys()
+            .map(|(file, line, _)| (file, line))
+            .collect::<Vec<_>>();
+        assert_eq!(locations, vec![(String::from("src/Packing.sol"), 24)]);
+    }
+
+    #[test]
+    fn test_inefficient_storage_packing() {
+        let detector = InefficientStoragePackingDetector::default();
+        let contracts = tests_configuration().get_contracts_for(detector.name());
+
+        for contract_files in contracts {
+            let detector = InefficientStoragePackingDetector::default();
+            let context = load_contracts(&contract_files);
+            test_inefficient_storage_packing_for(contract_files, context, detector);
+        }
+    }
+}
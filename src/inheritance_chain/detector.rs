@@ -0,0 +1,173 @@
+// _   ___   _______ _   _  ________   __  _______   __  ___  ___  _________ _      _____
+// | \ | \ \ / /_   _| | | | | ___ \ \ / / |  ___\ \ / / / _ \ |  \/  || ___ \ |    |  ___|
+// |  \| |\ V /  | | | |_| | | |_/ /\ V /  | |__  \ V / / /_\ \| .  . || |_/ / |    | |__
+// | . ` | \ /   | | |  _  | | ___ \ \ /   |  __| /   \ |  _  || |\/| ||  __/| |    |  __|
+// | |\  | | |   | | | | | | | |_/ / | |   | |___/ /^\ \| | | || |  | || |   | |____| |___
+// \_| \_/ \_/   \_/ \_| |_/ \____/  \_/   \____/\/   \/\_| |_/\_|  |_/\_|   \_____/\____/
+// ______                     _     _       ______     _            _
+// | ___ \                   | |   | |      |  _  \   | |          | |
+// | |_/ /___ _   _ ___  __ _| |__ | | ___  | | | |___| |_ ___  ___| |_ ___  _ __
+// |    // _ \ | | / __|/ _` | '_ \| |/ _ \ | | | / _ \ __/ _ \/ __| __/ _ \| '__|
+// | |\ \  __/ |_| \__ \ (_| | |_) | |  __/ | |/ /  __/ ||  __/ (__| || (_) | |
+// \_| \_\___|\__,_|___/\__,_|_.__/|_|\___| |___/ \___|\__\___|\___|\__\___/|_|
+//
+// This ReusableDetector is responsible for computing the C3 linearized inheritance chain of ContractDefinition AST nodes.
+// The `detect` function does this following:
+//
+//   1. Accept ContractDefinition nodes via the `within` parameter.
+//   2. For each of those, resolve its direct bases from the `is A, B` inheritance specifiers.
+//   3. Recursively linearize the bases and merge them the way solc does (right-most base is the most derived).
+//   4. Add the chain, most derived contract first, to the `found_instances` vector.
+//   5. Return the `found_instances` vector.
+//
+
+use std::collections::HashMap;
+
+use aderyn_driver::context::workspace_context::ASTNode;
+use aderyn_driver::context::workspace_context::WorkspaceContext;
+use aderyn_driver::core_ast::NodeID;
+use aderyn_driver::detector::ReusableDetector;
+
+#[derive(Default)]
+pub struct InheritanceChainDetector {
+    found_instances: Vec<ASTNode>,
+}
+
+impl ReusableDetector for InheritanceChainDetector {
+    fn detect(
+        &mut self,
+        context: &WorkspaceContext,
+        _: &[ASTNode],
+        within: &[ASTNode],
+    ) -> Result<&[ASTNode], Box<dyn std::error::Error>> {
+        let mut linearizations = HashMap::new();
+        for node in within {
+            if let ASTNode::ContractDefinition(contract_definition) = node {
+                let chain = linearize(context, contract_definition.id, &mut linearizations)?;
+                for id in chain {
+                    if let Some(contract) = context.nodes.get(&id) {
+                        self.found_instances.push(contract.clone());
+                    }
+                }
+            }
+        }
+
+        Ok(&self.found_instances)
+    }
+
+    fn name(&self) -> String {
+        "inheritance-chain".to_string()
+    }
+}
+
+// Ids of the contracts deriving from `contract` (including itself). solc already records the C3
+// linearization of every contract, so this is a single pass over `linearizedBaseContracts`.
+pub fn derived_contracts(context: &WorkspaceContext, contract: NodeID) -> Vec<NodeID> {
+    context
+        .contract_definitions()
+        .into_iter()
+        .filter(|c| {
+            c.linearized_base_contracts
+                .as_ref()
+                .is_some_and(|bases| bases.contains(&contract))
+        })
+        .map(|c| c.id)
+        .collect()
+}
+
+// C3 linearization of `contract`, most derived first (same order as solc's `linearizedBaseContracts`)
+pub fn linearize(
+    context: &WorkspaceContext,
+    contract: NodeID,
+    linearizations: &mut HashMap<NodeID, Vec<NodeID>>,
+) -> Result<Vec<NodeID>, String> {
+    if let Some(chain) = linearizations.get(&contract) {
+        return Ok(chain.clone());
+    }
+
+    let Some(ASTNode::ContractDefinition(contract_definition)) = context.nodes.get(&contract)
+    else {
+        return Err(format!("contract {} is not part of the context", contract));
+    };
+
+    // Guards against cyclic inheritance (ex: an incomplete context)
+    linearizations.insert(contract, vec![contract]);
+
+    // Solidity lists bases from "most base-like" to "most derived", C3 expects the opposite
+    let bases = contract_definition
+        .base_contracts
+        .iter()
+        .filter_map(|specifier| specifier.base_name.referenced_declaration)
+        .rev()
+        .collect::<Vec<_>>();
+
+    let mut sequences = vec![];
+    for base in &bases {
+        sequences.push(linearize(context, *base, linearizations)?);
+    }
+    sequences.push(bases);
+
+    let mut chain = vec![contract];
+    loop {
+        sequences.retain(|sequence| !sequence.is_empty());
+        if sequences.is_empty() {
+            break;
+        }
+
+        // The next contract is the first head that does not appear in the tail of another sequence
+        let head = sequences
+            .iter()
+            .map(|sequence| sequence[0])
+            .find(|candidate| !sequences.iter().any(|s| s[1..].contains(candidate)))
+            .ok_or_else(|| {
+                format!(
+                    "linearization of {} is impossible",
+                    contract_definition.name
+                )
+            })?;
+
+        chain.push(head);
+        for sequence in sequences.iter_mut() {
+            if sequence[0] == head {
+                sequence.remove(0);
+            }
+        }
+    }
+
+    linearizations.insert(contract, chain.clone());
+    Ok(chain)
+}
+
+#[cfg(test)]
+mod inheritance_chain_tests {
+
+    use crate::bot_utils::{load_contracts, node_name, NodeSelector};
+    use crate::config_tests::tests_configuration;
+
+    use super::InheritanceChainDetector;
+
+    use aderyn_driver::detector::ReusableDetector;
+
+    #[test]
+    fn test_inheritance_chain() {
+        let detector = InheritanceChainDetector::default();
+        let cases = tests_configuration().get_reusable_cases_for(detector.name());
+
+        for case in cases {
+            let mut detector = InheritanceChainDetector::default();
+            case.run(&mut detector);
+        }
+    }
+
+    #[test]
+    fn test_inheritance_chain_is_c3_ordered() {
+        let context = load_contracts(&["./foundry_workspace/out/Diamond.sol/D.json".into()]);
+        let within = NodeSelector::contract("D").select(&context);
+
+        let mut detector = InheritanceChainDetector::default();
+        let chain = detector.detect(&context, &[], &within).unwrap();
+        let chain = chain.iter().filter_map(node_name).collect::<Vec<_>>();
+
+        assert_eq!(chain, vec!["D", "C", "B", "A"]);
+    }
+}
@@ -0,0 +1,237 @@
+// _   ___   _______ _   _  ________   __  _______   __  ___  ___  _________ _      _____
+// | \ | \ \ / /_   _| | | | | ___ \ \ / / |  ___\ \ / / / _ \ |  \/  || ___ \ |    |  ___|
+// |  \| |\ V /  | | | |_| | | |_/ /\ V /  | |__  \ V / / /_\ \| .  . || |_/ / |    | |__
+// | . ` | \ /   | | |  _  | | ___ \ \ /   |  __| /   \ |  _  || |\/| ||  __/| |    |  __|
+// | |\  | | |   | | | | | | | |_/ / | |   | |___/ /^\ \| | | || |  | || |   | |____| |___
+// \_| \_/ \_/   \_/ \_| |_/ \____/  \_/   \____/\/   \/\_| |_/\_|  |_/\_|   \_____/\____/
+// ______                     _     _       ______     _            _
+// | ___ \                   | |   | |      |  _  \   | |          | |
+// | |_/ /___ _   _ ___  __ _| |__ | | ___  | | | |___| |_ ___  ___| |_ ___  _ __
+// |    // _ \ | | / __|/ _` | '_ \| |/ _ \ | | | / _ \ __/ _ \/ __| __/ _ \| '__|
+// | |\ \  __/ |_| \__ \ (_| | |_) | |  __/ | |/ /  __/ ||  __/ (__| || (_) | |
+// \_| \_\___|\__,_|___/\__,_|_.__/|_|\___| |___/ \___|\__\___|\___|\__\___/|_|
+//
+// This ReusableDetector is responsible for finding the inline assembly blocks within the given scope.
+// The `detect` function does this following:
+//
+//   1. Accept ContractDefinition, FunctionDefinition or ModifierDefinition nodes via the `within` parameter.
+//   2. Collect every InlineAssembly node that has one of those as ancestor.
+//   3. Summarize each block: the notable Yul opcodes it calls (see `YulOpcode`), read from its Yul AST,
+//      and the Solidity variables it references (`externalReferences` in the AST).
+//   4. Add each InlineAssembly node to the `found_instances` vector.
+//   5. Return the `found_instances` vector.
+//
+// Use `summaries()` after `detect` to get the summary of each block.
+//
+
+use std::collections::BTreeSet;
+
+use aderyn_driver::context::workspace_context::ASTNode;
+use aderyn_driver::context::workspace_context::WorkspaceContext;
+use aderyn_driver::core_ast::{ExternalReference, NodeID};
+use aderyn_driver::detector::ReusableDetector;
+
+use crate::analysis::yul::yul_calls;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum YulOpcode {
+    SStore,
+    DelegateCall,
+    Call,
+    Create2,
+    SelfDestruct,
+    ReturnDataCopy,
+}
+
+impl YulOpcode {
+    fn from_builtin(name: &str) -> Option<Self> {
+        match name {
+            "sstore" => Some(YulOpcode::SStore),
+            "delegatecall" => Some(YulOpcode::DelegateCall),
+            "call" => Some(YulOpcode::Call),
+            "create2" => Some(YulOpcode::Create2),
+            "selfdestruct" => Some(YulOpcode::SelfDestruct),
+            "returndatacopy" => Some(YulOpcode::ReturnDataCopy),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AssemblySummary {
+    // InlineAssembly node
+    pub node: NodeID,
+    pub opcodes: BTreeSet<YulOpcode>,
+    // Declarations of the Solidity variables used in the block, without duplicates
+    pub referenced_variables: Vec<NodeID>,
+}
+
+impl AssemblySummary {
+    pub fn uses(&self, opcode: YulOpcode) -> bool {
+        self.opcodes.contains(&opcode)
+    }
+}
+
+#[derive(Default)]
+pub struct InlineAssemblyDetector {
+    found_instances: Vec<ASTNode>,
+    summaries: Vec<AssemblySummary>,
+}
+
+impl InlineAssemblyDetector {
+    pub fn summaries(&self) -> &[AssemblySummary] {
+        &self.summaries
+    }
+}
+
+impl ReusableDetector for InlineAssemblyDetector {
+    fn detect(
+        &mut self,
+        context: &WorkspaceContext,
+        _: &[ASTNode],
+        within: &[ASTNode],
+    ) -> Result<&[ASTNode], Box<dyn std::error::Error>> {
+        let scopes = within
+            .iter()
+            .filter(|node| {
+                matches!(
+                    node,
+                    ASTNode::ContractDefinition(_)
+                        | ASTNode::FunctionDefinition(_)
+                        | ASTNode::ModifierDefinition(_)
+                )
+            })
+            .filter_map(|node| node.id())
+            .collect::<Vec<_>>();
+
+        let mut assemblies = context
+            .inline_assemblies()
+            .into_iter()
+            .filter(|assembly| {
+                context
+                    .get_parent_chain(assembly.id)
+                    .iter()
+                    .any(|ancestor| ancestor.id().is_some_and(|id| scopes.contains(&id)))
+            })
+            .collect::<Vec<_>>();
+        assemblies.sort_by_key(|assembly| assembly.id);
+
+        for assembly in assemblies {
+            let opcodes = yul_calls(assembly)
+                .iter()
+                .filter_map(|call| YulOpcode::from_builtin(&call.function_name.name))
+                .collect();
+
+            let mut referenced_variables = vec![];
+            for reference in &assembly.external_references {
+                for declaration in referenced_declarations(reference) {
+                    if !referenced_variables.contains(&declaration) {
+                        referenced_variables.push(declaration);
+                    }
+                }
+            }
+
+            self.summaries.push(AssemblySummary {
+                node: assembly.id,
+                opcodes,
+                referenced_variables,
+            });
+            self.found_instances
+                .push(ASTNode::InlineAssembly(assembly.clone()));
+        }
+
+        Ok(&self.found_instances)
+    }
+
+    fn name(&self) -> String {
+        "inline-assembly".to_string()
+    }
+}
+
+// Declarations referenced from an assembly block. The fields of `ExternalReferenceData` are private,
+// so they are read back from its serialized form.
+fn referenced_declarations(reference: &ExternalReference) -> Vec<NodeID> {
+    let data = match reference {
+        ExternalReference::Untagged(data) => vec![data],
+        ExternalReference::Tagged(map) => map.values().collect(),
+    };
+    data.into_iter()
+        .filter_map(|data| serde_json::to_value(data).ok())
+        .filter_map(|value| value.get("declaration")?.as_i64())
+        .collect()
+}
+
+#[cfg(test)]
+mod inline_assembly_tests {
+
+    use crate::bot_utils::{load_contracts, NodeSelector};
+    use crate::config_tests::tests_configuration;
+
+    use super::{InlineAssemblyDetector, YulOpcode};
+
+    use aderyn_driver::context::workspace_context::ASTNode;
+    use aderyn_driver::detector::ReusableDetector;
+
+    #[test]
+    fn test_inline_assembly() {
+        let detector = InlineAssemblyDetector::default();
+        let cases = tests_configuration().get_reusable_cases_for(detector.name());
+
+        for case in cases {
+            let mut detector = InlineAssemblyDetector::default();
+            case.run(&mut detector);
+        }
+    }
+
+    #[test]
+    fn test_inline_assembly_summaries() {
+        let context =
+            load_contracts(&["./foundry_workspace/out/Assembly.sol/Assembly.json".into()]);
+        let within = NodeSelector::contract("Assembly").select(&context);
+
+        let mut detector = InlineAssemblyDetector::default();
+        let found = detector.detect(&context, &[], &within).unwrap();
+        assert_eq!(found.len(), 4);
+
+        let summary_of = |function: &str| {
+            let function = context
+                .function_definitions()
+                .into_iter()
+                .find(|f| f.name == function)
+                .unwrap();
+            detector
+                .summaries()
+                .iter()
+                .find(|s| {
+                    context
+                        .get_parent_chain(s.node)
+                        .iter()
+                        .any(|ancestor| ancestor.id() == Some(function.id))
+                })
+                .unwrap()
+                .clone()
+        };
+        let name_of = |id| match context.nodes.get(&id) {
+            Some(ASTNode::VariableDeclaration(v)) => v.name.clone(),
+            _ => String::new(),
+        };
+
+        let store = summary_of("store");
+        assert!(store.uses(YulOpcode::SStore));
+        let mut names = store
+            .referenced_variables
+            .iter()
+            .map(|&id| name_of(id))
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["counter", "value"]);
+
+        let forward = summary_of("forward");
+        assert!(forward.uses(YulOpcode::DelegateCall));
+        assert!(forward.uses(YulOpcode::ReturnDataCopy));
+        assert!(!forward.uses(YulOpcode::Call));
+
+        assert!(summary_of("deploy").uses(YulOpcode::Create2));
+        assert!(summary_of("pureMath").opcodes.is_empty());
+    }
+}
@@ -1,10 +1,33 @@
+pub mod access_control_guards;
+pub mod arbitrary_transfer_from;
+pub mod external_call_sites;
+pub mod floating_pragma;
+pub mod inefficient_storage_packing;
+pub mod inheritance_chain;
+pub mod inline_assembly;
+pub mod outdated_solidity_version;
 pub mod public_state_variables;
+pub mod push0_incompatible_version;
+pub mod reachable_functions;
 pub mod state_variable_is_never_used;
+pub mod state_variable_is_never_written;
+pub mod state_variable_is_write_only;
+pub mod state_variable_reads_and_writes;
 pub mod state_variables_in_contract_definition;
+pub mod state_variables_written_by_function;
+pub mod upgradeable_storage_collision;
 // ADERYN-PILOT: 0x03 - Custom Detectors
 
 // Internals
+pub mod analysis;
 pub mod bot_brain;
+pub mod bot_detector;
 pub mod bot_utils;
+pub mod cache;
+pub mod confidence;
 pub mod config_tests;
+pub mod fixes;
+pub mod query;
+pub mod rule_engine;
 pub mod runner;
+pub mod watch;
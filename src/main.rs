@@ -27,7 +27,11 @@
  *
  */
 use clap::{Parser, Subcommand};
-use my_bot::{bot_brain, runner};
+use my_bot::{
+    bot_brain::{self, BenchmarkOptions},
+    confidence::Confidence,
+    runner,
+};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -35,6 +39,21 @@ pub struct CommandLineArgs {
     // These are commands are intended to be used by judging systems
     #[clap(subcommand, name = "pilot")]
     pilot: Option<PilotCommand>,
+    /// Apply the fixes suggested for the reported findings to the sources of the project
+    #[arg(long)]
+    apply_fixes: bool,
+    /// With --apply-fixes, print the fixes as a patch instead of writing them
+    #[arg(long, requires = "apply_fixes")]
+    dry_run: bool,
+    /// Only report findings of at least this confidence: High, Medium or Low
+    #[arg(long, default_value = "Low")]
+    min_confidence: Confidence,
+    /// Rerun the detectors on every change to the project and print the new and resolved findings
+    #[arg(long, conflicts_with = "apply_fixes")]
+    watch: bool,
+    /// Rerun every detector instead of reusing the instances saved in .nyth-cache
+    #[arg(long)]
+    no_cache: bool,
 }
 
 #[derive(Debug, Subcommand)]
@@ -48,14 +67,93 @@ enum PilotCommand {
         /// Markdown file for judging path/to/*.judge.md
         output: String,
     },
+    /// Time `detect()` of every custom detector and compare against a saved baseline
+    Benchmark {
+        /// Root folders of the projects to benchmark on
+        #[arg(default_values = ["./benchmarks/workspaces/vault", "./foundry_workspace"])]
+        roots: Vec<String>,
+        /// Timings of a known good run
+        #[arg(long, default_value = "benchmarks/baseline.json")]
+        baseline: String,
+        /// Overwrite the baseline with the timings of this run
+        #[arg(long)]
+        save_baseline: bool,
+        /// Number of runs per detector, the median is reported
+        #[arg(long, default_value_t = 5)]
+        iterations: usize,
+        /// Allowed slowdown in percent before failing
+        #[arg(long, default_value_t = 20.0)]
+        tolerance: f64,
+    },
+    /// Score custom detectors against labeled projects and write a markdown scorecard
+    Evaluate {
+        /// Folder whose sub folders are projects carrying a nyth-labels.json sidecar
+        corpus: String,
+        /// Markdown file for the scorecard
+        #[arg(default_value = "scorecard.md")]
+        output: String,
+    },
+    /// Compare the storage layout of two versions of an upgradeable contract
+    StorageDiff {
+        /// Deployed version: an artifact (out/Vault.sol/Vault.json) or a project root
+        old: String,
+        /// Upgraded version: an artifact or a project root
+        new: String,
+        /// Contract to compare, required for project roots
+        #[arg(long)]
+        contract: Option<String>,
+    },
+    /// Print the nodes matching a structural query, ex: 'FunctionDefinition >> ForStatement >> FunctionCall[name=transfer]'
+    Query {
+        /// Query to evaluate (see `src/query/mod.rs` for the syntax)
+        expression: String,
+        /// Root folder of the project to query
+        #[arg(long, default_value = "./foundry_workspace")]
+        root: String,
+        /// Save the query as `rules/<PROMOTE>.toml` instead, to run it as a detector
+        #[arg(long)]
+        promote: Option<String>,
+        /// Title of the promoted detector
+        #[arg(long, default_value = "")]
+        title: String,
+        /// Description of the promoted detector
+        #[arg(long, default_value = "")]
+        description: String,
+        /// Severity of the promoted detector: Critical, High, Medium, Low or NC
+        #[arg(long, default_value = "Low")]
+        severity: String,
+    },
+    /// Run custom detectors on every project of a manifest, with a report per project and a summary table
+    Batch {
+        /// TOML file listing the projects: [[project]] root = "..." (name, scope and exclude are optional)
+        manifest: String,
+        /// Folder for the reports and summary.md
+        #[arg(default_value = "batch-reports")]
+        output: String,
+    },
+    /// Delete the detector instances saved by previous runs in .nyth-cache
+    ClearCache {
+        /// Only delete the instances of this detector
+        detector: Option<String>,
+    },
 }
 
 fn main() {
     let cmd_args = CommandLineArgs::parse();
 
+    if cmd_args.apply_fixes {
+        runner::apply_fixes(cmd_args.min_confidence, cmd_args.no_cache, cmd_args.dry_run);
+        return;
+    }
+
+    if cmd_args.watch {
+        runner::watch(cmd_args.min_confidence);
+        return;
+    }
+
     if cmd_args.pilot.is_none() {
         println!("[*] Running bot ");
-        runner::run();
+        runner::run(cmd_args.min_confidence, cmd_args.no_cache);
         return;
     }
 
@@ -64,5 +162,45 @@ fn main() {
         PilotCommand::GenerateReportForJudge { root, output } => {
             bot_brain::generate_report_for_judge(root.as_str(), output.as_str());
         }
+        PilotCommand::Benchmark {
+            roots,
+            baseline,
+            save_baseline,
+            iterations,
+            tolerance,
+        } => bot_brain::benchmark_detectors(BenchmarkOptions {
+            roots,
+            baseline,
+            save_baseline,
+            iterations,
+            tolerance,
+        }),
+        PilotCommand::Evaluate { corpus, output } => {
+            bot_brain::evaluate_detectors(corpus.as_str(), output.as_str());
+        }
+        PilotCommand::StorageDiff { old, new, contract } => {
+            bot_brain::storage_diff(old.as_str(), new.as_str(), contract.as_deref());
+        }
+        PilotCommand::Query {
+            expression,
+            root,
+            promote,
+            title,
+            description,
+            severity,
+        } => match promote {
+            Some(name) => bot_brain::promote_query(
+                expression.as_str(),
+                name.as_str(),
+                title.as_str(),
+                description.as_str(),
+                severity.as_str(),
+            ),
+            None => bot_brain::run_query(expression.as_str(), root.as_str()),
+        },
+        PilotCommand::Batch { manifest, output } => {
+            bot_brain::batch_detectors(manifest.as_str(), output.as_str());
+        }
+        PilotCommand::ClearCache { detector } => runner::clear_cache(detector.as_deref()),
     }
 }
@@ -0,0 +1,171 @@
+// _   ___   _______ _   _  ________   __  _______   __  ___  ___  _________ _      _____
+// | \ | \ \ / /_   _| | | | | ___ \ \ / / |  ___\ \ / / / _ \ |  \/  || ___ \ |    |  ___|
+// |  \| |\ V /  | | | |_| | | |_/ /\ V /  | |__  \ V / / /_\ \| .  . || |_/ / |    | |__
+// | . ` | \ /   | | |  _  | | ___ \ \ /   |  __| /   \ |  _  || |\/| ||  __/| |    |  __|
+// | |\  | | |   | | | | | | | |_/ / | |   | |___/ /^\ \| | | || |  | || |   | |____| |___
+// \_| \_/ \_/   \_/ \_| |_/ \____/  \_/   \____/\/   \/\_| |_/\_|  |_/\_|   \_____/\____/
+//
+//  _____                    ______     _            _
+// |_   _|                   |  _  \   | |          | |
+//   | | ___ ___ _   _  ___  | | | |___| |_ ___  ___| |_ ___  _ __
+//   | |/ __/ __| | | |/ _ \ | | | / _ \ __/ _ \/ __| __/ _ \| '__|
+//  _| |\__ \__ \ |_| |  __/ | |/ /  __/ ||  __/ (__| || (_) | |
+//  \___/___/___/\__,_|\___| |___/ \___|\__\___|\___|\__\___/|_|
+//
+// This IssueDetector is responsible for finding `pragma solidity` directives allowing compilers with known bugs.
+// The `detect` function does this following:
+//
+//   1. Accepts a WorkspaceContext as `context`.
+//   2. Retrieves the `pragma solidity` directives of every SourceUnit (only the changed ones when the cache reruns it,
+//      see `UnitScopedDetector`) from the shared `SolidityVersions` analysis.
+//   3. Looks up the oldest release each range accepts in the embedded list of known compiler bugs (`analysis::solc_bugs`).
+//   4. Captures the directives whose oldest release is affected by a bug of low severity or more.
+//   5. Returns true if any issues are found, otherwise false.
+//
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+
+use aderyn_driver::context::workspace_context::{ASTNode, WorkspaceContext};
+use aderyn_driver::core_ast::{NodeID, SourceUnit};
+use aderyn_driver::detection_modules::capture;
+use aderyn_driver::detector::{IssueDetector, IssueSeverity};
+
+use crate::analysis::solc_bugs::{bugs_affecting, BugSeverity};
+use crate::analysis::Analyses;
+use crate::bot_detector::BotDetector;
+use crate::cache::UnitScopedDetector;
+use crate::confidence::Confidence;
+
+#[derive(Default)]
+pub struct OutdatedSolidityVersionDetector {
+    // Keys are source file name and line number
+    found_instances: BTreeMap<(String, usize, String), NodeID>,
+}
+
+impl IssueDetector for OutdatedSolidityVersionDetector {
+    fn detect(&mut self, context: &WorkspaceContext) -> Result<bool, Box<dyn Error>> {
+        self.capture_outdated(context, |_| true);
+        Ok(!self.found_instances.is_empty())
+    }
+
+    fn title(&self) -> String {
+        String::from("Solidity version with known compiler bugs")
+    }
+
+    fn description(&self) -> String {
+        String::from("The pragma allows a compiler release affected by published bugs (see https://docs.soliditylang.org/en/latest/bugs.html). Require a recent release such as 0.8.24 or later.")
+    }
+
+    fn severity(&self) -> IssueSeverity {
+        IssueSeverity::Low
+    }
+
+    fn name(&self) -> String {
+        "outdated-solidity-version".to_string()
+    }
+
+    fn instances(&self) -> BTreeMap<(String, usize, String), NodeID> {
+        self.found_instances.clone()
+    }
+}
+
+impl BotDetector for OutdatedSolidityVersionDetector {
+    fn confidence(&self) -> Confidence {
+        Confidence::High
+    }
+
+    fn unit_scoped(&mut self) -> Option<&mut dyn UnitScopedDetector> {
+        Some(self)
+    }
+}
+
+// The pragmas of a source unit only concern that unit
+impl UnitScopedDetector for OutdatedSolidityVersionDetector {
+    fn detect_units(
+        &mut self,
+        context: &WorkspaceContext,
+        units: &BTreeSet<String>,
+    ) -> Result<bool, Box<dyn Error>> {
+        self.capture_outdated(context, |source_unit| {
+            source_unit
+                .absolute_path
+                .as_ref()
+                .is_some_and(|path| units.contains(path))
+        });
+        Ok(!self.found_instances.is_empty())
+    }
+}
+
+impl OutdatedSolidityVersionDetector {
+    // Captures the outdated pragmas of the source units `in_scope` accepts
+    fn capture_outdated(
+        &mut self,
+        context: &WorkspaceContext,
+        in_scope: impl Fn(&SourceUnit) -> bool,
+    ) {
+        let analyses = Analyses::new(context);
+        let versions = analyses.solidity_versions();
+        for source_unit in context.source_units() {
+            if !in_scope(source_unit) {
+                continue;
+            }
+            for (pragma, range) in versions.pragmas_of(source_unit.id) {
+                let outdated = range.lowest_release().is_some_and(|lowest| {
+                    bugs_affecting(&lowest)
+                        .iter()
+                        .any(|bug| bug.severity >= BugSeverity::Low)
+                });
+                if outdated {
+                    self.capture_pragma(context, *pragma);
+                }
+            }
+        }
+    }
+
+    fn capture_pragma(&mut self, context: &WorkspaceContext, pragma: NodeID) {
+        if let Some(ASTNode::PragmaDirective(pragma)) = context.nodes.get(&pragma) {
+            capture!(self, context, pragma);
+        }
+    }
+}
+
+#[cfg(test)]
+mod outdated_solidity_version_tests {
+
+    use crate::bot_utils::load_contracts;
+    use crate::config_tests::tests_configuration;
+
+    use super::OutdatedSolidityVersionDetector;
+
+    use aderyn_driver::context::workspace_context::WorkspaceContext;
+    use aderyn_driver::detector::IssueDetector;
+
+    fn test_outdated_solidity_version_for(
+        _contract_files: Vec<String>,
+        context: WorkspaceContext,
+        mut detector: impl IssueDetector,
+    ) {
+        // assert that the detector finds the range of Legacy.sol, not the bug free 0.8.24 of Pinned.sol
+        let found = detector.detect(&context).unwrap();
+        assert!(found);
+        let locations = detector
+            .instances()
+            .into_keys()
+            .map(|(file, line, _)| (file, line))
+            .collect::<Vec<_>>();
+        assert_eq!(locations, vec![(String::from("src/pragma/Legacy.sol"), 2)]);
+    }
+
+    #[test]
+    fn test_outdated_solidity_version() {
+        let detector = OutdatedSolidityVersionDetector::default();
+        let contracts = tests_configuration().get_contracts_for(detector.name());
+
+        for contract_files in contracts {
+            let detector = OutdatedSolidityVersionDetector::default();
+            let context = load_contracts(&contract_files);
+            test_outdated_solidity_version_for(contract_files, context, detector);
+        }
+    }
+}
@@ -1,10 +1,14 @@
 use std::{collections::BTreeMap, error::Error};
 
-use aderyn_driver::context::workspace_context::WorkspaceContext;
+use aderyn_driver::context::workspace_context::{ASTNode, WorkspaceContext};
 use aderyn_driver::core_ast::{NodeID, Visibility};
 use aderyn_driver::detection_modules::capture;
 use aderyn_driver::detector::{IssueDetector, IssueSeverity};
 
+use crate::bot_detector::BotDetector;
+use crate::confidence::Confidence;
+use crate::fixes::{Fix, FixableDetector};
+
 #[derive(Default)]
 pub struct PublicStateVariablesDetector {
     // Keys are source file name and line number
@@ -49,19 +53,53 @@ impl IssueDetector for PublicStateVariablesDetector {
     }
 }
 
+impl BotDetector for PublicStateVariablesDetector {
+    fn confidence(&self) -> Confidence {
+        Confidence::High
+    }
+
+    fn fixable(&self) -> Option<&dyn FixableDetector> {
+        Some(self)
+    }
+}
+
+impl FixableDetector for PublicStateVariablesDetector {
+    // Internal variables have no getter but stay readable by derived contracts.
+    // Getters implementing a function of a base contract or interface must stay, so those are left alone.
+    fn fix(&self, context: &WorkspaceContext, instance: NodeID) -> Option<Fix> {
+        let Some(ASTNode::VariableDeclaration(variable)) = context.nodes.get(&instance) else {
+            return None;
+        };
+        let overrides = variable.overrides.is_some()
+            || variable.base_functions.iter().flatten().next().is_some();
+        if overrides {
+            return None;
+        }
+        Fix::replace_word(
+            context,
+            variable.id,
+            &variable.src,
+            "public",
+            "internal",
+            format!("Make `{}` internal", variable.name),
+        )
+    }
+}
+
 #[cfg(test)]
 mod public_state_variables_tests {
 
+    use crate::bot_utils::load_contracts;
+    use crate::bot_utils::solc::load_solidity;
     use crate::config_tests::tests_configuration;
 
     use super::PublicStateVariablesDetector;
 
     use aderyn_driver::context::workspace_context::WorkspaceContext;
-    use aderyn_driver::detector::detector_test_helpers::load_contract;
     use aderyn_driver::detector::IssueDetector;
 
     fn test_public_state_variables_for(
-        _contract_file: String,
+        _contract_files: Vec<String>,
         context: WorkspaceContext,
         mut detector: impl IssueDetector,
     ) {
@@ -75,10 +113,29 @@ mod public_state_variables_tests {
         let detector = PublicStateVariablesDetector::default();
         let contracts = tests_configuration().get_contracts_for(detector.name());
 
-        for contract_file in contracts {
+        for contract_files in contracts {
             let detector = PublicStateVariablesDetector::default();
-            let context = load_contract(&contract_file);
-            test_public_state_variables_for(contract_file, context, detector);
+            let context = load_contracts(&contract_files);
+            test_public_state_variables_for(contract_files, context, detector);
         }
     }
+
+    #[test]
+    fn test_public_state_variables_inline() {
+        let Some(context) = load_solidity(
+            r#"
+            pragma solidity ^0.8.0;
+            contract Inline {
+                uint256 public exposed;
+                uint256 private hidden;
+            }
+            "#,
+        ) else {
+            return;
+        };
+
+        let mut detector = PublicStateVariablesDetector::default();
+        assert!(detector.detect(&context).unwrap());
+        assert_eq!(detector.instances().len(), 1);
+    }
 }
@@ -0,0 +1,193 @@
+// _   ___   _______ _   _  ________   __  _______   __  ___  ___  _________ _      _____
+// | \ | \ \ / /_   _| | | | | ___ \ \ / / |  ___\ \ / / / _ \ |  \/  || ___ \ |    |  ___|
+// |  \| |\ V /  | | | |_| | | |_/ /\ V /  | |__  \ V / / /_\ \| .  . || |_/ / |    | |__
+// | . ` | \ /   | | |  _  | | ___ \ \ /   |  __| /   \ |  _  || |\/| ||  __/| |    |  __|
+// | |\  | | |   | | | | | | | |_/ / | |   | |___/ /^\ \| | | || |  | || |   | |____| |___
+// \_| \_/ \_/   \_/ \_| |_/ \____/  \_/   \____/\/   \/\_| |_/\_|  |_/\_|   \_____/\____/
+//
+//  _____                    ______     _            _
+// |_   _|                   |  _  \   | |          | |
+//   | | ___ ___ _   _  ___  | | | |___| |_ ___  ___| |_ ___  _ __
+//   | |/ __/ __| | | |/ _ \ | | | / _ \ __/ _ \/ __| __/ _ \| '__|
+//  _| |\__ \__ \ |_| |  __/ | |/ /  __/ ||  __/ (__| || (_) | |
+//  \___/___/___/\__,_|\___| |___/ \___|\__\___|\___|\__\___/|_|
+//
+// This IssueDetector is responsible for finding `pragma solidity` directives allowing compilers that emit `PUSH0`.
+// The `detect` function does this following:
+//
+//   1. Accepts a WorkspaceContext as `context`.
+//   2. Skips the project when its foundry.toml sets an `evm_version` older than Shanghai (see `for_project`).
+//   3. Retrieves the `pragma solidity` directives of every SourceUnit from the shared `SolidityVersions` analysis.
+//   4. Captures the directives whose range accepts 0.8.20 or later, which target Shanghai by default.
+//   5. Returns true if any issues are found, otherwise false.
+//
+
+use std::{collections::BTreeMap, error::Error, path::Path};
+
+use aderyn_driver::context::workspace_context::{ASTNode, WorkspaceContext};
+use aderyn_driver::core_ast::NodeID;
+use aderyn_driver::detection_modules::capture;
+use aderyn_driver::detector::{IssueDetector, IssueSeverity};
+
+use crate::analysis::pragma::SolidityVersion;
+use crate::analysis::Analyses;
+use crate::bot_detector::BotDetector;
+use crate::confidence::Confidence;
+
+// First release targeting Shanghai (and emitting PUSH0) by default
+const SHANGHAI_DEFAULT: SolidityVersion = SolidityVersion::new(0, 8, 20);
+
+// `evm_version`s without `PUSH0`, oldest first
+const PRE_SHANGHAI: &[&str] = &[
+    "homestead",
+    "tangerineWhistle",
+    "spuriousDragon",
+    "byzantium",
+    "constantinople",
+    "petersburg",
+    "istanbul",
+    "berlin",
+    "london",
+    "paris",
+];
+
+#[derive(Default)]
+pub struct Push0IncompatibleVersionDetector {
+    // Keys are source file name and line number
+    found_instances: BTreeMap<(String, usize, String), NodeID>,
+    // `evm_version` of the project, when it overrides the compiler's default
+    evm_version: Option<String>,
+}
+
+impl Push0IncompatibleVersionDetector {
+    // Reads the `evm_version` of the default profile in `<root>/foundry.toml`
+    pub fn for_project(root: &Path) -> Self {
+        let config = std::fs::read_to_string(root.join("foundry.toml")).unwrap_or_default();
+        Self {
+            evm_version: evm_version(&config),
+            ..Default::default()
+        }
+    }
+}
+
+impl IssueDetector for Push0IncompatibleVersionDetector {
+    fn detect(&mut self, context: &WorkspaceContext) -> Result<bool, Box<dyn Error>> {
+        // Every compiler emits the opcodes of the configured EVM, not of its default one
+        if self
+            .evm_version
+            .as_deref()
+            .is_some_and(|evm_version| PRE_SHANGHAI.contains(&evm_version))
+        {
+            return Ok(false);
+        }
+
+        let analyses = Analyses::new(context);
+        let versions = analyses.solidity_versions();
+        for source_unit in context.source_units() {
+            for (pragma, range) in versions.pragmas_of(source_unit.id) {
+                if range.allows_at_least(SHANGHAI_DEFAULT) {
+                    self.capture_pragma(context, *pragma);
+                }
+            }
+        }
+        Ok(!self.found_instances.is_empty())
+    }
+
+    fn title(&self) -> String {
+        String::from("PUSH0 is not supported by all chains")
+    }
+
+    fn description(&self) -> String {
+        String::from("Since 0.8.20 the compiler targets the Shanghai EVM by default, whose `PUSH0` opcode is not available on every chain (some L2s and EVM compatible chains). Deploying there fails. Pick an `evm_version` supported by every target chain, or a compiler older than 0.8.20.")
+    }
+
+    fn severity(&self) -> IssueSeverity {
+        IssueSeverity::Low
+    }
+
+    fn name(&self) -> String {
+        "push0-incompatible-version".to_string()
+    }
+
+    fn instances(&self) -> BTreeMap<(String, usize, String), NodeID> {
+        self.found_instances.clone()
+    }
+}
+
+impl BotDetector for Push0IncompatibleVersionDetector {
+    // The target chain may support PUSH0 by now
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+}
+
+impl Push0IncompatibleVersionDetector {
+    fn capture_pragma(&mut self, context: &WorkspaceContext, pragma: NodeID) {
+        if let Some(ASTNode::PragmaDirective(pragma)) = context.nodes.get(&pragma) {
+            capture!(self, context, pragma);
+        }
+    }
+}
+
+// `[profile.default] evm_version = "paris"` => "paris"
+fn evm_version(config: &str) -> Option<String> {
+    let config = config.parse::<toml::Table>().ok()?;
+    let evm_version = config.get("profile")?.get("default")?.get("evm_version")?;
+    evm_version.as_str().map(String::from)
+}
+
+#[cfg(test)]
+mod push0_incompatible_version_tests {
+
+    use crate::bot_utils::load_contracts;
+    use crate::config_tests::tests_configuration;
+
+    use super::{evm_version, Push0IncompatibleVersionDetector};
+
+    use aderyn_driver::context::workspace_context::WorkspaceContext;
+    use aderyn_driver::detector::IssueDetector;
+
+    fn test_push0_incompatible_version_for(
+        _contract_files: Vec<String>,
+        context: WorkspaceContext,
+        mut detector: impl IssueDetector,
+    ) {
+        // assert that the detector finds the pinned 0.8.24 of Pinned.sol, not the 0.7 range of Legacy.sol
+        let found = detector.detect(&context).unwrap();
+        assert!(found);
+        let locations = detector
+            .instances()
+            .into_keys()
+            .map(|(file, line, _)| (file, line))
+            .collect::<Vec<_>>();
+        assert_eq!(locations, vec![(String::from("src/pragma/Pinned.sol"), 2)]);
+    }
+
+    #[test]
+    fn test_push0_incompatible_version() {
+        let detector = Push0IncompatibleVersionDetector::default();
+        let contracts = tests_configuration().get_contracts_for(detector.name());
+
+        for contract_files in contracts {
+            let detector = Push0IncompatibleVersionDetector::default();
+            let context = load_contracts(&contract_files);
+            test_push0_incompatible_version_for(contract_files, context, detector);
+        }
+    }
+
+    #[test]
+    fn test_push0_with_pre_shanghai_evm_version() {
+        let config = "[profile.default]\nsrc = \"src\"\nevm_version = \"paris\"\n";
+        assert_eq!(evm_version(config).as_deref(), Some("paris"));
+        assert_eq!(evm_version("[profile.default]\nsrc = \"src\"\n"), None);
+
+        // Pinned.sol compiled for Paris emits no PUSH0
+        let context = load_contracts(&["./foundry_workspace/out/Pinned.sol/Pinned.json".into()]);
+        let mut detector = Push0IncompatibleVersionDetector {
+            evm_version: evm_version(config),
+            ..Default::default()
+        };
+        assert!(!detector.detect(&context).unwrap());
+        assert!(detector.instances().is_empty());
+    }
+}
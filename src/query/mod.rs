@@ -0,0 +1,423 @@
+// Structural queries over the AST. A query is a chain of node selectors joined by relations, and
+// matches the nodes selected by its last selector:
+//
+//   FunctionDefinition[visibility=external] >> ForStatement >> FunctionCall[name=transfer] @call
+//
+//   Kind              node kind as in the solc AST (`ForStatement`, `FunctionCall`, ...), `*` for any
+//   [attr=value]      attribute equals value, [attr~regex] attribute matches regex (quote values
+//                     containing spaces or brackets). Attributes: name, visibility, mutability, type,
+//                     state_variable (see `rule_engine::attributes`)
+//   A > B             B is a child of A
+//   A >> B            B is a descendant of A
+//   A ~ B             B is a sibling of A
+//   @name             capture the node matched by the selector
+//
+
+use std::collections::{BTreeMap, HashMap};
+
+use aderyn_driver::context::workspace_context::WorkspaceContext;
+use aderyn_driver::core_ast::{NodeID, NodeType};
+use regex::Regex;
+
+use crate::rule_engine::{attributes, Attributes};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Relation {
+    Child,
+    Descendant,
+    Sibling,
+}
+
+#[derive(Debug)]
+enum Predicate {
+    Equals(String, String),
+    Matches(String, Regex),
+}
+
+#[derive(Debug)]
+struct Selector {
+    // `None` for `*`
+    kind: Option<NodeType>,
+    predicates: Vec<Predicate>,
+    capture: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct Query {
+    // Each selector but the first comes with its relation to the previous one
+    steps: Vec<(Option<Relation>, Selector)>,
+    pub source: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QueryMatch {
+    // Node matched by the last selector
+    pub node: NodeID,
+    pub captures: BTreeMap<String, NodeID>,
+}
+
+impl Query {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut steps = vec![];
+        let mut relation = None;
+        let mut rest = source.trim();
+
+        while !rest.is_empty() {
+            if !steps.is_empty() {
+                let (next_relation, after) = take_relation(rest);
+                relation = Some(next_relation);
+                rest = after.trim_start();
+            }
+            let (selector, after) = take_selector(rest)?;
+            steps.push((relation, selector));
+            rest = after.trim_start();
+        }
+
+        if steps.is_empty() {
+            return Err(String::from("empty query"));
+        }
+        Ok(Self {
+            steps,
+            source: source.trim().to_string(),
+        })
+    }
+
+    // Every match, ordered by node id
+    pub fn evaluate(&self, context: &WorkspaceContext) -> Vec<QueryMatch> {
+        let tree = Tree::build(context);
+        let (_, last) = self.steps.last().unwrap();
+
+        let mut ids = context.nodes.keys().copied().collect::<Vec<_>>();
+        ids.sort();
+
+        let mut matches = vec![];
+        for id in ids {
+            if !selects(context, last, id) {
+                continue;
+            }
+            let mut captures = BTreeMap::new();
+            if self.satisfies(context, &tree, self.steps.len() - 1, id, &mut captures) {
+                matches.push(QueryMatch { node: id, captures });
+            }
+        }
+        matches
+    }
+
+    // Whether `id`, already matched by step `index`, has the relations required by the steps before it
+    fn satisfies(
+        &self,
+        context: &WorkspaceContext,
+        tree: &Tree,
+        index: usize,
+        id: NodeID,
+        captures: &mut BTreeMap<String, NodeID>,
+    ) -> bool {
+        let (relation, selector) = &self.steps[index];
+        if let Some(name) = &selector.capture {
+            captures.insert(name.clone(), id);
+        }
+        let Some(relation) = relation else {
+            return true;
+        };
+
+        let candidates = match relation {
+            Relation::Child => tree.parents.get(&id).copied().into_iter().collect(),
+            Relation::Descendant => tree.ancestors(id),
+            Relation::Sibling => tree.siblings(id),
+        };
+        let (_, previous) = &self.steps[index - 1];
+        for candidate in candidates {
+            if !selects(context, previous, candidate) {
+                continue;
+            }
+            let mut candidate_captures = captures.clone();
+            if self.satisfies(context, tree, index - 1, candidate, &mut candidate_captures) {
+                *captures = candidate_captures;
+                return true;
+            }
+        }
+        false
+    }
+}
+
+// Parent links of the context, computed once per evaluation
+struct Tree {
+    parents: HashMap<NodeID, NodeID>,
+    children: HashMap<NodeID, Vec<NodeID>>,
+}
+
+impl Tree {
+    fn build(context: &WorkspaceContext) -> Self {
+        let mut parents = HashMap::new();
+        let mut children: HashMap<NodeID, Vec<NodeID>> = HashMap::new();
+        for id in context.nodes.keys() {
+            if let Some(parent) = context.get_parent(*id).and_then(|p| p.id()) {
+                parents.insert(*id, parent);
+                children.entry(parent).or_default().push(*id);
+            }
+        }
+        children.values_mut().for_each(|c| c.sort());
+        Self { parents, children }
+    }
+
+    // Closest first
+    fn ancestors(&self, id: NodeID) -> Vec<NodeID> {
+        let mut ancestors = vec![];
+        let mut current = id;
+        while let Some(parent) = self.parents.get(&current) {
+            ancestors.push(*parent);
+            current = *parent;
+        }
+        ancestors
+    }
+
+    fn siblings(&self, id: NodeID) -> Vec<NodeID> {
+        self.parents
+            .get(&id)
+            .and_then(|parent| self.children.get(parent))
+            .map(|children| children.iter().copied().filter(|c| *c != id).collect())
+            .unwrap_or_default()
+    }
+}
+
+fn selects(context: &WorkspaceContext, selector: &Selector, id: NodeID) -> bool {
+    let Some(node) = context.nodes.get(&id) else {
+        return false;
+    };
+    if let Some(kind) = &selector.kind {
+        if node.node_type() != *kind {
+            return false;
+        }
+    }
+    let attributes = attributes(node);
+    selector.predicates.iter().all(|predicate| match predicate {
+        Predicate::Equals(attribute, value) => attributes.get(attribute).as_ref() == Some(value),
+        Predicate::Matches(attribute, regex) => attributes
+            .get(attribute)
+            .is_some_and(|actual| regex.is_match(&actual)),
+    })
+}
+
+fn take_relation(query: &str) -> (Relation, &str) {
+    if let Some(rest) = query.strip_prefix(">>") {
+        (Relation::Descendant, rest)
+    } else if let Some(rest) = query.strip_prefix('>') {
+        (Relation::Child, rest)
+    } else if let Some(rest) = query.strip_prefix('~') {
+        (Relation::Sibling, rest)
+    } else {
+        // Whitespace alone means descendant, as in CSS
+        (Relation::Descendant, query)
+    }
+}
+
+fn take_selector(query: &str) -> Result<(Selector, &str), String> {
+    let end = query
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '*'))
+        .unwrap_or(query.len());
+    let kind = &query[..end];
+    if kind.is_empty() {
+        return Err(format!("expected a node kind at `{}`", query));
+    }
+    let mut rest = &query[end..];
+
+    let mut predicates = vec![];
+    while let Some(after) = rest.strip_prefix('[') {
+        let close = closing_bracket(after).ok_or_else(|| format!("unclosed `[` in `{}`", query))?;
+        predicates.push(parse_predicate(&after[..close])?);
+        rest = &after[close + 1..];
+    }
+
+    let mut capture = None;
+    if let Some(after) = rest.trim_start().strip_prefix('@') {
+        let end = after
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(after.len());
+        if end == 0 {
+            return Err(format!("expected a capture name at `{}`", rest));
+        }
+        capture = Some(after[..end].to_string());
+        rest = &after[end..];
+    }
+
+    let kind = match kind {
+        "*" => None,
+        kind => Some(node_type(kind).ok_or_else(|| format!("unknown node kind `{}`", kind))?),
+    };
+    let selector = Selector {
+        kind,
+        predicates,
+        capture,
+    };
+    Ok((selector, rest))
+}
+
+// Position of the `]` closing a predicate, skipping quoted values
+fn closing_bracket(predicate: &str) -> Option<usize> {
+    let mut quoted = false;
+    for (index, c) in predicate.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ']' if !quoted => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_predicate(predicate: &str) -> Result<Predicate, String> {
+    let operator = predicate
+        .find(['=', '~'])
+        .ok_or_else(|| format!("expected `=` or `~` in `[{}]`", predicate))?;
+    let attribute = predicate[..operator].trim().to_string();
+    if !Attributes::NAMES.contains(&attribute.as_str()) {
+        return Err(format!(
+            "unknown attribute `{}`, expected one of {}",
+            attribute,
+            Attributes::NAMES.join(", ")
+        ));
+    }
+    let value = predicate[operator + 1..].trim();
+    let value = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+        .to_string();
+
+    if predicate[operator..].starts_with('~') {
+        let regex = Regex::new(&value).map_err(|err| err.to_string())?;
+        Ok(Predicate::Matches(attribute, regex))
+    } else {
+        Ok(Predicate::Equals(attribute, value))
+    }
+}
+
+// Kinds of the nodes a WorkspaceContext holds
+fn node_type(kind: &str) -> Option<NodeType> {
+    let node_type = match kind {
+        "ArrayTypeName" => NodeType::ArrayTypeName,
+        "Assignment" => NodeType::Assignment,
+        "BinaryOperation" => NodeType::BinaryOperation,
+        "Block" => NodeType::Block,
+        "Break" => NodeType::Break,
+        "Conditional" => NodeType::Conditional,
+        "Continue" => NodeType::Continue,
+        "ContractDefinition" => NodeType::ContractDefinition,
+        "ElementaryTypeName" => NodeType::ElementaryTypeName,
+        "ElementaryTypeNameExpression" => NodeType::ElementaryTypeNameExpression,
+        "EmitStatement" => NodeType::EmitStatement,
+        "EnumDefinition" => NodeType::EnumDefinition,
+        "EnumValue" => NodeType::EnumValue,
+        "ErrorDefinition" => NodeType::ErrorDefinition,
+        "EventDefinition" => NodeType::EventDefinition,
+        "ExpressionStatement" => NodeType::ExpressionStatement,
+        "ForStatement" => NodeType::ForStatement,
+        "FunctionCall" => NodeType::FunctionCall,
+        "FunctionCallOptions" => NodeType::FunctionCallOptions,
+        "FunctionDefinition" => NodeType::FunctionDefinition,
+        "FunctionTypeName" => NodeType::FunctionTypeName,
+        "Identifier" => NodeType::Identifier,
+        "IdentifierPath" => NodeType::IdentifierPath,
+        "IfStatement" => NodeType::IfStatement,
+        "ImportDirective" => NodeType::ImportDirective,
+        "IndexAccess" => NodeType::IndexAccess,
+        "IndexRangeAccess" => NodeType::IndexRangeAccess,
+        "InheritanceSpecifier" => NodeType::InheritanceSpecifier,
+        "InlineAssembly" => NodeType::InlineAssembly,
+        "Literal" => NodeType::Literal,
+        "Mapping" => NodeType::Mapping,
+        "MemberAccess" => NodeType::MemberAccess,
+        "ModifierDefinition" => NodeType::ModifierDefinition,
+        "ModifierInvocation" => NodeType::ModifierInvocation,
+        "NewExpression" => NodeType::NewExpression,
+        "OverrideSpecifier" => NodeType::OverrideSpecifier,
+        "ParameterList" => NodeType::ParameterList,
+        "PlaceholderStatement" => NodeType::PlaceholderStatement,
+        "PragmaDirective" => NodeType::PragmaDirective,
+        "Return" => NodeType::Return,
+        "RevertStatement" => NodeType::RevertStatement,
+        "SourceUnit" => NodeType::SourceUnit,
+        "StructDefinition" => NodeType::StructDefinition,
+        "StructuredDocumentation" => NodeType::StructuredDocumentation,
+        "TryCatchClause" => NodeType::TryCatchClause,
+        "TryStatement" => NodeType::TryStatement,
+        "TupleExpression" => NodeType::TupleExpression,
+        "UnaryOperation" => NodeType::UnaryOperation,
+        "UserDefinedTypeName" => NodeType::UserDefinedTypeName,
+        "UserDefinedValueTypeDefinition" => NodeType::UserDefinedValueTypeDefinition,
+        "UsingForDirective" => NodeType::UsingForDirective,
+        "VariableDeclaration" => NodeType::VariableDeclaration,
+        "VariableDeclarationStatement" => NodeType::VariableDeclarationStatement,
+        "WhileStatement" => NodeType::WhileStatement,
+        _ => return None,
+    };
+    Some(node_type)
+}
+
+#[cfg(test)]
+mod query_tests {
+
+    use crate::bot_utils::load_contracts;
+
+    use super::Query;
+
+    use aderyn_driver::context::workspace_context::ASTNode;
+
+    const QUERY: &str = "./foundry_workspace/out/Query.sol/Payouts.json";
+
+    #[test]
+    fn test_descendant_queries() {
+        let context = load_contracts(&[QUERY.into()]);
+
+        let in_loops =
+            Query::parse("FunctionDefinition >> ForStatement >> FunctionCall[name=transfer]")
+                .unwrap();
+        assert_eq!(in_loops.evaluate(&context).len(), 2);
+
+        let external = Query::parse(
+            "FunctionDefinition[visibility=external] @function >> ForStatement >> FunctionCall[name=transfer] @call",
+        )
+        .unwrap();
+        let matches = external.evaluate(&context);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].captures["call"], matches[0].node);
+        let Some(ASTNode::FunctionDefinition(function)) =
+            context.nodes.get(&matches[0].captures["function"])
+        else {
+            panic!("`function` should capture a FunctionDefinition");
+        };
+        assert_eq!(function.name, "payAll");
+    }
+
+    #[test]
+    fn test_child_and_sibling_queries() {
+        let context = load_contracts(&[QUERY.into()]);
+
+        // `token.transfer(..)` sits in an ExpressionStatement, never directly in the loop
+        let direct = Query::parse("ForStatement > FunctionCall[name=transfer]").unwrap();
+        assert!(direct.evaluate(&context).is_empty());
+        let statements = Query::parse("ExpressionStatement > FunctionCall[name=transfer]").unwrap();
+        assert_eq!(statements.evaluate(&context).len(), 3);
+
+        let siblings =
+            Query::parse(r#"VariableDeclaration[name=token] ~ VariableDeclaration[type~"\[\]"]"#)
+                .unwrap();
+        assert_eq!(siblings.evaluate(&context).len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_queries() {
+        assert!(Query::parse("").is_err());
+        assert!(Query::parse("FunctionCall[name=transfer").is_err());
+        assert!(Query::parse("FunctionCall >> [name=x]").is_err());
+
+        // Misspelled kinds and attributes would silently never match
+        assert!(Query::parse("ForStatment >> FunctionCall")
+            .unwrap_err()
+            .contains("unknown node kind `ForStatment`"));
+        assert!(Query::parse("FunctionCall[nmae=transfer]")
+            .unwrap_err()
+            .contains("unknown attribute `nmae`"));
+        assert!(Query::parse("* > FunctionCall[type~^uint]").is_ok());
+    }
+}
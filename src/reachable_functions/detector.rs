@@ -0,0 +1,155 @@
+// _   ___   _______ _   _  ________   __  _______   __  ___  ___  _________ _      _____
+// | \ | \ \ / /_   _| | | | | ___ \ \ / / |  ___\ \ / / / _ \ |  \/  || ___ \ |    |  ___|
+// |  \| |\ V /  | | | |_| | | |_/ /\ V /  | |__  \ V / / /_\ \| .  . || |_/ / |    | |__
+// | . ` | \ /   | | |  _  | | ___ \ \ /   |  __| /   \ |  _  || |\/| ||  __/| |    |  __|
+// | |\  | | |   | | | | | | | |_/ / | |   | |___/ /^\ \| | | || |  | || |   | |____| |___
+// \_| \_/ \_/   \_/ \_| |_/ \____/  \_/   \____/\/   \/\_| |_/\_|  |_/\_|   \_____/\____/
+// ______                     _     _       ______     _            _
+// | ___ \                   | |   | |      |  _  \   | |          | |
+// | |_/ /___ _   _ ___  __ _| |__ | | ___  | | | |___| |_ ___  ___| |_ ___  _ __
+// |    // _ \ | | / __|/ _` | '_ \| |/ _ \ | | | / _ \ __/ _ \/ __| __/ _ \| '__|
+// | |\ \  __/ |_| \__ \ (_| | |_) | |  __/ | |/ /  __/ ||  __/ (__| || (_) | |
+// \_| \_\___|\__,_|___/\__,_|_.__/|_|\___| |___/ \___|\__\___|\___|\__\___/|_|
+//
+// This ReusableDetector is responsible for finding every function and modifier reachable from entry points.
+// The `detect` function does this following:
+//
+//   1. Accept FunctionDefinition / ModifierDefinition nodes (the entry points) via the `within` parameter.
+//   2. Walk the shared CallGraph from them, following internal, modifier, library and `super` calls
+//      (and external calls too when built with `following_external_calls`).
+//   3. Add every reached FunctionDefinition / ModifierDefinition (entries included) to the `found_instances` vector.
+//   4. Return the `found_instances` vector.
+//
+
+use aderyn_driver::context::workspace_context::ASTNode;
+use aderyn_driver::context::workspace_context::WorkspaceContext;
+use aderyn_driver::detector::ReusableDetector;
+
+use crate::analysis::Analyses;
+
+#[derive(Default)]
+pub struct ReachableFunctionsDetector {
+    found_instances: Vec<ASTNode>,
+    follow_external: bool,
+}
+
+impl ReachableFunctionsDetector {
+    // Also walk into functions called through external calls (`other.f()`, `this.f()`)
+    pub fn following_external_calls() -> Self {
+        Self {
+            follow_external: true,
+            ..Default::default()
+        }
+    }
+
+    // Same as `detect`, reusing the call graph of `analyses`
+    pub fn detect_with(
+        &mut self,
+        analyses: &Analyses,
+        _: &[ASTNode],
+        within: &[ASTNode],
+    ) -> Result<&[ASTNode], Box<dyn std::error::Error>> {
+        let context = analyses.context();
+        let entries = within
+            .iter()
+            .filter_map(|node| match node {
+                ASTNode::FunctionDefinition(function) => Some(function.id),
+                ASTNode::ModifierDefinition(modifier) => Some(modifier.id),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let reachable = analyses
+            .call_graph()
+            .reachable_from(&entries, self.follow_external);
+        for id in reachable {
+            if let Some(node) = context.nodes.get(&id) {
+                self.found_instances.push(node.clone());
+            }
+        }
+
+        Ok(&self.found_instances)
+    }
+}
+
+impl ReusableDetector for ReachableFunctionsDetector {
+    fn detect(
+        &mut self,
+        context: &WorkspaceContext,
+        using: &[ASTNode],
+        within: &[ASTNode],
+    ) -> Result<&[ASTNode], Box<dyn std::error::Error>> {
+        self.detect_with(&Analyses::new(context), using, within)
+    }
+
+    fn name(&self) -> String {
+        "reachable-functions".to_string()
+    }
+}
+
+#[cfg(test)]
+mod reachable_functions_tests {
+
+    use crate::bot_utils::{load_contracts, node_name, NodeSelector};
+    use crate::config_tests::tests_configuration;
+
+    use super::ReachableFunctionsDetector;
+
+    use aderyn_driver::detector::ReusableDetector;
+
+    #[test]
+    fn test_reachable_functions() {
+        let detector = ReachableFunctionsDetector::default();
+        let cases = tests_configuration().get_reusable_cases_for(detector.name());
+
+        for case in cases {
+            let mut detector = ReachableFunctionsDetector::default();
+            case.run(&mut detector);
+        }
+    }
+
+    #[test]
+    fn test_reachable_functions_following_external_calls() {
+        let context = load_contracts(&["./foundry_workspace/out/CallGraph.sol/Caller.json".into()]);
+        let within = NodeSelector::function("forward").select(&context);
+
+        let mut detector = ReachableFunctionsDetector::default();
+        let internal_only = detector.detect(&context, &[], &within).unwrap().len();
+        assert_eq!(internal_only, 1);
+
+        let mut detector = ReachableFunctionsDetector::following_external_calls();
+        let mut reached = detector
+            .detect(&context, &[], &within)
+            .unwrap()
+            .iter()
+            .filter_map(node_name)
+            .collect::<Vec<_>>();
+        reached.sort();
+        assert_eq!(
+            reached,
+            vec!["_check", "_store", "_store", "checked", "double", "entry", "forward"]
+        );
+    }
+
+    #[test]
+    fn test_reachable_functions_through_overrides() {
+        let context =
+            load_contracts(&["./foundry_workspace/out/Dispatch.sol/GuardedVault.json".into()]);
+        let within = NodeSelector::function("deposit").select(&context);
+
+        let mut detector = ReachableFunctionsDetector::default();
+        let mut reached = detector
+            .detect(&context, &[], &within)
+            .unwrap()
+            .iter()
+            .filter_map(node_name)
+            .collect::<Vec<_>>();
+        reached.sort();
+
+        // Both `_beforeDeposit` implementations, and what the override calls
+        assert_eq!(
+            reached,
+            vec!["_beforeDeposit", "_beforeDeposit", "_checkOwner", "deposit"]
+        );
+    }
+}
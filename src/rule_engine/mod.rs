@@ -0,0 +1,573 @@
+// Declarative detectors: every `rules/*.toml` file becomes an IssueDetector capturing the nodes of
+// one kind whose attributes match all of the given predicates. Example:
+//
+//   name = "mutable-uppercase-state-variable"
+//   title = "Mutable state variable named like a constant"
+//   description = "..."
+//   severity = "NC"                  # Critical, High, Medium, Low or NC
+//   confidence = "High"              # High, Medium or Low, Medium when missing
+//
+//   [match]
+//   kind = "VariableDeclaration"
+//   state_variable = true
+//   mutability = ["mutable"]         # any of
+//   visibility = ["public", "internal"]
+//   name = "^[A-Z][A-Z0-9_]*$"       # regex
+//   type = "^uint"                   # regex on the type string
+//
+// Instead of `[match]`, a rule can hold a structural `query` (see `crate::query`), for example
+// `query = "FunctionDefinition[visibility=external] >> ForStatement >> FunctionCall[name=transfer]"`.
+//
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::path::Path;
+
+use aderyn_driver::context::workspace_context::{ASTNode, WorkspaceContext};
+use aderyn_driver::core_ast::{Expression, NodeID, NodeType};
+use aderyn_driver::detection_modules::capture;
+use aderyn_driver::detector::{IssueDetector, IssueSeverity};
+use regex::Regex;
+
+use crate::bot_detector::BotDetector;
+use crate::cache::{fingerprint, UnitScopedDetector};
+use crate::confidence::Confidence;
+use crate::query::Query;
+use serde::{Deserialize, Serialize};
+
+// Folder the runner and the metadata load rules from, next to Cargo.toml whatever the working directory
+pub const RULES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/rules");
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct RuleFile {
+    pub(crate) name: String,
+    pub(crate) title: String,
+    pub(crate) description: String,
+    pub(crate) severity: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) confidence: Option<String>,
+    #[serde(rename = "match", skip_serializing_if = "Option::is_none")]
+    pub(crate) pattern: Option<PatternFile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) query: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct PatternFile {
+    kind: String,
+    visibility: Option<Vec<String>>,
+    mutability: Option<Vec<String>>,
+    name: Option<String>,
+    #[serde(rename = "type")]
+    type_string: Option<String>,
+    state_variable: Option<bool>,
+}
+
+#[derive(Debug)]
+pub struct Rule {
+    pub name: String,
+    pub title: String,
+    pub description: String,
+    pub severity: IssueSeverity,
+    pub confidence: Confidence,
+    // Hash of the rule file, changes whenever the rule does
+    pub fingerprint: u64,
+    matcher: Matcher,
+}
+
+#[derive(Debug)]
+enum Matcher {
+    Pattern(Pattern),
+    Query(Query),
+}
+
+#[derive(Debug)]
+struct Pattern {
+    kind: NodeType,
+    visibility: Option<Vec<String>>,
+    mutability: Option<Vec<String>>,
+    name_pattern: Option<Regex>,
+    type_pattern: Option<Regex>,
+    state_variable: Option<bool>,
+}
+
+// Attributes of a node that rules and queries can match on, `None` when the kind has no such attribute
+#[derive(Default)]
+pub(crate) struct Attributes<'a> {
+    pub(crate) name: Option<&'a str>,
+    pub(crate) visibility: Option<String>,
+    pub(crate) mutability: Option<String>,
+    pub(crate) type_string: Option<&'a str>,
+    pub(crate) state_variable: Option<bool>,
+}
+
+impl Attributes<'_> {
+    // Every attribute `get` knows
+    pub(crate) const NAMES: &'static [&'static str] =
+        &["name", "visibility", "mutability", "type", "state_variable"];
+
+    // Value of `attribute` (one of `NAMES`) as text
+    pub(crate) fn get(&self, attribute: &str) -> Option<String> {
+        match attribute {
+            "name" => self.name.map(|n| n.to_string()),
+            "visibility" => self.visibility.clone(),
+            "mutability" => self.mutability.clone(),
+            "type" => self.type_string.map(|t| t.to_string()),
+            "state_variable" => self.state_variable.map(|s| s.to_string()),
+            _ => None,
+        }
+    }
+}
+
+impl Rule {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let file: RuleFile = toml::from_str(source).map_err(|err| err.to_string())?;
+        let severity = severity(&file.severity)
+            .ok_or_else(|| format!("unknown severity `{}`", file.severity))?;
+        let confidence = match &file.confidence {
+            Some(confidence) => confidence.parse()?,
+            None => Confidence::default(),
+        };
+
+        let matcher = match (file.pattern, file.query) {
+            (Some(pattern), None) => Matcher::Pattern(Pattern::parse(pattern)?),
+            (None, Some(query)) => Matcher::Query(Query::parse(&query)?),
+            _ => {
+                return Err(String::from(
+                    "expected either a `[match]` table or a `query`",
+                ))
+            }
+        };
+        Ok(Self {
+            name: file.name,
+            title: file.title,
+            description: file.description,
+            severity,
+            confidence,
+            fingerprint: fingerprint(source.as_bytes()),
+            matcher,
+        })
+    }
+
+    // Nodes of `context` matched by the rule
+    pub fn find(&self, context: &WorkspaceContext) -> Vec<NodeID> {
+        match &self.matcher {
+            Matcher::Pattern(pattern) => {
+                let mut ids = context
+                    .nodes
+                    .iter()
+                    .filter(|(_, node)| pattern.matches(node))
+                    .map(|(id, _)| *id)
+                    .collect::<Vec<_>>();
+                ids.sort();
+                ids
+            }
+            Matcher::Query(query) => query
+                .evaluate(context)
+                .into_iter()
+                .map(|m| m.node)
+                .collect(),
+        }
+    }
+}
+
+impl Pattern {
+    fn parse(pattern: PatternFile) -> Result<Self, String> {
+        let kind = node_type(&pattern.kind)
+            .ok_or_else(|| format!("unsupported node kind `{}`", pattern.kind))?;
+        let regex = |pattern: Option<String>| {
+            pattern
+                .map(|p| Regex::new(&p).map_err(|err| err.to_string()))
+                .transpose()
+        };
+        let lowercase = |values: Option<Vec<String>>| {
+            values.map(|values| values.iter().map(|v| v.to_lowercase()).collect())
+        };
+
+        let parsed = Self {
+            kind,
+            visibility: lowercase(pattern.visibility),
+            mutability: lowercase(pattern.mutability),
+            name_pattern: regex(pattern.name)?,
+            type_pattern: regex(pattern.type_string)?,
+            state_variable: pattern.state_variable,
+        };
+
+        // Reject predicates the kind cannot satisfy rather than silently never matching
+        let supported = supported_attributes(&parsed.kind);
+        let used = [
+            ("visibility", parsed.visibility.is_some()),
+            ("mutability", parsed.mutability.is_some()),
+            ("type", parsed.type_pattern.is_some()),
+            ("state_variable", parsed.state_variable.is_some()),
+        ];
+        for (attribute, is_used) in used {
+            if is_used && !supported.contains(&attribute) {
+                return Err(format!(
+                    "`{}` does not apply to {}",
+                    attribute, pattern.kind
+                ));
+            }
+        }
+        Ok(parsed)
+    }
+
+    fn matches(&self, node: &ASTNode) -> bool {
+        if node.node_type() != self.kind {
+            return false;
+        }
+        let attributes = attributes(node);
+
+        let any_of = |expected: &Option<Vec<String>>, actual: &Option<String>| {
+            expected
+                .as_ref()
+                .is_none_or(|expected| actual.as_ref().is_some_and(|a| expected.contains(a)))
+        };
+        let matches_regex = |regex: &Option<Regex>, actual: Option<&str>| {
+            regex
+                .as_ref()
+                .is_none_or(|regex| actual.is_some_and(|a| regex.is_match(a)))
+        };
+
+        any_of(&self.visibility, &attributes.visibility)
+            && any_of(&self.mutability, &attributes.mutability)
+            && matches_regex(&self.name_pattern, attributes.name)
+            && matches_regex(&self.type_pattern, attributes.type_string)
+            && self
+                .state_variable
+                .is_none_or(|expected| attributes.state_variable == Some(expected))
+    }
+}
+
+// Every `*.toml` rule of `directory`, sorted by file name, or why the file is not a valid rule.
+// A missing directory has no rules.
+pub fn load_rules(directory: &Path) -> Vec<Result<Rule, String>> {
+    let Ok(entries) = std::fs::read_dir(directory) else {
+        return vec![];
+    };
+    let mut paths = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            std::fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|source| Rule::parse(&source))
+                .map_err(|err| format!("Invalid rule {}: {}", path.display(), err))
+        })
+        .collect()
+}
+
+// IssueDetectors for the rules of `directory`, to subscribe next to the Rust detectors.
+// Invalid rule files are reported and skipped.
+pub fn rule_detectors(directory: &str) -> Vec<Box<dyn BotDetector>> {
+    let mut detectors: Vec<Box<dyn BotDetector>> = vec![];
+    for rule in load_rules(Path::new(directory)) {
+        match rule {
+            Ok(rule) => detectors.push(Box::new(RuleDetector::new(rule))),
+            Err(err) => eprintln!("[!] {}, skipping it", err),
+        }
+    }
+    detectors
+}
+
+pub struct RuleDetector {
+    rule: Rule,
+    // Keys are source file name and line number
+    found_instances: BTreeMap<(String, usize, String), NodeID>,
+}
+
+impl RuleDetector {
+    pub fn new(rule: Rule) -> Self {
+        Self {
+            rule,
+            found_instances: BTreeMap::new(),
+        }
+    }
+}
+
+impl IssueDetector for RuleDetector {
+    fn detect(&mut self, context: &WorkspaceContext) -> Result<bool, Box<dyn Error>> {
+        for id in self.rule.find(context) {
+            if let Some(node) = context.nodes.get(&id) {
+                capture!(self, context, node.clone());
+            }
+        }
+        Ok(!self.found_instances.is_empty())
+    }
+
+    fn title(&self) -> String {
+        self.rule.title.clone()
+    }
+
+    fn description(&self) -> String {
+        self.rule.description.clone()
+    }
+
+    fn severity(&self) -> IssueSeverity {
+        self.rule.severity.clone()
+    }
+
+    fn name(&self) -> String {
+        self.rule.name.clone()
+    }
+
+    fn instances(&self) -> BTreeMap<(String, usize, String), NodeID> {
+        self.found_instances.clone()
+    }
+}
+
+impl BotDetector for RuleDetector {
+    fn confidence(&self) -> Confidence {
+        self.rule.confidence
+    }
+
+    fn unit_scoped(&mut self) -> Option<&mut dyn UnitScopedDetector> {
+        Some(self)
+    }
+}
+
+// Patterns look at one node, queries at its ancestors, descendants and siblings: all in its source unit
+impl UnitScopedDetector for RuleDetector {
+    fn detect_units(
+        &mut self,
+        context: &WorkspaceContext,
+        units: &BTreeSet<String>,
+    ) -> Result<bool, Box<dyn Error>> {
+        self.detect(context)?;
+        self.found_instances
+            .retain(|(file, _, _), _| units.contains(file));
+        Ok(!self.found_instances.is_empty())
+    }
+}
+
+fn node_type(kind: &str) -> Option<NodeType> {
+    match kind {
+        "ContractDefinition" => Some(NodeType::ContractDefinition),
+        "FunctionDefinition" => Some(NodeType::FunctionDefinition),
+        "ModifierDefinition" => Some(NodeType::ModifierDefinition),
+        "VariableDeclaration" => Some(NodeType::VariableDeclaration),
+        "EventDefinition" => Some(NodeType::EventDefinition),
+        "ErrorDefinition" => Some(NodeType::ErrorDefinition),
+        "StructDefinition" => Some(NodeType::StructDefinition),
+        "Identifier" => Some(NodeType::Identifier),
+        "MemberAccess" => Some(NodeType::MemberAccess),
+        _ => None,
+    }
+}
+
+fn supported_attributes(kind: &NodeType) -> &'static [&'static str] {
+    match kind {
+        NodeType::FunctionDefinition => &["visibility", "mutability"],
+        NodeType::ModifierDefinition => &["visibility"],
+        NodeType::VariableDeclaration => &["visibility", "mutability", "type", "state_variable"],
+        NodeType::Identifier | NodeType::MemberAccess => &["type"],
+        _ => &[],
+    }
+}
+
+fn severity(severity: &str) -> Option<IssueSeverity> {
+    match severity.to_lowercase().as_str() {
+        "critical" => Some(IssueSeverity::Critical),
+        "high" => Some(IssueSeverity::High),
+        "medium" => Some(IssueSeverity::Medium),
+        "low" => Some(IssueSeverity::Low),
+        "nc" => Some(IssueSeverity::NC),
+        _ => None,
+    }
+}
+
+// Enum values are compared by their lowercase name (`public`, `nonpayable`, `immutable`, ...)
+fn lowercase_name(value: &impl std::fmt::Debug) -> String {
+    format!("{:?}", value).to_lowercase()
+}
+
+pub(crate) fn attributes(node: &ASTNode) -> Attributes<'_> {
+    match node {
+        ASTNode::ContractDefinition(n) => Attributes {
+            name: Some(&n.name),
+            ..Default::default()
+        },
+        ASTNode::FunctionDefinition(n) => Attributes {
+            name: Some(&n.name),
+            visibility: Some(lowercase_name(&n.visibility)),
+            mutability: Some(lowercase_name(&n.state_mutability)),
+            ..Default::default()
+        },
+        ASTNode::ModifierDefinition(n) => Attributes {
+            name: Some(&n.name),
+            visibility: Some(lowercase_name(&n.visibility)),
+            ..Default::default()
+        },
+        ASTNode::VariableDeclaration(n) => Attributes {
+            name: Some(&n.name),
+            visibility: Some(lowercase_name(&n.visibility)),
+            mutability: n.mutability.as_ref().map(lowercase_name),
+            type_string: n.type_descriptions.type_string.as_deref(),
+            state_variable: Some(n.state_variable),
+        },
+        ASTNode::EventDefinition(n) => Attributes {
+            name: Some(&n.name),
+            ..Default::default()
+        },
+        ASTNode::ErrorDefinition(n) => Attributes {
+            name: Some(&n.name),
+            ..Default::default()
+        },
+        ASTNode::StructDefinition(n) => Attributes {
+            name: Some(&n.name),
+            ..Default::default()
+        },
+        ASTNode::Identifier(n) => Attributes {
+            name: Some(&n.name),
+            type_string: n.type_descriptions.type_string.as_deref(),
+            ..Default::default()
+        },
+        ASTNode::MemberAccess(n) => Attributes {
+            name: Some(&n.member_name),
+            type_string: n.type_descriptions.type_string.as_deref(),
+            ..Default::default()
+        },
+        // Named after the function called (`transfer` for `token.transfer(..)`)
+        ASTNode::FunctionCall(n) => Attributes {
+            name: match n.expression.as_ref() {
+                Expression::Identifier(callee) => Some(&callee.name),
+                Expression::MemberAccess(callee) => Some(&callee.member_name),
+                _ => None,
+            },
+            type_string: n.type_descriptions.type_string.as_deref(),
+            ..Default::default()
+        },
+        _ => Attributes::default(),
+    }
+}
+
+#[cfg(test)]
+mod rule_engine_tests {
+
+    use std::path::Path;
+
+    use crate::bot_utils::load_contracts;
+
+    use crate::confidence::Confidence;
+
+    use super::{attributes, load_rules, rule_detectors, Rule, RuleDetector, RULES_DIR};
+
+    use aderyn_driver::detector::IssueDetector;
+
+    const UNUSED_PREFIX: &str = r#"
+        name = "unused-prefix"
+        title = "State variable named unused"
+        description = "Its name says it all."
+        severity = "Low"
+
+        [match]
+        kind = "VariableDeclaration"
+        state_variable = true
+        visibility = ["public"]
+        name = "^unused"
+        type = "^uint256$"
+    "#;
+
+    #[test]
+    fn test_rule_detector() {
+        let context = load_contracts(&["./foundry_workspace/out/Counter.sol/Counter.json".into()]);
+
+        let mut detector = RuleDetector::new(Rule::parse(UNUSED_PREFIX).unwrap());
+        let found = detector.detect(&context).unwrap();
+        assert!(found);
+        // `unused_number`, not `number`
+        assert!(detector.instances().len() == 1);
+        assert_eq!(detector.name(), "unused-prefix");
+        assert_eq!(
+            Rule::parse(UNUSED_PREFIX).unwrap().confidence,
+            Confidence::Medium
+        );
+    }
+
+    #[test]
+    fn test_invalid_rules_are_rejected() {
+        let not_applicable = UNUSED_PREFIX.replace("VariableDeclaration", "ContractDefinition");
+        assert!(Rule::parse(&not_applicable)
+            .unwrap_err()
+            .contains("does not apply"));
+
+        let bad_severity = UNUSED_PREFIX.replace("\"Low\"", "\"Urgent\"");
+        assert!(Rule::parse(&bad_severity).is_err());
+
+        let bad_confidence = UNUSED_PREFIX.replace(
+            "severity = \"Low\"",
+            "severity = \"Low\"\nconfidence = \"Certain\"",
+        );
+        assert!(Rule::parse(&bad_confidence).is_err());
+    }
+
+    #[test]
+    fn test_query_rule() {
+        let context = load_contracts(&["./foundry_workspace/out/Query.sol/Payouts.json".into()]);
+        let rule = Rule::parse(
+            r#"
+            name = "transfer-in-loop"
+            title = "Token transfer inside a loop"
+            description = "One failing transfer reverts the whole batch."
+            severity = "Medium"
+            query = "FunctionDefinition[visibility=external] >> ForStatement >> FunctionCall[name=transfer]"
+        "#,
+        )
+        .unwrap();
+
+        let mut detector = RuleDetector::new(rule);
+        assert!(detector.detect(&context).unwrap());
+        assert!(detector.instances().len() == 1);
+    }
+
+    // Every rule of `rules/` with the nodes it must find in Rules.sol
+    const SHIPPED_RULES: &[(&str, &[&str])] = &[
+        ("mutable-uppercase-state-variable", &["MAX_SUPPLY"]),
+        ("underscored-external-function", &["_mint"]),
+    ];
+
+    #[test]
+    fn test_shipped_rules() {
+        let context = load_contracts(&["./foundry_workspace/out/Rules.sol/Rules.json".into()]);
+
+        let rules = load_rules(Path::new(RULES_DIR));
+        assert_eq!(rules.len(), SHIPPED_RULES.len());
+        for rule in rules {
+            let rule = rule.unwrap();
+            let Some((_, expected)) = SHIPPED_RULES.iter().find(|(name, _)| *name == rule.name)
+            else {
+                panic!("{} has no fixture in SHIPPED_RULES", rule.name);
+            };
+            let found = rule
+                .find(&context)
+                .iter()
+                .filter_map(|id| context.nodes.get(id))
+                .filter_map(|node| attributes(node).name.map(String::from))
+                .collect::<Vec<_>>();
+            assert_eq!(&found, expected, "{}", rule.name);
+        }
+    }
+
+    #[test]
+    fn test_invalid_rule_files_are_skipped() {
+        let directory = std::env::temp_dir().join(format!("rules-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("a-valid.toml"), UNUSED_PREFIX).unwrap();
+        std::fs::write(directory.join("b-invalid.toml"), "name = ").unwrap();
+
+        let rules = load_rules(&directory);
+        assert!(rules[0].is_ok());
+        assert!(rules[1].as_ref().unwrap_err().contains("b-invalid.toml"));
+        assert_eq!(rule_detectors(directory.to_str().unwrap()).len(), 1);
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
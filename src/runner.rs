@@ -1,28 +1,116 @@
-use aderyn_driver::detector::IssueDetector;
+use std::path::Path;
+use std::time::Duration;
+
 use aderyn_driver::driver::{drive_with, Args};
 
+use crate::arbitrary_transfer_from::detector::ArbitraryTransferFromDetector;
+use crate::bot_detector::BotDetector;
+use crate::bot_utils::load_project;
+use crate::cache::{self, CACHE_DIR};
+use crate::confidence::{filter_by_confidence, Confidence};
+use crate::fixes::{self, collect_fixes, SharedFixes};
+use crate::floating_pragma::detector::FloatingPragmaDetector;
+use crate::inefficient_storage_packing::detector::InefficientStoragePackingDetector;
+use crate::outdated_solidity_version::detector::OutdatedSolidityVersionDetector;
 use crate::public_state_variables::detector::PublicStateVariablesDetector;
+use crate::push0_incompatible_version::detector::Push0IncompatibleVersionDetector;
+use crate::rule_engine::{rule_detectors, RULES_DIR};
 use crate::state_variable_is_never_used::detector::StateVariableIsNeverUsedDetector;
+use crate::state_variable_is_never_written::detector::StateVariableIsNeverWrittenDetector;
+use crate::state_variable_is_write_only::detector::StateVariableIsWriteOnlyDetector;
+use crate::upgradeable_storage_collision::detector::UpgradeableStorageCollisionDetector;
+use crate::watch;
+
+const ROOT: &str = "./foundry_workspace";
+const OUTPUT: &str = "report.md";
+
+// `cargo run` will run this function, `--min-confidence` drops the less certain findings.
+// Detectors whose inputs did not change since the last run reuse their instances from `.nyth-cache`,
+// unless `--no-cache` is given.
+pub fn run(minimum_confidence: Confidence, no_cache: bool) {
+    let fixes = SharedFixes::default();
+    drive_with(
+        args(),
+        collect_fixes(reported(minimum_confidence, no_cache), &fixes),
+    );
+
+    let section = fixes::report_section(&fixes.lock().unwrap());
+    if !section.is_empty() {
+        let report = std::fs::read_to_string(OUTPUT).unwrap_or_default();
+        std::fs::write(OUTPUT, report + &section).unwrap();
+    }
+}
+
+// `cargo run -- --watch` will run this function: it reruns the subscriptions whenever the sources,
+// the artifacts or the rules change, and prints the new and resolved findings
+pub fn watch(minimum_confidence: Confidence) {
+    let rules = Path::new(RULES_DIR);
+    watch::watch(Path::new(ROOT), &[rules], Duration::from_secs(1), || {
+        let context = load_project(ROOT);
+        watch::findings(
+            &context,
+            filter_by_confidence(subscriptions(), minimum_confidence),
+        )
+    })
+}
 
-// `cargo run` will run this function
-pub fn run() {
+// `cargo run -- clear-cache [detector]` will run this function
+pub fn clear_cache(detector: Option<&str>) {
+    cache::clear(Path::new(CACHE_DIR), detector)
+}
+
+fn subscriptions() -> Vec<Box<dyn BotDetector>> {
     // Subscribe the detectors to the driver.
     // This will run the detectors and generate a report on `cargo run`.
     // Only detectors that are subscribed here will be run.
-    let subscriptions: Vec<Box<dyn IssueDetector>> = vec![
+    let root = Path::new(ROOT);
+    let mut subscriptions: Vec<Box<dyn BotDetector>> = vec![
         Box::<StateVariableIsNeverUsedDetector>::default(),
         Box::<PublicStateVariablesDetector>::default(),
+        Box::<StateVariableIsNeverWrittenDetector>::default(),
+        Box::<StateVariableIsWriteOnlyDetector>::default(),
+        Box::<ArbitraryTransferFromDetector>::default(),
+        Box::<InefficientStoragePackingDetector>::default(),
+        Box::<UpgradeableStorageCollisionDetector>::default(),
+        Box::<FloatingPragmaDetector>::default(),
+        Box::<OutdatedSolidityVersionDetector>::default(),
+        Box::new(Push0IncompatibleVersionDetector::for_project(root)),
     ];
+    // Declarative detectors from `rules/*.toml`
+    subscriptions.extend(rule_detectors(RULES_DIR));
+    subscriptions
+}
 
-    drive_with(
-        Args {
-            root: "./foundry_workspace".to_string(),
-            output: "report.md".to_string(),
-            exclude: None,
-            scope: None,
-            no_snippets: false,
-            stdout: false,
-        },
-        subscriptions,
-    )
+// The subscriptions as `cargo run` reports them: reusing the instances saved in `.nyth-cache` unless
+// `no_cache`, and keeping those of at least `minimum_confidence`
+fn reported(minimum_confidence: Confidence, no_cache: bool) -> Vec<Box<dyn BotDetector>> {
+    let mut subscriptions = subscriptions();
+    if !no_cache {
+        subscriptions = cache::with_cache(subscriptions, ROOT);
+    }
+    filter_by_confidence(subscriptions, minimum_confidence)
+}
+
+// `cargo run -- --apply-fixes [--dry-run]` will run this function, it applies the fixes of the findings
+// `cargo run` would report with the same `--min-confidence` and `--no-cache`
+pub fn apply_fixes(minimum_confidence: Confidence, no_cache: bool, dry_run: bool) {
+    let context = load_project(ROOT);
+    let fixes = SharedFixes::default();
+    for mut detector in collect_fixes(reported(minimum_confidence, no_cache), &fixes) {
+        if let Err(err) = detector.detect(&context) {
+            eprintln!("[!] {} failed: {}", detector.name(), err);
+        }
+    }
+    fixes::apply(&std::mem::take(&mut *fixes.lock().unwrap()).all(), Path::new(ROOT), dry_run);
+}
+
+fn args() -> Args {
+    Args {
+        root: ROOT.to_string(),
+        output: OUTPUT.to_string(),
+        exclude: None,
+        scope: None,
+        no_snippets: false,
+        stdout: false,
+    }
 }
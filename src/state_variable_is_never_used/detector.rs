@@ -17,20 +17,32 @@
 //
 //   1. Accepts a WorkspaceContext as `context`.
 //   2. Retrieves all ContractDefinition nodes from the `context`.
-//   3. For each ContractDefinition, retrieves all state variables, by using the ReusableDetector: `StateVariablesInContractDefinitionDetector`.
-//   4. For each state variable, checks if it is referenced within the contract, by searching for Identifier nodes that reference the state variable ID.
-//   5. If no references are found, captures the state variable as an issue.
+//   3. For each ContractDefinition, computes the contracts deriving from it (C3 linearization) by using the
+//      ReusableDetector: `InheritanceChainDetector`.
+//   4. Classifies the references to its state variables as reads or writes, by using the ReusableDetector:
+//      `StateVariableReadsAndWritesDetector` (which queries the shared ReferenceIndex).
+//   5. If a state variable is neither read nor written anywhere in the derived hierarchy, captures it as an issue.
+//      Internal variables of leaf contracts are always reported, those of abstract contracts that nothing in the
+//      workspace inherits from are not (they may be used by contracts outside of it).
+//      (Read-only and write-only variables are reported by `StateVariableIsNeverWrittenDetector`
+//      and `StateVariableIsWriteOnlyDetector`.)
 //   6. Returns true if any issues are found, otherwise false.
 //
 
 use std::{collections::BTreeMap, error::Error};
 
 use aderyn_driver::context::workspace_context::{ASTNode, WorkspaceContext};
-use aderyn_driver::core_ast::NodeID;
+use aderyn_driver::core_ast::{NodeID, NodeType, Visibility};
 use aderyn_driver::detection_modules::capture;
-use aderyn_driver::detector::{IssueDetector, IssueSeverity, ReusableDetector};
+use aderyn_driver::detector::{IssueDetector, IssueSeverity};
 
-use crate::state_variables_in_contract_definition::detector::StateVariablesInContractDefinitionDetector;
+use crate::analysis::storage_layout::StorageLayout;
+use crate::analysis::Analyses;
+use crate::bot_detector::BotDetector;
+use crate::confidence::Confidence;
+use crate::fixes::{Fix, FixableDetector};
+use crate::inheritance_chain::detector::derived_contracts;
+use crate::state_variable_reads_and_writes::detector::StateVariableReadsAndWritesDetector;
 
 #[derive(Default)]
 pub struct StateVariableIsNeverUsedDetector {
@@ -40,23 +52,41 @@ pub struct StateVariableIsNeverUsedDetector {
 
 impl IssueDetector for StateVariableIsNeverUsedDetector {
     fn detect(&mut self, context: &WorkspaceContext) -> Result<bool, Box<dyn Error>> {
+        let analyses = Analyses::new(context);
         context
             .contract_definitions()
             .into_iter()
             .for_each(|contract_definition| {
-                let mut get_state_variables = StateVariablesInContractDefinitionDetector::default();
-                let state_variables = get_state_variables
-                    .detect(context, &[], &[contract_definition.into()])
+                let hierarchy = derived_contracts(context, contract_definition.id);
+                let is_leaf = hierarchy.len() == 1;
+                let is_abstract = contract_definition.is_abstract.unwrap_or(false);
+
+                let mut get_accesses = StateVariableReadsAndWritesDetector::default();
+                get_accesses
+                    .detect_with(&analyses, &[], &[contract_definition.into()])
                     .unwrap();
-                for state_variable in state_variables {
-                    if let ASTNode::VariableDeclaration(sv) = state_variable {
-                        let references = context
-                            .identifiers()
-                            .into_iter()
-                            .filter(|identifier| identifier.referenced_declaration == sv.id);
-                        if references.count() == 0 {
-                            capture!(self, context, sv.clone());
+                for (id, accesses) in get_accesses.accesses() {
+                    if let Some(ASTNode::VariableDeclaration(sv)) = context.nodes.get(id) {
+                        // Public variables can be read through their getter from anywhere,
+                        // others only from the contract declaring them or contracts deriving from it
+                        let used = accesses
+                            .reads
+                            .iter()
+                            .chain(accesses.writes.iter())
+                            .any(|r| {
+                                sv.visibility == Visibility::Public
+                                    || enclosing_contract(context, *r)
+                                        .is_some_and(|c| hierarchy.contains(&c))
+                            });
+                        if used {
+                            continue;
+                        }
+                        // An abstract contract nobody inherits from here is meant to be inherited
+                        // elsewhere, so its non private variables may still be used
+                        if is_abstract && is_leaf && sv.visibility != Visibility::Private {
+                            continue;
                         }
+                        capture!(self, context, sv.clone());
                     }
                 }
             });
@@ -87,19 +117,78 @@ impl IssueDetector for StateVariableIsNeverUsedDetector {
     }
 }
 
+impl BotDetector for StateVariableIsNeverUsedDetector {
+    fn confidence(&self) -> Confidence {
+        Confidence::High
+    }
+
+    // Public variables may still be read through their getter by code outside of the workspace
+    fn instance_confidence(&self, context: &WorkspaceContext, instance: NodeID) -> Confidence {
+        match context.nodes.get(&instance) {
+            Some(ASTNode::VariableDeclaration(sv)) if sv.visibility == Visibility::Public => {
+                Confidence::Medium
+            }
+            _ => Confidence::High,
+        }
+    }
+
+    fn fixable(&self) -> Option<&dyn FixableDetector> {
+        Some(self)
+    }
+}
+
+impl FixableDetector for StateVariableIsNeverUsedDetector {
+    // Removing a variable can move the variables stored after it to other slots, which corrupts the storage
+    // of deployed upgradeable contracts. A variable is only removed when no other variable moves, in the
+    // declaring contract and in every contract deriving from it: stored last, or followed by variables that
+    // start a new slot anyway (ex: `uint128 unused` between a `uint128` and a `uint256`).
+    fn fix(&self, context: &WorkspaceContext, instance: NodeID) -> Option<Fix> {
+        let Some(ASTNode::VariableDeclaration(sv)) = context.nodes.get(&instance) else {
+            return None;
+        };
+        let contract = enclosing_contract(context, sv.id)?;
+        let moves_nothing = derived_contracts(context, contract)
+            .into_iter()
+            .all(|derived| removal_moves_nothing(context, derived, sv.id));
+        if !moves_nothing {
+            return None;
+        }
+        Fix::remove(context, sv.id, &sv.src, format!("Remove `{}`", sv.name))
+    }
+}
+
+// Constants and immutables are not in storage, removing them moves nothing
+fn removal_moves_nothing(context: &WorkspaceContext, contract: NodeID, variable: NodeID) -> bool {
+    let Some(layout) = StorageLayout::compute(context, contract) else {
+        return false;
+    };
+    layout
+        .without(variable)
+        .entries
+        .iter()
+        .all(|entry| layout.entry(entry.variable) == Some(entry))
+}
+
+fn enclosing_contract(context: &WorkspaceContext, node: NodeID) -> Option<NodeID> {
+    match context.get_closest_parent(node, NodeType::ContractDefinition) {
+        Some(ASTNode::ContractDefinition(contract_definition)) => Some(contract_definition.id),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod state_variable_is_never_set_tests {
 
+    use crate::bot_utils::load_contracts;
     use crate::config_tests::tests_configuration;
 
     use super::StateVariableIsNeverUsedDetector;
 
     use aderyn_driver::context::workspace_context::WorkspaceContext;
-    use aderyn_driver::detector::detector_test_helpers::load_contract;
     use aderyn_driver::detector::IssueDetector;
 
     fn test_state_variable_is_never_set_for(
-        _contract_file: String,
+        _contract_files: Vec<String>,
         context: WorkspaceContext,
         mut detector: impl IssueDetector,
     ) {
@@ -114,10 +203,10 @@ mod state_variable_is_never_set_tests {
         let detector = StateVariableIsNeverUsedDetector::default();
         let contracts = tests_configuration().get_contracts_for(detector.name());
 
-        for contract_file in contracts {
+        for contract_files in contracts {
             let detector = StateVariableIsNeverUsedDetector::default();
-            let context = load_contract(&contract_file);
-            test_state_variable_is_never_set_for(contract_file, context, detector);
+            let context = load_contracts(&contract_files);
+            test_state_variable_is_never_set_for(contract_files, context, detector);
         }
     }
 }
@@ -0,0 +1,140 @@
+// _   ___   _______ _   _  ________   __  _______   __  ___  ___  _________ _      _____
+// | \ | \ \ / /_   _| | | | | ___ \ \ / / |  ___\ \ / / / _ \ |  \/  || ___ \ |    |  ___|
+// |  \| |\ V /  | | | |_| | | |_/ /\ V /  | |__  \ V / / /_\ \| .  . || |_/ / |    | |__
+// | . ` | \ /   | | |  _  | | ___ \ \ /   |  __| /   \ |  _  || |\/| ||  __/| |    |  __|
+// | |\  | | |   | | | | | | | |_/ / | |   | |___/ /^\ \| | | || |  | || |   | |____| |___
+// \_| \_/ \_/   \_/ \_| |_/ \____/  \_/   \____/\/   \/\_| |_/\_|  |_/\_|   \_____/\____/
+//
+//  _____                    ______     _            _
+// |_   _|                   |  _  \   | |          | |
+//   | | ___ ___ _   _  ___  | | | |___| |_ ___  ___| |_ ___  _ __
+//   | |/ __/ __| | | |/ _ \ | | | / _ \ __/ _ \/ __| __/ _ \| '__|
+//  _| |\__ \__ \ |_| |  __/ | |/ /  __/ ||  __/ (__| || (_) | |
+//  \___/___/___/\__,_|\___| |___/ \___|\__\___|\___|\__\___/|_|
+//
+// This IssueDetector is responsible for finding state variables that are read but never written.
+// The `detect` function does this following:
+//
+//   1. Accepts a WorkspaceContext as `context`.
+//   2. Retrieves all ContractDefinition nodes from the `context`.
+//   3. For each ContractDefinition, classifies the references to its state variables as reads or writes,
+//      by using the ReusableDetector: `StateVariableReadsAndWritesDetector`.
+//   4. Captures the non constant state variables that have reads, no writes and no initial value
+//      (they always hold their default value).
+//   5. Returns true if any issues are found, otherwise false.
+//
+
+use std::{collections::BTreeMap, error::Error};
+
+use aderyn_driver::context::workspace_context::{ASTNode, WorkspaceContext};
+use aderyn_driver::core_ast::NodeID;
+use aderyn_driver::detection_modules::capture;
+use aderyn_driver::detector::{IssueDetector, IssueSeverity};
+
+use crate::analysis::Analyses;
+use crate::bot_detector::BotDetector;
+use crate::confidence::Confidence;
+use crate::state_variable_reads_and_writes::detector::StateVariableReadsAndWritesDetector;
+
+#[derive(Default)]
+pub struct StateVariableIsNeverWrittenDetector {
+    // Keys are source file name and line number
+    found_instances: BTreeMap<(String, usize, String), NodeID>,
+}
+
+impl IssueDetector for StateVariableIsNeverWrittenDetector {
+    fn detect(&mut self, context: &WorkspaceContext) -> Result<bool, Box<dyn Error>> {
+        let analyses = Analyses::new(context);
+        context
+            .contract_definitions()
+            .into_iter()
+            .for_each(|contract_definition| {
+                let mut get_accesses = StateVariableReadsAndWritesDetector::default();
+                get_accesses
+                    .detect_with(&analyses, &[], &[contract_definition.into()])
+                    .unwrap();
+                for (id, accesses) in get_accesses.accesses() {
+                    if let Some(ASTNode::VariableDeclaration(sv)) = context.nodes.get(id) {
+                        let never_written = !sv.constant
+                            && sv.value.is_none()
+                            && accesses.writes.is_empty()
+                            && !accesses.reads.is_empty();
+                        if never_written {
+                            capture!(self, context, sv.clone());
+                        }
+                    }
+                }
+            });
+        Ok(!self.found_instances.is_empty())
+    }
+
+    fn title(&self) -> String {
+        String::from("State Variable is never written")
+    }
+
+    fn description(&self) -> String {
+        String::from("State variable is read but never assigned, so it always holds its default value. Consider making it a constant or assigning it.")
+    }
+
+    fn severity(&self) -> IssueSeverity {
+        IssueSeverity::Low
+    }
+
+    fn name(&self) -> String {
+        "state-variable-is-never-written".to_string()
+    }
+
+    fn instances(&self) -> BTreeMap<(String, usize, String), NodeID> {
+        self.found_instances.clone()
+    }
+}
+
+impl BotDetector for StateVariableIsNeverWrittenDetector {
+    // Accesses from inline assembly or through delegatecall go unseen
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+}
+
+#[cfg(test)]
+mod state_variable_is_never_written_tests {
+
+    use crate::bot_utils::load_contracts;
+    use crate::config_tests::tests_configuration;
+
+    use super::StateVariableIsNeverWrittenDetector;
+
+    use aderyn_driver::context::workspace_context::WorkspaceContext;
+    use aderyn_driver::detector::IssueDetector;
+
+    fn test_state_variable_is_never_written_for(
+        _contract_files: Vec<String>,
+        context: WorkspaceContext,
+        mut detector: impl IssueDetector,
+    ) {
+        // assert that the detector finds `neverWritten` only
+        let found = detector.detect(&context).unwrap();
+        assert!(found);
+        let locations = detector
+            .instances()
+            .into_keys()
+            .map(|(file, line, _)| (file, line))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            locations,
+            vec![(String::from("src/ReadsAndWrites.sol"), 13)]
+        );
+    }
+
+    #[test]
+    fn test_state_variable_is_never_written() {
+        let detector = StateVariableIsNeverWrittenDetector::default();
+        let contracts = tests_configuration().get_contracts_for(detector.name());
+
+        for contract_files in contracts {
+            let detector = StateVariableIsNeverWrittenDetector::default();
+            let context = load_contracts(&contract_files);
+            test_state_variable_is_never_written_for(contract_files, context, detector);
+        }
+    }
+}
@@ -0,0 +1,211 @@
+// _   ___   _______ _   _  ________   __  _______   __  ___  ___  _________ _      _____
+// | \ | \ \ / /_   _| | | | | ___ \ \ / / |  ___\ \ / / / _ \ |  \/  || ___ \ |    |  ___|
+// |  \| |\ V /  | | | |_| | | |_/ /\ V /  | |__  \ V / / /_\ \| .  . || |_/ / |    | |__
+// | . ` | \ /   | | |  _  | | ___ \ \ /   |  __| /   \ |  _  || |\/| ||  __/| |    |  __|
+// | |\  | | |   | | | | | | | |_/ / | |   | |___/ /^\ \| | | || |  | || |   | |____| |___
+// \_| \_/ \_/   \_/ \_| |_/ \____/  \_/   \____/\/   \/\_| |_/\_|  |_/\_|   \_____/\____/
+// ______                     _     _       ______     _            _
+// | ___ \                   | |   | |      |  _  \   | |          | |
+// | |_/ /___ _   _ ___  __ _| |__ | | ___  | | | |___| |_ ___  ___| |_ ___  _ __
+// |    // _ \ | | / __|/ _` | '_ \| |/ _ \ | | | / _ \ __/ _ \/ __| __/ _ \| '__|
+// | |\ \  __/ |_| \__ \ (_| | |_) | |  __/ | |/ /  __/ ||  __/ (__| || (_) | |
+// \_| \_\___|\__,_|___/\__,_|_.__/|_|\___| |___/ \___|\__\___|\___|\__\___/|_|
+//
+// This ReusableDetector is responsible for classifying every reference to a state variable as a read or a write.
+// The `detect` function does this following:
+//
+//   1. Accept ContractDefinition nodes via the `within` parameter (optionally narrowed down to the
+//      VariableDeclaration nodes passed in `using`).
+//   2. Retrieve their state variables by using the ReusableDetector: `StateVariablesInContractDefinitionDetector`.
+//   3. For each state variable, look up every reference to it in the ReferenceIndex of the `Analyses`.
+//   4. Classify each reference as a read, a write or both (assignment LHS, `++`, `delete`, push/pop,
+//      ...) and record it in the per variable `accesses` map. Taking a local storage pointer is a read,
+//      passing the variable to a storage parameter a write, and the reads and writes made through a
+//      pointer are recorded as reads and writes of the variables it may point into.
+//   5. Return every reference site (read or write) in the `found_instances` vector.
+//
+// Use `accesses()` after `detect` to get the read sites and write sites of each state variable.
+//
+
+use std::collections::BTreeMap;
+
+use aderyn_driver::context::workspace_context::ASTNode;
+use aderyn_driver::context::workspace_context::WorkspaceContext;
+use aderyn_driver::core_ast::NodeID;
+use aderyn_driver::detector::ReusableDetector;
+
+use crate::analysis::access::classify_access;
+use crate::analysis::Analyses;
+use crate::state_variables_in_contract_definition::detector::StateVariablesInContractDefinitionDetector;
+
+#[derive(Default, Clone, Debug)]
+pub struct StateVariableAccesses {
+    pub reads: Vec<NodeID>,
+    pub writes: Vec<NodeID>,
+}
+
+#[derive(Default)]
+pub struct StateVariableReadsAndWritesDetector {
+    found_instances: Vec<ASTNode>,
+    // Keys are the ids of the state variables
+    accesses: BTreeMap<NodeID, StateVariableAccesses>,
+}
+
+impl StateVariableReadsAndWritesDetector {
+    pub fn accesses(&self) -> &BTreeMap<NodeID, StateVariableAccesses> {
+        &self.accesses
+    }
+
+    // Same as `detect`, reusing the ReferenceIndex of `analyses` (callers running it once per
+    // contract then index the context only once)
+    pub fn detect_with(
+        &mut self,
+        analyses: &Analyses,
+        using: &[ASTNode],
+        within: &[ASTNode],
+    ) -> Result<&[ASTNode], Box<dyn std::error::Error>> {
+        let context = analyses.context();
+        let references = analyses.references();
+        let aliases = analyses.storage_aliases();
+        let only = using
+            .iter()
+            .filter_map(|node| match node {
+                ASTNode::VariableDeclaration(variable) => Some(variable.id),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut get_state_variables = StateVariablesInContractDefinitionDetector::default();
+        let state_variables = get_state_variables.detect(context, &[], within)?;
+
+        for state_variable in state_variables {
+            let ASTNode::VariableDeclaration(state_variable) = state_variable else {
+                continue;
+            };
+            if !only.is_empty() && !only.contains(&state_variable.id) {
+                continue;
+            }
+
+            let accesses = self.accesses.entry(state_variable.id).or_default();
+            for reference in references.references_to(state_variable.id) {
+                let access = classify_access(context, *reference);
+                if access.is_read() {
+                    accesses.reads.push(*reference);
+                }
+                if access.is_write() {
+                    accesses.writes.push(*reference);
+                }
+                if let Some(node) = context.nodes.get(reference) {
+                    self.found_instances.push(node.clone());
+                }
+            }
+
+            // `S storage s = x; s.y = 1` writes `x`, `return s.y` reads it
+            let pointers = aliases
+                .iter()
+                .filter(|(_, targets)| targets.contains(&state_variable.id))
+                .map(|(pointer, _)| *pointer);
+            for pointer in pointers {
+                for reference in references.references_to(pointer) {
+                    let access = classify_access(context, *reference);
+                    if access.is_read() {
+                        accesses.reads.push(*reference);
+                    }
+                    if access.is_write() {
+                        accesses.writes.push(*reference);
+                    }
+                    if access.is_read() || access.is_write() {
+                        if let Some(node) = context.nodes.get(reference) {
+                            self.found_instances.push(node.clone());
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(&self.found_instances)
+    }
+}
+
+impl ReusableDetector for StateVariableReadsAndWritesDetector {
+    fn detect(
+        &mut self,
+        context: &WorkspaceContext,
+        using: &[ASTNode],
+        within: &[ASTNode],
+    ) -> Result<&[ASTNode], Box<dyn std::error::Error>> {
+        self.detect_with(&Analyses::new(context), using, within)
+    }
+
+    fn name(&self) -> String {
+        "state-variable-reads-and-writes".to_string()
+    }
+}
+
+#[cfg(test)]
+mod state_variable_reads_and_writes_tests {
+
+    use crate::bot_utils::load_contracts;
+    use crate::config_tests::tests_configuration;
+
+    use super::StateVariableReadsAndWritesDetector;
+
+    use aderyn_driver::detector::ReusableDetector;
+
+    #[test]
+    fn test_state_variable_reads_and_writes() {
+        let detector = StateVariableReadsAndWritesDetector::default();
+        let cases = tests_configuration().get_reusable_cases_for(detector.name());
+
+        for case in cases {
+            let mut detector = StateVariableReadsAndWritesDetector::default();
+            case.run(&mut detector);
+        }
+    }
+
+    #[test]
+    fn test_state_variable_reads_and_writes_classification() {
+        let context = load_contracts(&["./foundry_workspace/out/Counter.sol/Counter.json".into()]);
+        let counter = context.nodes[&context.contract_definitions()[0].id].clone();
+
+        let mut detector = StateVariableReadsAndWritesDetector::default();
+        detector.detect(&context, &[], &[counter]).unwrap();
+
+        let variables = context.variable_declarations();
+        let number = variables.iter().find(|v| v.name == "number").unwrap();
+        let accesses = &detector.accesses()[&number.id];
+
+        // `number = newNumber` writes, `number++` reads and writes
+        assert_eq!(accesses.writes.len(), 2);
+        assert_eq!(accesses.reads.len(), 1);
+    }
+
+    #[test]
+    fn test_writes_through_storage_pointers() {
+        let context = load_contracts(&[
+            "./foundry_workspace/out/StorageWrites.sol/StorageWrites.json".into(),
+        ]);
+        let contract = context.nodes[&context.contract_definitions()[0].id].clone();
+
+        let mut detector = StateVariableReadsAndWritesDetector::default();
+        detector.detect(&context, &[], &[contract]).unwrap();
+
+        let variables = context.variable_declarations();
+        let accounts = variables.iter().find(|v| v.name == "accounts").unwrap();
+        let accesses = &detector.accesses()[&accounts.id];
+
+        // Taking the pointers of `deposit`, `reset` and `peek` reads, and so do `account.balance +=
+        // amount` in `deposit`, `account.nonce += 1` in `_bump` and `account.balance` in `peek`.
+        // `account.balance += amount`, passing `accounts[msg.sender]` to `_bump`, `account.nonce += 1`
+        // and `account.nonce = 0` in `reset` write
+        assert_eq!(accesses.reads.len(), 6);
+        assert_eq!(accesses.writes.len(), 4);
+
+        // `account = backups[msg.sender]` reads `backups` without writing `accounts`, the write
+        // through `account` may hit either
+        let backups = variables.iter().find(|v| v.name == "backups").unwrap();
+        let accesses = &detector.accesses()[&backups.id];
+        assert_eq!(accesses.reads.len(), 1);
+        assert_eq!(accesses.writes.len(), 1);
+    }
+}
@@ -65,35 +65,17 @@ mod state_variables_in_contract_definition_tests {
 
     use super::StateVariablesInContractDefinitionDetector;
 
-    use aderyn_driver::context::workspace_context::WorkspaceContext;
-    use aderyn_driver::detector::detector_test_helpers::load_contract;
     use aderyn_driver::detector::ReusableDetector;
 
-    fn test_state_variables_in_contract_definition_for(
-        _contract_file: String,
-        context: WorkspaceContext,
-        mut detector: impl ReusableDetector,
-    ) {
-        // assert that the detector finds instances
-        let contract_definition_ast = context
-            .nodes
-            .get(&context.contract_definitions()[0].id)
-            .unwrap()
-            .clone();
-        let contracts = vec![contract_definition_ast];
-        let results = detector.detect(&context, &Vec::new(), &contracts).unwrap();
-        assert!(!results.is_empty());
-    }
-
     #[test]
     fn test_state_variables_in_contract_definition() {
         let detector = StateVariablesInContractDefinitionDetector::default();
-        let contracts = tests_configuration().get_contracts_for(detector.name());
+        let cases = tests_configuration().get_reusable_cases_for(detector.name());
 
-        for contract_file in contracts {
-            let detector = StateVariablesInContractDefinitionDetector::default();
-            let context = load_contract(&contract_file);
-            test_state_variables_in_contract_definition_for(contract_file, context, detector);
+        for case in cases {
+            // `using` and `within` are selected from the contract as configured in `config_tests.rs`
+            let mut detector = StateVariablesInContractDefinitionDetector::default();
+            case.run(&mut detector);
         }
     }
 }
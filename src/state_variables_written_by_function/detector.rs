@@ -0,0 +1,185 @@
+// _   ___   _______ _   _  ________   __  _______   __  ___  ___  _________ _      _____
+// | \ | \ \ / /_   _| | | | | ___ \ \ / / |  ___\ \ / / / _ \ |  \/  || ___ \ |    |  ___|
+// |  \| |\ V /  | | | |_| | | |_/ /\ V /  | |__  \ V / / /_\ \| .  . || |_/ / |    | |__
+// | . ` | \ /   | | |  _  | | ___ \ \ /   |  __| /   \ |  _  || |\/| ||  __/| |    |  __|
+// | |\  | | |   | | | | | | | |_/ / | |   | |___/ /^\ \| | | || |  | || |   | |____| |___
+// \_| \_/ \_/   \_/ \_| |_/ \____/  \_/   \____/\/   \/\_| |_/\_|  |_/\_|   \_____/\____/
+// ______                     _     _       ______     _            _
+// | ___ \                   | |   | |      |  _  \   | |          | |
+// | |_/ /___ _   _ ___  __ _| |__ | | ___  | | | |___| |_ ___  ___| |_ ___  _ __
+// |    // _ \ | | / __|/ _` | '_ \| |/ _ \ | | | / _ \ __/ _ \/ __| __/ _ \| '__|
+// | |\ \  __/ |_| \__ \ (_| | |_) | |  __/ | |/ /  __/ ||  __/ (__| || (_) | |
+// \_| \_\___|\__,_|___/\__,_|_.__/|_|\___| |___/ \___|\__\___|\___|\__\___/|_|
+//
+// This ReusableDetector is responsible for finding the state variables a function or modifier writes, transitively.
+// The `detect` function does this following:
+//
+//   1. Accept FunctionDefinition / ModifierDefinition nodes via the `within` parameter.
+//   2. Find every function and modifier they can execute (internal, library and `super` calls plus
+//      modifiers), by using the ReusableDetector: `ReachableFunctionsDetector`.
+//   3. Map storage pointers (`Struct storage s = map[k]`, storage parameters) back to the state
+//      variables they point into.
+//   4. Classify every reference to a state variable or to one of its aliases, and keep the written state variables.
+//   5. Add the VariableDeclaration nodes of those state variables to the `found_instances` vector.
+//   6. Return the `found_instances` vector.
+//
+
+use std::collections::BTreeSet;
+
+use aderyn_driver::context::browser::{ExtractIdentifiers, ExtractMemberAccesses};
+use aderyn_driver::context::workspace_context::ASTNode;
+use aderyn_driver::context::workspace_context::WorkspaceContext;
+use aderyn_driver::core_ast::{Identifier, MemberAccess, NodeID};
+use aderyn_driver::detector::ReusableDetector;
+
+use crate::analysis::access::{classify_access, is_state_variable};
+use crate::analysis::Analyses;
+use crate::reachable_functions::detector::ReachableFunctionsDetector;
+
+#[derive(Default)]
+pub struct StateVariablesWrittenByFunctionDetector {
+    found_instances: Vec<ASTNode>,
+}
+
+impl StateVariablesWrittenByFunctionDetector {
+    // Same as `detect`, reusing the call graph and storage aliases of `analyses`
+    pub fn detect_with(
+        &mut self,
+        analyses: &Analyses,
+        _: &[ASTNode],
+        within: &[ASTNode],
+    ) -> Result<&[ASTNode], Box<dyn std::error::Error>> {
+        let context = analyses.context();
+        let mut get_reachable = ReachableFunctionsDetector::default();
+        let reachable = get_reachable.detect_with(analyses, &[], within)?;
+
+        let aliases = analyses.storage_aliases();
+        let mut written = BTreeSet::new();
+        for callable in reachable {
+            let references = match callable {
+                ASTNode::FunctionDefinition(function) => references_in(
+                    ExtractIdentifiers::from(function).extracted,
+                    ExtractMemberAccesses::from(function).extracted,
+                ),
+                ASTNode::ModifierDefinition(modifier) => references_in(
+                    ExtractIdentifiers::from(modifier).extracted,
+                    ExtractMemberAccesses::from(modifier).extracted,
+                ),
+                _ => continue,
+            };
+
+            for (reference, declaration) in references {
+                let targets = match aliases.get(&declaration) {
+                    Some(state_variables) => state_variables.clone(),
+                    None if is_state_variable(context, declaration) => {
+                        BTreeSet::from([declaration])
+                    }
+                    None => continue,
+                };
+                // Creating a pointer only reads, writing through it writes what it points into
+                if classify_access(context, reference).is_write() {
+                    written.extend(targets);
+                }
+            }
+        }
+
+        for id in written {
+            if let Some(state_variable) = context.nodes.get(&id) {
+                self.found_instances.push(state_variable.clone());
+            }
+        }
+
+        Ok(&self.found_instances)
+    }
+}
+
+impl ReusableDetector for StateVariablesWrittenByFunctionDetector {
+    fn detect(
+        &mut self,
+        context: &WorkspaceContext,
+        using: &[ASTNode],
+        within: &[ASTNode],
+    ) -> Result<&[ASTNode], Box<dyn std::error::Error>> {
+        self.detect_with(&Analyses::new(context), using, within)
+    }
+
+    fn name(&self) -> String {
+        "state-variables-written-by-function".to_string()
+    }
+}
+
+// (reference node, referenced declaration) pairs
+fn references_in(
+    identifiers: Vec<Identifier>,
+    member_accesses: Vec<MemberAccess>,
+) -> Vec<(NodeID, NodeID)> {
+    let mut references = identifiers
+        .into_iter()
+        .map(|i| (i.id, i.referenced_declaration))
+        .collect::<Vec<_>>();
+    references.extend(
+        member_accesses
+            .into_iter()
+            .filter_map(|m| m.referenced_declaration.map(|d| (m.id, d))),
+    );
+    references
+}
+
+#[cfg(test)]
+mod state_variables_written_by_function_tests {
+
+    use crate::bot_utils::{load_contracts, NodeSelector};
+    use crate::config_tests::tests_configuration;
+
+    use super::StateVariablesWrittenByFunctionDetector;
+
+    use aderyn_driver::context::workspace_context::ASTNode;
+    use aderyn_driver::detector::ReusableDetector;
+
+    #[test]
+    fn test_state_variables_written_by_function() {
+        let detector = StateVariablesWrittenByFunctionDetector::default();
+        let cases = tests_configuration().get_reusable_cases_for(detector.name());
+
+        for case in cases {
+            let mut detector = StateVariablesWrittenByFunctionDetector::default();
+            case.run(&mut detector);
+        }
+    }
+
+    #[test]
+    fn test_storage_pointer_without_writes() {
+        let context = load_contracts(&[
+            "./foundry_workspace/out/StorageWrites.sol/StorageWrites.json".into(),
+        ]);
+        let within = NodeSelector::function("peek").select(&context);
+
+        let mut detector = StateVariablesWrittenByFunctionDetector::default();
+        assert!(detector.detect(&context, &[], &within).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_storage_pointer_arguments_and_rebinding() {
+        let context = load_contracts(&[
+            "./foundry_workspace/out/StorageWrites.sol/StorageWrites.json".into(),
+        ]);
+        let written = |function: &str| {
+            let within = NodeSelector::function(function).select(&context);
+            let mut detector = StateVariablesWrittenByFunctionDetector::default();
+            let mut names = detector
+                .detect(&context, &[], &within)
+                .unwrap()
+                .iter()
+                .filter_map(|node| match node {
+                    ASTNode::VariableDeclaration(variable) => Some(variable.name.clone()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            names.sort();
+            names
+        };
+
+        assert_eq!(written("bumpNonce"), vec!["accounts"]);
+        assert_eq!(written("reset"), vec!["accounts", "backups"]);
+    }
+}
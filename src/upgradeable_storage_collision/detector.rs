@@ -0,0 +1,236 @@
+// _   ___   _______ _   _  ________   __  _______   __  ___  ___  _________ _      _____
+// | \ | \ \ / /_   _| | | | | ___ \ \ / / |  ___\ \ / / / _ \ |  \/  || ___ \ |    |  ___|
+// |  \| |\ V /  | | | |_| | | |_/ /\ V /  | |__  \ V / / /_\ \| .  . || |_/ / |    | |__
+// | . ` | \ /   | | |  _  | | ___ \ \ /   |  __| /   \ |  _  || |\/| ||  __/| |    |  __|
+// | |\  | | |   | | | | | | | |_/ / | |   | |___/ /^\ \| | | || |  | || |   | |____| |___
+// \_| \_/ \_/   \_/ \_| |_/ \____/  \_/   \____/\/   \/\_| |_/\_|  |_/\_|   \_____/\____/
+//
+//  _____                    ______     _            _
+// |_   _|                   |  _  \   | |          | |
+//   | | ___ ___ _   _  ___  | | | |___| |_ ___  ___| |_ ___  _ __
+//   | |/ __/ __| | | |/ _ \ | | | / _ \ __/ _ \/ __| __/ _ \| '__|
+//  _| |\__ \__ \ |_| |  __/ | |/ /  __/ ||  __/ (__| || (_) | |
+//  \___/___/___/\__,_|\___| |___/ \___|\__\___|\___|\__\___/|_|
+//
+// This IssueDetector is responsible for finding storage collisions between versions of upgradeable contracts.
+// The `detect` function does this following:
+//
+//   1. Accepts a WorkspaceContext as `context`.
+//   2. Pairs each ContractDefinition annotated with `@custom:oz-upgrades-from <Previous>` (the OpenZeppelin
+//      Upgrades annotation) with the contract it upgrades, found by `path:Name` or by a name no other contract has.
+//   3. Compares the storage layout of each pair, by using `compare_layouts`
+//      (reordered, removed, retyped or inserted-before-gap variables and resized gaps).
+//   4. Checks the `__gap` arrays of every contract (not last in their contract, not fixed size).
+//   5. Captures the state variables of the newer version involved (or the contract when the variable was removed),
+//      and lists each conflict with its slots in the description.
+//   6. Returns true if any issues are found, otherwise false.
+//
+// Use `pilot storage-diff` to compare two versions living in different artifacts or projects.
+//
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    error::Error,
+};
+
+use aderyn_driver::context::workspace_context::{ASTNode, WorkspaceContext};
+use aderyn_driver::core_ast::{ContractDefinition, Documentation, NodeID, NodeType};
+use aderyn_driver::detection_modules::capture;
+use aderyn_driver::detector::{IssueDetector, IssueSeverity};
+
+use crate::analysis::storage_layout::{compare_layouts, StorageConflict, StorageLayout};
+use crate::analysis::Analyses;
+use crate::bot_detector::BotDetector;
+use crate::confidence::Confidence;
+
+#[derive(Default)]
+pub struct UpgradeableStorageCollisionDetector {
+    // Keys are source file name and line number
+    found_instances: BTreeMap<(String, usize, String), NodeID>,
+    // `Contract`: conflict, with its slots
+    conflicts: Vec<String>,
+}
+
+impl IssueDetector for UpgradeableStorageCollisionDetector {
+    fn detect(&mut self, context: &WorkspaceContext) -> Result<bool, Box<dyn Error>> {
+        let analyses = Analyses::new(context);
+        let layouts = analyses.storage_layouts();
+
+        let contract_definitions = context.contract_definitions();
+
+        // Newer versions get their gaps checked by `compare_layouts`
+        let mut compared = HashSet::new();
+        for new in &contract_definitions {
+            let Some((path, name)) = upgrades_from(new) else {
+                continue;
+            };
+            // A bare name shared by several contracts is ambiguous, it needs the `path:Name` form
+            let mut candidates = contract_definitions.iter().filter(|c| {
+                c.name == name && path.is_none_or(|path| source_path(context, c) == Some(path))
+            });
+            let (Some(old), None) = (candidates.next(), candidates.next()) else {
+                continue;
+            };
+            compared.insert(new.id);
+            let (Some(old_layout), Some(new_layout)) = (layouts.of(old.id), layouts.of(new.id))
+            else {
+                continue;
+            };
+            for conflict in compare_layouts(old_layout, new_layout) {
+                self.capture_conflict(context, new, new_layout, &conflict);
+            }
+        }
+
+        for contract_definition in contract_definitions {
+            if compared.contains(&contract_definition.id) {
+                continue;
+            }
+            if let Some(layout) = layouts.of(contract_definition.id) {
+                for conflict in layout.gap_misuses() {
+                    self.capture_conflict(context, contract_definition, layout, &conflict);
+                }
+            }
+        }
+
+        Ok(!self.found_instances.is_empty())
+    }
+
+    fn title(&self) -> String {
+        String::from("Storage layout is incompatible with the previous version")
+    }
+
+    fn description(&self) -> String {
+        let mut description = String::from("A variable was reordered, removed, retyped or inserted before a `__gap` (or a `__gap` was resized or misplaced). Behind a proxy, the new implementation reads and writes the slots of the previous one, corrupting state. Keep existing variables in place and take new slots out of the gap.");
+        if !self.conflicts.is_empty() {
+            description.push_str("\n\nConflicts found:\n");
+            for conflict in &self.conflicts {
+                description.push_str(&format!("\n- {}", conflict));
+            }
+        }
+        description
+    }
+
+    fn severity(&self) -> IssueSeverity {
+        IssueSeverity::High
+    }
+
+    fn name(&self) -> String {
+        "upgradeable-storage-collision".to_string()
+    }
+
+    fn instances(&self) -> BTreeMap<(String, usize, String), NodeID> {
+        self.found_instances.clone()
+    }
+}
+
+impl BotDetector for UpgradeableStorageCollisionDetector {
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+}
+
+impl UpgradeableStorageCollisionDetector {
+    fn capture_conflict(
+        &mut self,
+        context: &WorkspaceContext,
+        contract: &ContractDefinition,
+        layout: &StorageLayout,
+        conflict: &StorageConflict,
+    ) {
+        self.conflicts
+            .push(format!("`{}`: {}", contract.name, conflict));
+        let variable = match conflict {
+            StorageConflict::Removed { .. } => None,
+            StorageConflict::GapResized { .. } | StorageConflict::MisusedGap { .. } => {
+                layout.entries.iter().find(|e| {
+                    e.is_gap() && e.slot <= conflict.slot() && conflict.slot() <= e.end_slot()
+                })
+            }
+            _ => layout.entries.iter().find(|e| e.label == conflict.label()),
+        };
+        match variable.and_then(|e| context.nodes.get(&e.variable)) {
+            Some(ASTNode::VariableDeclaration(variable)) => {
+                capture!(self, context, variable.clone())
+            }
+            _ => capture!(self, context, contract),
+        }
+    }
+}
+
+// `/// @custom:oz-upgrades-from VaultV1` => (None, "VaultV1")
+// `/// @custom:oz-upgrades-from src/Vault.sol:VaultV1` => (Some("src/Vault.sol"), "VaultV1")
+fn upgrades_from(contract: &ContractDefinition) -> Option<(Option<&str>, &str)> {
+    let text = match contract.documentation.as_ref()? {
+        Documentation::String(text) => text.as_deref()?,
+        Documentation::Structured(documentation) => documentation.as_ref()?.text.as_str(),
+    };
+    let reference = text
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("@custom:oz-upgrades-from"))?
+        .split_whitespace()
+        .next()?;
+    match reference.rsplit_once(':') {
+        Some((path, name)) => Some((Some(path), name)),
+        None => Some((None, reference)),
+    }
+}
+
+// `absolutePath` of the source unit declaring `contract` (ex: `src/Vault.sol`)
+fn source_path<'a>(
+    context: &'a WorkspaceContext,
+    contract: &ContractDefinition,
+) -> Option<&'a str> {
+    match context.get_closest_parent(contract.id, NodeType::SourceUnit) {
+        Some(ASTNode::SourceUnit(source_unit)) => source_unit.absolute_path.as_deref(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod upgradeable_storage_collision_tests {
+
+    use crate::bot_utils::load_contracts;
+    use crate::config_tests::tests_configuration;
+
+    use super::UpgradeableStorageCollisionDetector;
+
+    use aderyn_driver::context::workspace_context::WorkspaceContext;
+    use aderyn_driver::detector::IssueDetector;
+
+    fn test_upgradeable_storage_collision_for(
+        _contract_files: Vec<String>,
+        context: WorkspaceContext,
+        mut detector: impl IssueDetector,
+    ) {
+        // assert that the detector finds the 5 conflicting variables of VaultV2 and the misplaced gap.
+        // TokenV2 only appends `minter` to the TokenV1 of its own file, not to the legacy one.
+        let found = detector.detect(&context).unwrap();
+        assert!(found);
+        let locations = detector
+            .instances()
+            .into_keys()
+            .map(|(file, line, _)| (file, line))
+            .collect::<Vec<_>>();
+        let expected = [15, 16, 17, 18, 19, 45]
+            .map(|line| (String::from("src/Upgradeable.sol"), line))
+            .to_vec();
+        assert_eq!(locations, expected);
+
+        // Each conflict is listed with its slots
+        let description = detector.description();
+        assert!(description
+            .contains("`VaultV2`: `owner` moved from slot 0 (offset 0) to slot 1 (offset 0)"));
+    }
+
+    #[test]
+    fn test_upgradeable_storage_collision() {
+        let detector = UpgradeableStorageCollisionDetector::default();
+        let contracts = tests_configuration().get_contracts_for(detector.name());
+
+        for contract_files in contracts {
+            let detector = UpgradeableStorageCollisionDetector::default();
+            let context = load_contracts(&contract_files);
+            test_upgradeable_storage_collision_for(contract_files, context, detector);
+        }
+    }
+}
@@ -0,0 +1,323 @@
+// Watch mode: reruns the detectors whenever a `.sol` file or an artifact of the project changes and
+// prints the findings that appeared or went away since the previous run.
+//
+// Changes are found by polling modification times, which needs no platform specific watcher and copes
+// with the build rewriting `out/` on every run (the snapshot is taken after the run).
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use aderyn_driver::context::workspace_context::{ASTNode, WorkspaceContext};
+use aderyn_driver::core_ast::{NodeID, NodeType};
+
+use crate::bot_detector::BotDetector;
+use crate::fixes::parse_src;
+
+// Folders never worth watching: dependencies, build caches and version control
+const IGNORED_DIRECTORIES: &[&str] = &["lib", "node_modules", "cache", ".git"];
+
+// A finding is identified by its detector, its file and what it points at: the name of the declaration
+// reported, or the source text of other nodes. The line only locates it for display, so that editing
+// the code above a finding does not report it as resolved and new again.
+#[derive(Clone, Debug)]
+pub struct Finding {
+    pub detector: String,
+    pub file: String,
+    pub subject: String,
+    // Among the findings of the detector with the same subject in the file, in line order
+    pub occurrence: usize,
+    pub line: usize,
+}
+
+impl Finding {
+    fn identity(&self) -> (&str, &str, &str, usize) {
+        (&self.detector, &self.file, &self.subject, self.occurrence)
+    }
+}
+
+impl PartialEq for Finding {
+    fn eq(&self, other: &Self) -> bool {
+        self.identity() == other.identity()
+    }
+}
+
+impl Eq for Finding {}
+
+impl PartialOrd for Finding {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Finding {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.identity().cmp(&other.identity())
+    }
+}
+
+// Modification times of the watched files
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Snapshot(BTreeMap<PathBuf, SystemTime>);
+
+impl Snapshot {
+    // `.sol` sources, `.json` artifacts and `.toml` configs under `root` and the `extra` folders (ex: rules)
+    pub fn take(root: &Path, extra: &[&Path]) -> Self {
+        let mut files = BTreeMap::new();
+        for directory in std::iter::once(root).chain(extra.iter().copied()) {
+            collect(directory, &mut files);
+        }
+        Self(files)
+    }
+
+    // Files added, removed or modified since `previous`
+    pub fn changes(&self, previous: &Snapshot) -> Vec<PathBuf> {
+        let mut changed = self
+            .0
+            .iter()
+            .filter(|(path, modified)| previous.0.get(*path) != Some(*modified))
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+        changed.extend(
+            previous
+                .0
+                .keys()
+                .filter(|path| !self.0.contains_key(*path))
+                .cloned(),
+        );
+        changed
+    }
+}
+
+fn collect(directory: &Path, files: &mut BTreeMap<PathBuf, SystemTime>) {
+    let Ok(entries) = std::fs::read_dir(directory) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            let ignored = path
+                .file_name()
+                .is_some_and(|name| IGNORED_DIRECTORIES.iter().any(|i| name == *i));
+            if !ignored {
+                collect(&path, files);
+            }
+            continue;
+        }
+        let watched = path
+            .extension()
+            .is_some_and(|ext| ext == "sol" || ext == "json" || ext == "toml");
+        if !watched {
+            continue;
+        }
+        if let Ok(modified) = entry.metadata().and_then(|m| m.modified()) {
+            files.insert(path, modified);
+        }
+    }
+}
+
+// Runs `detectors` on `context` and collects their instances
+pub fn findings(
+    context: &WorkspaceContext,
+    detectors: Vec<Box<dyn BotDetector>>,
+) -> BTreeSet<Finding> {
+    let mut findings = BTreeSet::new();
+    for mut detector in detectors {
+        if let Err(err) = detector.detect(context) {
+            eprintln!("[!] {} failed: {}", detector.name(), err);
+            continue;
+        }
+        let mut occurrences: BTreeMap<(String, String), usize> = BTreeMap::new();
+        for ((file, line, src), id) in detector.instances() {
+            let subject = subject(context, id, &src).unwrap_or(src);
+            let occurrence = occurrences
+                .entry((file.clone(), subject.clone()))
+                .or_default();
+            findings.insert(Finding {
+                detector: detector.name(),
+                file,
+                subject,
+                occurrence: *occurrence,
+                line,
+            });
+            *occurrence += 1;
+        }
+    }
+    findings
+}
+
+// Name of the declaration `node`, or its source text with the whitespace collapsed
+fn subject(context: &WorkspaceContext, node: NodeID, src: &str) -> Option<String> {
+    let name = match context.nodes.get(&node)? {
+        ASTNode::ContractDefinition(n) => Some(&n.name),
+        ASTNode::FunctionDefinition(n) => Some(&n.name),
+        ASTNode::ModifierDefinition(n) => Some(&n.name),
+        ASTNode::VariableDeclaration(n) => Some(&n.name),
+        ASTNode::EventDefinition(n) => Some(&n.name),
+        ASTNode::ErrorDefinition(n) => Some(&n.name),
+        ASTNode::StructDefinition(n) => Some(&n.name),
+        _ => None,
+    };
+    // Constructors, fallback and receive functions have no name
+    if let Some(name) = name.filter(|name| !name.is_empty()) {
+        return Some(name.clone());
+    }
+
+    let Some(ASTNode::SourceUnit(source_unit)) =
+        context.get_closest_parent(node, NodeType::SourceUnit)
+    else {
+        return None;
+    };
+    let (start, length) = parse_src(src)?;
+    let text = source_unit.source.as_deref()?.get(start..start + length)?;
+    Some(text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+// Findings of `current` missing from `previous`, and the other way around
+pub fn diff<'a>(
+    previous: &'a BTreeSet<Finding>,
+    current: &'a BTreeSet<Finding>,
+) -> (Vec<&'a Finding>, Vec<&'a Finding>) {
+    (
+        current.difference(previous).collect(),
+        previous.difference(current).collect(),
+    )
+}
+
+// Calls `run` now and after every change under `root`, printing the difference between consecutive runs.
+// Runs that panic (ex: the project does not compile) are reported and wait for the next change.
+pub fn watch(
+    root: &Path,
+    extra: &[&Path],
+    interval: Duration,
+    mut run: impl FnMut() -> BTreeSet<Finding>,
+) -> ! {
+    let mut previous = None;
+    loop {
+        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(&mut run)) {
+            Ok(current) => {
+                print_diff(previous.as_ref(), &current);
+                previous = Some(current);
+            }
+            Err(_) => eprintln!("[!] Run failed, waiting for the next change"),
+        }
+
+        // Taken after the run, as running may rebuild the artifacts
+        let snapshot = Snapshot::take(root, extra);
+        println!("[*] Watching {} for changes", root.display());
+        loop {
+            std::thread::sleep(interval);
+            let changes = Snapshot::take(root, extra).changes(&snapshot);
+            if let Some(first) = changes.first() {
+                println!("\n[*] {} changed, rerunning", first.display());
+                break;
+            }
+        }
+    }
+}
+
+fn print_diff(previous: Option<&BTreeSet<Finding>>, current: &BTreeSet<Finding>) {
+    let Some(previous) = previous else {
+        println!("[*] {} findings", current.len());
+        return;
+    };
+    let (new, resolved) = diff(previous, current);
+    println!(
+        "[*] {} findings: {} new, {} resolved",
+        current.len(),
+        new.len(),
+        resolved.len()
+    );
+    for finding in new {
+        println!("  + {} {}:{}", finding.detector, finding.file, finding.line);
+    }
+    for finding in resolved {
+        println!("  - {} {}:{}", finding.detector, finding.file, finding.line);
+    }
+}
+
+#[cfg(test)]
+mod watch_tests {
+
+    use std::collections::BTreeSet;
+
+    use crate::bot_utils::load_contracts;
+    use crate::public_state_variables::detector::PublicStateVariablesDetector;
+    use crate::state_variable_is_never_used::detector::StateVariableIsNeverUsedDetector;
+
+    use super::{diff, findings, Finding, Snapshot};
+
+    const COUNTER: &str = "./foundry_workspace/out/Counter.sol/Counter.json";
+
+    #[test]
+    fn test_snapshot_changes() {
+        let root = std::env::temp_dir().join(format!("nyth-watch-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("lib")).unwrap();
+        std::fs::write(root.join("A.sol"), "contract A {}").unwrap();
+        std::fs::write(root.join("notes.md"), "not watched").unwrap();
+        std::fs::write(root.join("lib").join("B.sol"), "contract B {}").unwrap();
+
+        let before = Snapshot::take(&root, &[]);
+        assert_eq!(before.0.len(), 1);
+        assert!(Snapshot::take(&root, &[]).changes(&before).is_empty());
+
+        std::fs::write(root.join("C.sol"), "contract C {}").unwrap();
+        std::fs::remove_file(root.join("A.sol")).unwrap();
+        let changes = Snapshot::take(&root, &[]).changes(&before);
+        assert_eq!(changes, vec![root.join("C.sol"), root.join("A.sol")]);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_findings_diff() {
+        let context = load_contracts(&[COUNTER.into()]);
+        let previous = findings(
+            &context,
+            vec![Box::<PublicStateVariablesDetector>::default()],
+        );
+        let current = findings(
+            &context,
+            vec![Box::<StateVariableIsNeverUsedDetector>::default()],
+        );
+
+        let (new, resolved) = diff(&previous, &current);
+        assert_eq!(new.len(), 1);
+        assert_eq!(new[0].detector, "state-variable-is-never-set");
+        assert_eq!(new[0].file, "src/Counter.sol");
+        assert_eq!(new[0].subject, "unused_number");
+        assert_eq!(new[0].line, 8);
+        assert_eq!(resolved.len(), 2);
+
+        let (new, resolved) = diff(&current, &current);
+        assert!(new.is_empty() && resolved.is_empty());
+    }
+
+    #[test]
+    fn test_findings_survive_line_shifts() {
+        let finding = |subject: &str, occurrence, line| Finding {
+            detector: "floating-pragma".to_string(),
+            file: "src/Counter.sol".to_string(),
+            subject: subject.to_string(),
+            occurrence,
+            line,
+        };
+        let previous = BTreeSet::from([finding("pragma solidity ^0.8.13;", 0, 2)]);
+
+        // A line added above the finding
+        let current = BTreeSet::from([finding("pragma solidity ^0.8.13;", 0, 3)]);
+        let (new, resolved) = diff(&previous, &current);
+        assert!(new.is_empty() && resolved.is_empty());
+
+        // The same text found a second time is a new finding
+        let current = BTreeSet::from([
+            finding("pragma solidity ^0.8.13;", 0, 2),
+            finding("pragma solidity ^0.8.13;", 1, 9),
+        ]);
+        let (new, resolved) = diff(&previous, &current);
+        assert_eq!(new.len(), 1);
+        assert_eq!(new[0].line, 9);
+        assert!(resolved.is_empty());
+    }
+}